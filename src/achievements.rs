@@ -0,0 +1,501 @@
+//! Module des succès pour un jeu Asteroids.
+//!
+//! Les succès sont décrits dans `achievements.ron`, intégré au programme : chacun a un
+//! identifiant, un nom, une description et une condition d'obtention. Le nom et la
+//! description sont des clés de traduction.
+//!
+//! ```text
+//! (
+//!     id: "untouchable",
+//!     name: "achievements.untouchable.name",
+//!     description: "achievements.untouchable.description",
+//!     condition: Win(difficulty: Some("hard"), max_hits: Some(0)),
+//! )
+//! ```
+//!
+//! Les conditions sont évaluées à partir des statistiques (`Stats`) qu'accumulent les
+//! événements de jeu pendant une partie, suivie par un `Run`. Les succès obtenus et le nombre d'astéroïdes
+//! détruits sur toutes les parties sont enregistrés dans `achievements.cfg` :
+//!
+//! ```text
+//! unlocked = first_win, survivor
+//! small = 120
+//! medium = 48
+//! large = 17
+//! ```
+
+use crate::events::GameEvent;
+use crate::level::Tier;
+use crate::locale::tr;
+use crate::render::Renderer;
+use crate::stats::Stats;
+use crate::view;
+use macroquad::prelude::*;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+
+/// Condition d'obtention d'un succès.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum Condition {
+    /// Détruire, sur l'ensemble des parties, un nombre d'astéroïdes d'une taille.
+    Destroy {
+        /// Taille des astéroïdes à détruire.
+        tier: Tier,
+        /// Nombre d'astéroïdes à détruire.
+        count: u32,
+    },
+    /// Rester en vie pendant une durée donnée d'une même partie, en secondes.
+    Survive(f32),
+    /// Gagner une partie, en respectant les limites indiquées.
+    Win {
+        /// Profil de difficulté imposé (`easy`, `medium` ou `hard`), ou `None` pour tous.
+        #[serde(default)]
+        difficulty: Option<String>,
+        /// Nombre maximal de collisions subies par le vaisseau.
+        #[serde(default)]
+        max_hits: Option<u32>,
+        /// Nombre maximal de missiles tirés.
+        #[serde(default)]
+        max_missiles: Option<u32>,
+    },
+}
+
+impl Condition {
+    /// Indique si la condition est remplie.
+    ///
+    /// # Paramètres
+    /// - `run`: La partie en cours ou terminée.
+    /// - `destroyed`: Nombre d'astéroïdes détruits lors des parties précédentes, par
+    ///   taille.
+    pub fn is_met(&self, run: &Run, destroyed: [u32; 3]) -> bool {
+        match self {
+            Condition::Destroy { tier, count } => {
+                destroyed[*tier as usize] + run.stats.destroyed[*tier as usize] >= *count
+            }
+            Condition::Survive(seconds) => run.stats.duration >= *seconds,
+            Condition::Win {
+                difficulty,
+                max_hits,
+                max_missiles,
+            } => {
+                run.won
+                    && difficulty
+                        .as_deref()
+                        .is_none_or(|name| run.difficulty == Some(name))
+                    && max_hits.is_none_or(|max| run.stats.collisions <= max)
+                    && max_missiles.is_none_or(|max| run.stats.shots <= max)
+            }
+        }
+    }
+}
+
+/// Succès, tel que décrit dans `achievements.ron`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Achievement {
+    /// Identifiant, enregistré dans le fichier de progression.
+    pub id: String,
+    /// Clé de traduction du nom affiché.
+    pub name: String,
+    /// Clé de traduction de la description affichée sous le nom.
+    pub description: String,
+    /// Condition d'obtention.
+    pub condition: Condition,
+}
+
+/// Déroulement d'une partie, accumulé à partir de ses événements.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Run {
+    /// Nom du profil de difficulté fourni utilisé, ou `None`.
+    pub difficulty: Option<&'static str>,
+    /// Statistiques de la partie.
+    pub stats: Stats,
+    /// Indique si la partie a été gagnée.
+    pub won: bool,
+    /// Indique si la partie a été faussée par la console (invincibilité ou échelle de
+    /// temps) : elle ne débloque alors aucun succès.
+    pub tainted: bool,
+}
+
+impl Run {
+    /// Commence le suivi d'une partie.
+    ///
+    /// # Paramètres
+    /// - `difficulty`: Le nom du profil de difficulté fourni, ou `None`.
+    pub fn new(difficulty: Option<&'static str>) -> Self {
+        Self {
+            difficulty,
+            ..Default::default()
+        }
+    }
+
+    /// Accumule les événements d'une frame.
+    ///
+    /// # Paramètres
+    /// - `events`: Les événements de la frame.
+    /// - `delta`: La durée de la frame, en secondes.
+    pub fn record(&mut self, events: &[GameEvent], delta: f32) {
+        self.stats.record(events, delta);
+    }
+}
+
+/// Succès et progression du joueur.
+pub struct Achievements {
+    /// Succès disponibles, dans l'ordre d'affichage.
+    definitions: Vec<Achievement>,
+    /// Identifiants des succès obtenus.
+    unlocked: Vec<String>,
+    /// Nombre d'astéroïdes détruits lors des parties terminées, par taille.
+    destroyed: [u32; 3],
+}
+
+impl Achievements {
+    /// Chemin du fichier de progression.
+    pub const FILE: &'static str = "achievements.cfg";
+    /// Description des succès, intégrée au programme.
+    pub const DEFINITIONS: &'static str = include_str!("../achievements.ron");
+
+    /// Crée une progression vierge.
+    ///
+    /// # Paramètres
+    /// - `definitions`: Les succès disponibles.
+    pub fn new(definitions: Vec<Achievement>) -> Self {
+        Self {
+            definitions,
+            unlocked: Vec::new(),
+            destroyed: [0; 3],
+        }
+    }
+
+    /// Charge les succès intégrés et la progression enregistrée.
+    ///
+    /// # Retour
+    /// Les succès, avec une progression vierge si le fichier n'existe pas.
+    pub fn load() -> Self {
+        let definitions = Self::parse_definitions(Self::DEFINITIONS).unwrap_or_else(|error| {
+            eprintln!("Succès invalides : {error}");
+            Vec::new()
+        });
+        let mut achievements = Self::new(definitions);
+        if let Ok(text) = fs::read_to_string(Self::FILE) {
+            achievements.parse(&text);
+        }
+        achievements
+    }
+
+    /// Lit la description des succès au format RON.
+    pub fn parse_definitions(text: &str) -> Result<Vec<Achievement>, String> {
+        ron::from_str(text).map_err(|error| error.to_string())
+    }
+
+    /// Enregistre la progression dans le fichier de progression.
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(Self::FILE, self.to_config())
+    }
+
+    /// Lit la progression depuis le contenu d'un fichier de progression.
+    ///
+    /// Les lignes vides, les commentaires (`#`) et les valeurs invalides sont ignorés.
+    pub fn parse(&mut self, text: &str) {
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (name, value) = (name.trim(), value.trim());
+            if name == "unlocked" {
+                self.unlocked = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(String::from)
+                    .collect();
+            } else if let Some(tier) = Tier::ALL.into_iter().find(|tier| tier.name() == name) {
+                if let Ok(count) = value.parse() {
+                    self.destroyed[tier as usize] = count;
+                }
+            }
+        }
+    }
+
+    /// Écrit la progression sous la forme du fichier de progression.
+    pub fn to_config(&self) -> String {
+        let mut text = format!("unlocked = {}\n", self.unlocked.join(", "));
+        for tier in Tier::ALL {
+            text += &format!("{} = {}\n", tier.name(), self.destroyed[tier as usize]);
+        }
+        text
+    }
+
+    /// Indique si un succès a été obtenu.
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    /// Débloque les succès dont la condition est remplie par une partie.
+    ///
+    /// # Paramètres
+    /// - `run`: La partie en cours ou terminée.
+    ///
+    /// # Retour
+    /// Les succès obtenus lors de cet appel, aucun si la partie est faussée.
+    pub fn update(&mut self, run: &Run) -> Vec<Achievement> {
+        if run.tainted {
+            return Vec::new();
+        }
+        let unlocked: Vec<Achievement> = self
+            .definitions
+            .iter()
+            .filter(|achievement| {
+                !self.is_unlocked(&achievement.id)
+                    && achievement.condition.is_met(run, self.destroyed)
+            })
+            .cloned()
+            .collect();
+        self.unlocked
+            .extend(unlocked.iter().map(|achievement| achievement.id.clone()));
+        unlocked
+    }
+
+    /// Ajoute les astéroïdes détruits d'une partie terminée à la progression, sauf si
+    /// elle est faussée.
+    ///
+    /// # Paramètres
+    /// - `run`: La partie terminée.
+    pub fn end_run(&mut self, run: &Run) {
+        if run.tainted {
+            return;
+        }
+        for (total, count) in self.destroyed.iter_mut().zip(run.stats.destroyed) {
+            *total += count;
+        }
+    }
+
+    /// Retourne l'avancement d'un succès à compter d'astéroïdes.
+    ///
+    /// # Retour
+    /// Le nombre d'astéroïdes détruits et le nombre requis, ou `None` pour les autres
+    /// conditions.
+    pub fn progress(&self, achievement: &Achievement) -> Option<(u32, u32)> {
+        match achievement.condition {
+            Condition::Destroy { tier, count } => {
+                Some((self.destroyed[tier as usize].min(count), count))
+            }
+            _ => None,
+        }
+    }
+
+    /// Dessine la liste des succès, avec leur état ou leur avancement.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    ///
+    /// # Paramètres
+    /// - `top`: Ordonnée de la première ligne.
+    pub fn draw_list(&self, top: f32) {
+        let left = view::WIDTH * 0.2;
+        let right = view::WIDTH * 0.8;
+        let mut y = top;
+        for achievement in &self.definitions {
+            let unlocked = self.is_unlocked(&achievement.id);
+            let (color, detail) = match self.progress(achievement) {
+                _ if unlocked => (GOLD, tr("achievements.unlocked").to_string()),
+                Some((done, count)) => (LIGHTGRAY, format!("{done}/{count}")),
+                None => (LIGHTGRAY, tr("achievements.locked").to_string()),
+            };
+            draw_text(tr(&achievement.name), left, y, 30.0, color);
+            draw_text(tr(&achievement.description), left, y + 24.0, 20.0, GRAY);
+            let size = measure_text(&detail, None, 24, 1.0);
+            draw_text(&detail, right - size.width, y, 24.0, color);
+            y += 64.0;
+        }
+    }
+}
+
+/// Notifications des succès obtenus, affichées l'une après l'autre.
+pub struct Toasts {
+    /// Clés des noms des succès à annoncer, et durée d'affichage restante du premier.
+    queue: VecDeque<(String, f32)>,
+}
+
+impl Toasts {
+    /// Durée d'affichage d'une notification, en secondes.
+    pub const DURATION: f32 = 3.0;
+    /// Dimensions d'une notification.
+    pub const SIZE: Vec2 = vec2(320.0, 64.0);
+
+    /// Crée une file de notifications vide.
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Annonce les succès obtenus.
+    pub fn push(&mut self, unlocked: &[Achievement]) {
+        self.queue.extend(
+            unlocked
+                .iter()
+                .map(|achievement| (achievement.name.clone(), Self::DURATION)),
+        );
+    }
+
+    /// Fait avancer l'affichage de la notification en cours.
+    ///
+    /// # Paramètres
+    /// - `delta`: La durée de la frame, en secondes.
+    pub fn update(&mut self, delta: f32) {
+        if let Some((_, remaining)) = self.queue.front_mut() {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                self.queue.pop_front();
+            }
+        }
+    }
+
+    /// Dessine la notification en cours en bas au centre de la vue.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        let Some((name, _)) = self.queue.front() else {
+            return;
+        };
+        let corner = vec2(
+            (view::WIDTH - Self::SIZE.x) / 2.0,
+            view::HEIGHT - Self::SIZE.y - 80.0,
+        );
+        renderer.rectangle(corner, Self::SIZE, Color::new(0.0, 0.0, 0.0, 0.8));
+        renderer.rectangle_lines(corner, Self::SIZE, 2.0, GOLD);
+        renderer.text(
+            tr("achievements.toast"),
+            corner + vec2(12.0, 24.0),
+            20.0,
+            LIGHTGRAY,
+        );
+        renderer.text(tr(name), corner + vec2(12.0, 52.0), 28.0, GOLD);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asteroid::Asteroid;
+
+    fn destroyed(size: f32) -> GameEvent {
+        GameEvent::AsteroidDestroyed {
+            size,
+            position: Vec2::ZERO,
+        }
+    }
+
+    fn fired() -> GameEvent {
+        GameEvent::MissileFired {
+            position: Vec2::ZERO,
+        }
+    }
+
+    fn shipped() -> Achievements {
+        Achievements::new(Achievements::parse_definitions(Achievements::DEFINITIONS).unwrap())
+    }
+
+    #[test]
+    fn test_shipped_definitions() {
+        let achievements = shipped();
+        let ids: Vec<&str> = achievements
+            .definitions
+            .iter()
+            .map(|achievement| achievement.id.as_str())
+            .collect();
+
+        assert!(ids.contains(&"untouchable") && ids.contains(&"sharpshooter"));
+        assert_eq!(
+            achievements.definitions[1].condition,
+            Condition::Destroy {
+                tier: Tier::Small,
+                count: 100
+            }
+        );
+    }
+
+    #[test]
+    fn test_clean_hard_win_from_event_stream() {
+        let mut achievements = shipped();
+        let mut run = Run::new(Some("hard"));
+        let stream = [
+            vec![fired(), fired()],
+            vec![destroyed(Asteroid::LARGE)],
+            vec![fired(), destroyed(Asteroid::MEDIUM)],
+        ];
+        for events in &stream {
+            run.record(events, 1.0 / 60.0);
+            assert!(achievements.update(&run).is_empty());
+        }
+        run.won = true;
+
+        let unlocked: Vec<String> = achievements
+            .update(&run)
+            .into_iter()
+            .map(|achievement| achievement.id)
+            .collect();
+        assert_eq!(unlocked, ["first_win", "untouchable", "sharpshooter"]);
+        assert!(achievements.update(&run).is_empty());
+    }
+
+    #[test]
+    fn test_tainted_run_unlocks_nothing() {
+        let mut achievements = shipped();
+        let mut run = Run::new(Some("hard"));
+        run.record(&[destroyed(Asteroid::LARGE)], 301.0);
+        run.won = true;
+        run.tainted = true;
+
+        assert!(achievements.update(&run).is_empty());
+        achievements.end_run(&run);
+        assert_eq!(achievements.destroyed, [0; 3]);
+    }
+
+    #[test]
+    fn test_hits_and_missiles_break_limits() {
+        let mut achievements = shipped();
+        let mut run = Run::new(Some("hard"));
+        run.record(
+            &[GameEvent::SpaceshipHit {
+                position: Vec2::ZERO,
+            }],
+            0.0,
+        );
+        run.record(&[fired(); 11], 301.0);
+        run.won = true;
+
+        achievements.update(&run);
+        assert!(achievements.is_unlocked("first_win"));
+        assert!(achievements.is_unlocked("survivor"));
+        assert!(!achievements.is_unlocked("untouchable"));
+        assert!(!achievements.is_unlocked("sharpshooter"));
+    }
+
+    #[test]
+    fn test_destroy_progress_carries_over() {
+        let mut achievements = shipped();
+        achievements.parse("unlocked = survivor\nsmall = 95\nlarge = oops\n");
+        let mut run = Run::new(None);
+        run.record(&[destroyed(Asteroid::SMALL); 4], 0.0);
+        assert!(achievements.update(&run).is_empty());
+
+        run.record(&[destroyed(Asteroid::SMALL)], 0.0);
+        let unlocked = achievements.update(&run);
+        assert_eq!(unlocked[0].id, "pebble_crusher");
+
+        achievements.end_run(&run);
+        assert_eq!(
+            achievements.to_config(),
+            "unlocked = survivor, pebble_crusher\nsmall = 100\nmedium = 0\nlarge = 0\n"
+        );
+    }
+}
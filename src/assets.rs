@@ -0,0 +1,149 @@
+//! Module des ressources graphiques pour un jeu Asteroids.
+//!
+//! Les images du jeu sont intégrées au binaire, qui peut donc être lancé depuis
+//! n'importe quel répertoire. Un répertoire optionnel (option `--assets <répertoire>`)
+//! permet de les remplacer par des fichiers du même nom. Si une image ne peut pas être
+//! décodée, une image de remplacement est générée pour que le jeu reste jouable.
+
+use macroquad::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Ressources graphiques chargées pour le jeu.
+pub struct Assets {
+    /// Texture du vaisseau spatial.
+    pub spaceship: Texture2D,
+    /// Image de fond, dessinée derrière les étoiles.
+    pub background: Texture2D,
+}
+
+impl Assets {
+    /// Image du vaisseau spatial intégrée au binaire.
+    const SPACESHIP: &'static [u8] = include_bytes!("../img/spaceship.png");
+    /// Image de fond intégrée au binaire.
+    const BACKGROUND: &'static [u8] = include_bytes!("../img/asteroide.png");
+
+    /// Charge toutes les ressources graphiques.
+    ///
+    /// # Paramètres
+    /// - `directory`: Répertoire dont les fichiers remplacent les images intégrées.
+    pub fn load(directory: Option<&Path>) -> Self {
+        let spaceship = Texture2D::from_image(&load_image(
+            directory,
+            "spaceship.png",
+            Self::SPACESHIP,
+            placeholder_spaceship,
+        ));
+        let background = Texture2D::from_image(&load_image(
+            directory,
+            "asteroide.png",
+            Self::BACKGROUND,
+            placeholder_background,
+        ));
+        background.set_filter(FilterMode::Nearest);
+
+        Self {
+            spaceship,
+            background,
+        }
+    }
+}
+
+/// Charge une image depuis le répertoire de remplacement ou, à défaut, depuis le binaire.
+///
+/// # Paramètres
+/// - `directory`: Répertoire de remplacement optionnel.
+/// - `name`: Nom du fichier de l'image.
+/// - `embedded`: Contenu de l'image intégrée au binaire.
+/// - `placeholder`: Générateur de l'image de remplacement.
+///
+/// # Retour
+/// L'image décodée, ou l'image de remplacement si le décodage échoue.
+fn load_image(
+    directory: Option<&Path>,
+    name: &str,
+    embedded: &[u8],
+    placeholder: fn() -> Image,
+) -> Image {
+    let bytes = directory
+        .and_then(|directory| fs::read(directory.join(name)).ok())
+        .unwrap_or_else(|| embedded.to_vec());
+
+    match Image::from_file_with_format(&bytes, None) {
+        Ok(image) => image,
+        Err(error) => {
+            eprintln!("Impossible de décoder {name} ({error}) : image de remplacement utilisée");
+            placeholder()
+        }
+    }
+}
+
+/// Génère un vaisseau de remplacement : un triangle blanc pointant vers la droite.
+fn placeholder_spaceship() -> Image {
+    let size = 64;
+    let mut image = Image::gen_image_color(size, size, BLANK);
+    for y in 0..size {
+        // La demi-largeur du triangle diminue de la base (à gauche) vers la pointe (à droite)
+        let distance = (y as f32 - size as f32 / 2.0).abs();
+        for x in 0..size {
+            if distance <= (size - x) as f32 / 2.0 {
+                image.set_pixel(x as u32, y as u32, WHITE);
+            }
+        }
+    }
+    image
+}
+
+/// Génère un fond de remplacement : un dégradé bleu nuit.
+fn placeholder_background() -> Image {
+    let size = 256;
+    let mut image = Image::gen_image_color(size, size, BLACK);
+    for y in 0..size {
+        let shade = y as f32 / size as f32;
+        let color = Color::new(0.02, 0.02 + shade * 0.05, 0.08 + shade * 0.12, 1.0);
+        for x in 0..size {
+            image.set_pixel(x as u32, y as u32, color);
+        }
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_images_decode() {
+        let image = load_image(
+            None,
+            "spaceship.png",
+            Assets::SPACESHIP,
+            placeholder_spaceship,
+        );
+        assert!(image.width() > 0 && image.height() > 0);
+        assert!(Image::from_file_with_format(Assets::BACKGROUND, None).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_image_uses_placeholder() {
+        let image = load_image(None, "broken.png", b"not an image", placeholder_spaceship);
+
+        assert_eq!((image.width(), image.height()), (64, 64));
+        assert_eq!(image.get_pixel(2, 32), WHITE);
+        assert_eq!(image.get_pixel(62, 2), BLANK);
+    }
+
+    #[test]
+    fn test_missing_override_uses_embedded() {
+        let directory = Path::new("/nonexistent-asteroids-assets");
+        let image = load_image(
+            Some(directory),
+            "spaceship.png",
+            Assets::SPACESHIP,
+            placeholder_spaceship,
+        );
+
+        let embedded = Image::from_file_with_format(Assets::SPACESHIP, None).unwrap();
+        assert_eq!(image.width(), embedded.width());
+    }
+}
@@ -0,0 +1,436 @@
+//! Module du son pour un jeu Asteroids.
+//!
+//! Ce module définit le gestionnaire audio (`AudioManager`), qui joue les effets sonores
+//! et la musique de fond à partir des événements de jeu (`GameEvent`). Les fonctions de
+//! jeu ne jouent jamais de son directement : elles signalent ce qui s'est passé et le
+//! gestionnaire décide des sons à jouer.
+//!
+//! Les sons sont synthétisés au lancement du jeu, ce qui évite de dépendre de fichiers
+//! audio externes.
+//!
+//! ## Fichier de configuration
+//! Les volumes sont lus depuis `audio.cfg` :
+//!
+//! ```text
+//! master = 1
+//! effects = 0.8
+//! music = 0.5
+//! muted = false
+//! ```
+
+use crate::{asteroid::Asteroid, events::GameEvent};
+use macroquad::audio::{
+    load_sound_from_bytes, play_sound, set_sound_volume, PlaySoundParams, Sound,
+};
+use std::f32::consts::PI;
+use std::fs;
+use std::io;
+
+/// Volumes des canaux audio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioSettings {
+    /// Volume général, de `0.0` à `1.0`.
+    pub master: f32,
+    /// Volume des effets sonores, de `0.0` à `1.0`.
+    pub effects: f32,
+    /// Volume de la musique, de `0.0` à `1.0`.
+    pub music: f32,
+    /// Indique si le son est coupé.
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            effects: 0.8,
+            music: 0.5,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Chemin du fichier de configuration du son.
+    pub const FILE: &'static str = "audio.cfg";
+
+    /// Charge les volumes depuis le fichier de configuration.
+    ///
+    /// # Retour
+    /// Les volumes lus, ou les volumes par défaut si le fichier n'existe pas.
+    pub fn load() -> Self {
+        fs::read_to_string(Self::FILE)
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    /// Enregistre les volumes dans le fichier de configuration.
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(Self::FILE, self.to_config())
+    }
+
+    /// Lit les volumes depuis le contenu d'un fichier de configuration.
+    ///
+    /// Les lignes vides, les commentaires (`#`) et les valeurs invalides sont ignorés.
+    pub fn parse(text: &str) -> Self {
+        let mut settings = Self::default();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            let volume = match name.trim() {
+                "master" => &mut settings.master,
+                "effects" => &mut settings.effects,
+                "music" => &mut settings.music,
+                "muted" => {
+                    if let Ok(muted) = value.parse() {
+                        settings.muted = muted;
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            if let Ok(parsed) = value.parse::<f32>() {
+                *volume = parsed.clamp(0.0, 1.0);
+            }
+        }
+
+        settings
+    }
+
+    /// Écrit les volumes sous la forme du fichier de configuration.
+    pub fn to_config(self) -> String {
+        format!(
+            "master = {}\neffects = {}\nmusic = {}\nmuted = {}\n",
+            self.master, self.effects, self.music, self.muted
+        )
+    }
+
+    /// Retourne le volume effectif des effets sonores.
+    pub fn effects_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master * self.effects
+        }
+    }
+
+    /// Retourne le volume effectif de la musique.
+    pub fn music_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master * self.music
+        }
+    }
+}
+
+/// Gestionnaire des effets sonores et de la musique.
+pub struct AudioManager {
+    /// Volumes des canaux audio.
+    settings: AudioSettings,
+    /// Son d'un tir de missile.
+    fire: Sound,
+    /// Bruit du moteur, joué en boucle pendant la propulsion.
+    engine: Sound,
+    /// Explosions des grands, moyens et petits astéroïdes.
+    explosions: [Sound; 3],
+    /// Son d'un impact sur le vaisseau.
+    hit: Sound,
+    /// Jingle de fin de partie.
+    game_over: Sound,
+    /// Musique de fond, jouée en boucle.
+    music: Sound,
+}
+
+impl AudioManager {
+    /// Synthétise et charge tous les sons, puis lance la musique et le bruit du moteur.
+    ///
+    /// # Paramètres
+    /// - `settings`: Les volumes des canaux audio.
+    pub async fn new(settings: AudioSettings) -> Self {
+        let manager = Self {
+            settings,
+            fire: load(&synth::fire()).await,
+            engine: load(&synth::engine()).await,
+            explosions: [
+                load(&synth::explosion(0.9, 0.95)).await,
+                load(&synth::explosion(0.6, 0.85)).await,
+                load(&synth::explosion(0.35, 0.6)).await,
+            ],
+            hit: load(&synth::hit()).await,
+            game_over: load(&synth::game_over()).await,
+            music: load(&synth::music()).await,
+        };
+
+        // Le moteur tourne en permanence : son volume suit l'intensité de la propulsion
+        play_sound(
+            &manager.engine,
+            PlaySoundParams {
+                looped: true,
+                volume: 0.0,
+            },
+        );
+        play_sound(
+            &manager.music,
+            PlaySoundParams {
+                looped: true,
+                volume: settings.music_volume(),
+            },
+        );
+        manager
+    }
+
+    /// Retourne les volumes des canaux audio.
+    pub fn get_settings(&self) -> AudioSettings {
+        self.settings
+    }
+
+    /// Applique de nouveaux volumes et les enregistre.
+    pub fn set_settings(&mut self, settings: AudioSettings) {
+        self.settings = settings;
+        set_sound_volume(&self.music, self.settings.music_volume());
+        if let Err(error) = self.settings.save() {
+            eprintln!("Impossible d'enregistrer {}: {error}", AudioSettings::FILE);
+        }
+    }
+
+    /// Coupe ou rétablit le son et enregistre le réglage.
+    pub fn toggle_mute(&mut self) {
+        let mut settings = self.settings;
+        settings.muted = !settings.muted;
+        self.set_settings(settings);
+    }
+
+    /// Joue les sons correspondant aux événements d'une frame.
+    ///
+    /// Le bruit du moteur suit la propulsion signalée pendant la frame : il se tait
+    /// lorsqu'aucun événement de propulsion n'est reçu.
+    ///
+    /// # Paramètres
+    /// - `events`: Les événements survenus pendant la frame.
+    pub fn update(&self, events: &[GameEvent]) {
+        let volume = self.settings.effects_volume();
+        let mut thrust: f32 = 0.0;
+
+        for event in events {
+            let sound = match *event {
+                GameEvent::AsteroidDestroyed { size, .. } => &self.explosions[explosion_tier(size)],
+                GameEvent::SpaceshipHit { .. } => &self.hit,
+                GameEvent::MissileFired { .. } => &self.fire,
+                GameEvent::GameOver => &self.game_over,
+                GameEvent::Thrust { intensity, .. } => {
+                    thrust = thrust.max(intensity);
+                    continue;
+                }
+            };
+            play_sound(
+                sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume,
+                },
+            );
+        }
+
+        set_sound_volume(&self.engine, volume * thrust * 0.6);
+    }
+
+    /// Fait taire le bruit du moteur, par exemple pendant la pause ou en fin de partie.
+    pub fn stop_engine(&self) {
+        set_sound_volume(&self.engine, 0.0);
+    }
+}
+
+/// Retourne l'indice du son d'explosion correspondant à la taille d'un astéroïde.
+fn explosion_tier(size: f32) -> usize {
+    if size >= Asteroid::LARGE {
+        0
+    } else if size >= Asteroid::MEDIUM {
+        1
+    } else {
+        2
+    }
+}
+
+/// Charge un son depuis des données WAV synthétisées.
+async fn load(wav: &[u8]) -> Sound {
+    load_sound_from_bytes(wav)
+        .await
+        .expect("un son synthétisé doit toujours être lisible")
+}
+
+/// Synthèse des effets sonores et de la musique au format WAV.
+mod synth {
+    use super::PI;
+
+    /// Fréquence d'échantillonnage des sons synthétisés.
+    pub const SAMPLE_RATE: u32 = 22050;
+
+    /// Générateur de bruit blanc déterministe.
+    struct Noise(u32);
+
+    impl Noise {
+        /// Retourne un échantillon de bruit entre `-1.0` et `1.0`.
+        fn next(&mut self) -> f32 {
+            self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (self.0 >> 8) as f32 / (1 << 23) as f32 - 1.0
+        }
+    }
+
+    /// Génère `duration` secondes d'échantillons à partir du temps écoulé.
+    fn render(duration: f32, mut sample: impl FnMut(f32) -> f32) -> Vec<f32> {
+        let count = (duration * SAMPLE_RATE as f32) as usize;
+        (0..count)
+            .map(|i| sample(i as f32 / SAMPLE_RATE as f32))
+            .collect()
+    }
+
+    /// Onde carrée de phase `phase` (en tours).
+    fn square(phase: f32) -> f32 {
+        if phase.fract() < 0.5 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    /// Son de tir : onde carrée dont la fréquence chute rapidement.
+    pub fn fire() -> Vec<u8> {
+        let duration = 0.15;
+        let mut phase = 0.0;
+        wav(&render(duration, |t| {
+            let progress = t / duration;
+            phase += (1200.0 - 900.0 * progress) / SAMPLE_RATE as f32;
+            square(phase) * 0.3 * (1.0 - progress)
+        }))
+    }
+
+    /// Bruit du moteur : bruit grave, destiné à être joué en boucle.
+    pub fn engine() -> Vec<u8> {
+        let mut noise = Noise(7);
+        let mut filtered = 0.0;
+        wav(&render(0.5, |_| {
+            filtered = filtered * 0.92 + noise.next() * 0.08;
+            filtered * 4.0
+        }))
+    }
+
+    /// Explosion : bruit filtré qui s'éteint progressivement.
+    ///
+    /// # Paramètres
+    /// - `duration`: Durée de l'explosion en secondes.
+    /// - `smoothing`: Lissage du bruit, de `0.0` (aigu) à `1.0` (grave).
+    pub fn explosion(duration: f32, smoothing: f32) -> Vec<u8> {
+        let mut noise = Noise(42);
+        let mut filtered = 0.0;
+        let gain = 1.0 / (1.0 - smoothing).sqrt();
+        wav(&render(duration, |t| {
+            filtered = filtered * smoothing + noise.next() * (1.0 - smoothing);
+            let decay = 1.0 - t / duration;
+            filtered * gain * 0.8 * decay * decay
+        }))
+    }
+
+    /// Impact sur le vaisseau : dent de scie descendante mêlée de bruit.
+    pub fn hit() -> Vec<u8> {
+        let duration = 0.4;
+        let mut noise = Noise(3);
+        let mut phase: f32 = 0.0;
+        wav(&render(duration, |t| {
+            let progress = t / duration;
+            phase += (220.0 - 140.0 * progress) / SAMPLE_RATE as f32;
+            let saw = phase.fract() * 2.0 - 1.0;
+            (saw * 0.4 + noise.next() * 0.2) * (1.0 - progress)
+        }))
+    }
+
+    /// Jingle de fin de partie : quatre notes descendantes.
+    pub fn game_over() -> Vec<u8> {
+        let notes = [392.0, 330.0, 262.0, 196.0];
+        let note_length = 0.3;
+        wav(&render(note_length * notes.len() as f32, |t| {
+            let index = ((t / note_length) as usize).min(notes.len() - 1);
+            let local = t - index as f32 * note_length;
+            let triangle = (2.0 * PI * notes[index] * t).sin().asin() * 2.0 / PI;
+            triangle * 0.4 * (1.0 - local / note_length)
+        }))
+    }
+
+    /// Musique de fond : basse en arpège sur quatre accords, jouée en boucle.
+    pub fn music() -> Vec<u8> {
+        let roots = [110.0, 87.31, 130.81, 98.0];
+        let pattern = [1.0, 1.0, 2.0, 1.0, 1.5, 1.0, 2.0, 1.5];
+        let step = 0.25;
+        let length = step * (roots.len() * pattern.len()) as f32;
+        wav(&render(length, |t| {
+            let index = (t / step) as usize % (roots.len() * pattern.len());
+            let frequency = roots[index / pattern.len()] * pattern[index % pattern.len()];
+            let local = t - index as f32 * step;
+            square(frequency * t) * 0.25 * (1.0 - local / step).powi(2)
+        }))
+    }
+
+    /// Encode des échantillons mono entre `-1.0` et `1.0` au format WAV 16 bits.
+    pub fn wav(samples: &[f32]) -> Vec<u8> {
+        let data_size = (samples.len() * 2) as u32;
+        let mut bytes = Vec::with_capacity(44 + samples.len() * 2);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        for sample in samples {
+            let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_settings_round_trip() {
+        let settings = AudioSettings::parse("master = 0.5\nmusic = 2\nmuted = true\neffects = x\n");
+
+        assert_eq!(settings.master, 0.5);
+        assert_eq!(settings.music, 1.0);
+        assert_eq!(settings.effects, AudioSettings::default().effects);
+        assert!(settings.muted);
+        assert_eq!(settings.effects_volume(), 0.0);
+        assert_eq!(AudioSettings::parse(&settings.to_config()), settings);
+    }
+
+    #[test]
+    fn test_explosion_tier() {
+        assert_eq!(explosion_tier(Asteroid::LARGE), 0);
+        assert_eq!(explosion_tier(Asteroid::MEDIUM), 1);
+        assert_eq!(explosion_tier(Asteroid::SMALL), 2);
+    }
+
+    #[test]
+    fn test_wav_header() {
+        let bytes = synth::wav(&[0.0, 1.0, -1.0]);
+
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(bytes.len(), 44 + 6);
+        assert_eq!(i16::from_le_bytes([bytes[46], bytes[47]]), i16::MAX);
+    }
+}
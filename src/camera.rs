@@ -0,0 +1,216 @@
+//! Module de la caméra pour un jeu Asteroids.
+//!
+//! Ce module définit la caméra qui suit le vaisseau dans un monde plus grand que la
+//! fenêtre. Elle tremble lors des impacts selon un modèle de « traumatisme » : chaque
+//! impact ajoute du traumatisme, qui diminue progressivement, et l'amplitude du
+//! tremblement est proportionnelle à son carré. Le module dessine aussi la minicarte.
+
+use crate::{
+    asteroid::Asteroid, events::GameEvent, hud::Anchor, render::Renderer, spaceship::Spaceship,
+    view,
+};
+use ::rand::{rngs::SmallRng, Rng, SeedableRng};
+use macroquad::prelude::*;
+
+/// Caméra qui suit le vaisseau spatial dans le monde.
+pub struct GameCamera {
+    /// Dimensions du monde.
+    world: Vec2,
+    /// Centre de la vue dans le monde, sans tremblement.
+    center: Vec2,
+    /// Traumatisme actuel, de `0.0` (aucun) à `1.0` (maximal).
+    trauma: f32,
+    /// Décalage du tremblement appliqué à la vue.
+    shake: Vec2,
+    /// Générateur aléatoire du tremblement.
+    rng: SmallRng,
+}
+
+impl GameCamera {
+    /// Dimensions du monde par défaut.
+    pub const WORLD_SIZE: Vec2 = vec2(2400.0, 1800.0);
+    /// Décalage maximal du tremblement, en pixels.
+    pub const MAX_SHAKE: f32 = 18.0;
+    /// Diminution du traumatisme à chaque frame.
+    pub const TRAUMA_DECAY: f32 = 0.02;
+    /// Traumatisme ajouté lorsque le vaisseau est touché.
+    pub const HIT_TRAUMA: f32 = 0.6;
+    /// Traumatisme ajouté par la destruction d'un grand astéroïde.
+    pub const EXPLOSION_TRAUMA: f32 = 0.25;
+    /// Largeur de la minicarte, en fraction de la largeur de la fenêtre.
+    pub const MINIMAP_WIDTH: f32 = 0.18;
+
+    /// Crée une caméra centrée sur le monde.
+    ///
+    /// # Paramètres
+    /// - `world`: Les dimensions du monde.
+    pub fn new(world: Vec2) -> Self {
+        Self {
+            world,
+            center: world / 2.0,
+            trauma: 0.0,
+            shake: Vec2::ZERO,
+            rng: SmallRng::seed_from_u64(0),
+        }
+    }
+
+    /// Ajoute du traumatisme, limité à `1.0`.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Ajoute le traumatisme correspondant aux impacts d'une frame.
+    ///
+    /// # Paramètres
+    /// - `events`: Les événements survenus pendant la frame.
+    pub fn handle_events(&mut self, events: &[GameEvent]) {
+        for event in events {
+            match *event {
+                GameEvent::SpaceshipHit { .. } => self.add_trauma(Self::HIT_TRAUMA),
+                GameEvent::AsteroidDestroyed { size, .. } => {
+                    self.add_trauma(Self::EXPLOSION_TRAUMA * size / Asteroid::LARGE)
+                }
+                GameEvent::MissileFired { .. } | GameEvent::Thrust { .. } | GameEvent::GameOver => {
+                }
+            }
+        }
+    }
+
+    /// Suit la cible et fait évoluer le tremblement d'une frame.
+    ///
+    /// # Paramètres
+    /// - `target`: La position suivie, généralement celle du vaisseau.
+    pub fn update(&mut self, target: Vec2) {
+        self.center = follow(target, vec2(view::WIDTH, view::HEIGHT), self.world);
+
+        let amplitude = Self::MAX_SHAKE * self.trauma * self.trauma;
+        self.shake = vec2(
+            self.rng.gen_range(-1.0..=1.0),
+            self.rng.gen_range(-1.0..=1.0),
+        ) * amplitude;
+        self.trauma = (self.trauma - Self::TRAUMA_DECAY).max(0.0);
+    }
+
+    /// Retourne la caméra Macroquad correspondant à la vue actuelle.
+    pub fn get_camera(&self) -> Camera2D {
+        let size = vec2(view::WIDTH, view::HEIGHT);
+        let corner = self.center + self.shake - size / 2.0;
+        view::camera(Rect::new(corner.x, corner.y, size.x, size.y))
+    }
+
+    /// Convertit une position de la fenêtre (en pixels) en position dans le monde.
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.get_camera().screen_to_world(point)
+    }
+
+    /// Dessine la minicarte du monde entier dans le coin supérieur droit de la fenêtre.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    /// - `asteroids`: Liste des astéroïdes.
+    /// - `spaceship`: Le vaisseau spatial.
+    pub fn draw_minimap(
+        &self,
+        renderer: &mut dyn Renderer,
+        asteroids: &[Asteroid],
+        spaceship: &Spaceship,
+    ) {
+        let width = view::WIDTH * Self::MINIMAP_WIDTH;
+        let scale = width / self.world.x;
+        let height = self.world.y * scale;
+        let origin = Anchor::TopRight.place(
+            vec2(width, height),
+            vec2(10.0, 10.0),
+            vec2(view::WIDTH, view::HEIGHT),
+        );
+
+        let area = vec2(width, height);
+        renderer.rectangle(origin, area, Color::from_rgba(0, 0, 0, 160));
+        renderer.rectangle_lines(origin, area, 1.0, GRAY);
+
+        let size = vec2(view::WIDTH, view::HEIGHT) * scale;
+        let corner = origin + self.center * scale - size / 2.0;
+        renderer.rectangle_lines(corner, size, 1.0, DARKGRAY);
+
+        for asteroid in asteroids {
+            let point = origin + asteroid.get_position() * scale;
+            let radius = (asteroid.get_size() / 2.0 * scale).max(1.0);
+            renderer.circle(point, radius, YELLOW);
+        }
+        renderer.circle(origin + spaceship.get_position() * scale, 2.5, SKYBLUE);
+    }
+}
+
+/// Calcule le centre de la vue qui suit une cible sans sortir du monde.
+///
+/// Si le monde est plus petit que la vue sur un axe, la vue est centrée sur le monde.
+///
+/// # Paramètres
+/// - `target`: La position suivie.
+/// - `view`: Les dimensions de la vue.
+/// - `world`: Les dimensions du monde.
+fn follow(target: Vec2, view: Vec2, world: Vec2) -> Vec2 {
+    let axis = |target: f32, view: f32, world: f32| {
+        if world <= view {
+            world / 2.0
+        } else {
+            target.clamp(view / 2.0, world - view / 2.0)
+        }
+    };
+    vec2(
+        axis(target.x, view.x, world.x),
+        axis(target.y, view.y, world.y),
+    )
+}
+
+/// Lit des dimensions de monde au format `<largeur>x<hauteur>`.
+///
+/// # Retour
+/// Les dimensions lues, ou `None` si le texte est invalide ou les dimensions nulles.
+pub fn parse_world_size(text: &str) -> Option<Vec2> {
+    let (width, height) = text.split_once('x')?;
+    let size = vec2(width.trim().parse().ok()?, height.trim().parse().ok()?);
+    (size.x > 0.0 && size.y > 0.0).then_some(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_stays_in_world() {
+        let view = vec2(800.0, 600.0);
+        let world = vec2(2400.0, 1800.0);
+
+        assert_eq!(
+            follow(vec2(1000.0, 900.0), view, world),
+            vec2(1000.0, 900.0)
+        );
+        assert_eq!(follow(vec2(10.0, 1790.0), view, world), vec2(400.0, 1500.0));
+        assert_eq!(
+            follow(vec2(10.0, 10.0), view, vec2(600.0, 400.0)),
+            vec2(300.0, 200.0)
+        );
+    }
+
+    #[test]
+    fn test_trauma_accumulates() {
+        let mut camera = GameCamera::new(GameCamera::WORLD_SIZE);
+        camera.handle_events(&[GameEvent::SpaceshipHit {
+            position: vec2(0.0, 0.0),
+        }]);
+        assert_eq!(camera.trauma, GameCamera::HIT_TRAUMA);
+
+        camera.add_trauma(5.0);
+        assert_eq!(camera.trauma, 1.0);
+    }
+
+    #[test]
+    fn test_parse_world_size() {
+        assert_eq!(parse_world_size("3000x2000"), Some(vec2(3000.0, 2000.0)));
+        assert_eq!(parse_world_size("0x2000"), None);
+        assert_eq!(parse_world_size("big"), None);
+    }
+}
@@ -0,0 +1,308 @@
+//! Module de la console de développement pour un jeu Asteroids.
+//!
+//! La console s'ouvre par le haut de la fenêtre avec la touche accent grave (`` ` ``).
+//! Chaque ligne saisie est une commande suivie de ses arguments, séparés par des espaces.
+//!
+//! Les commandes sont enregistrées avec `Console::register` et reçoivent le contexte
+//! passé à la console : chaque système peut ainsi ajouter ses propres commandes. Trois
+//! commandes sont intégrées : `help`, `clear` et `load <fichier>`, qui exécute les
+//! commandes d'un fichier, une par ligne (les lignes commençant par `#` sont ignorées).
+
+use crate::view;
+use macroquad::prelude::*;
+use std::fs;
+
+/// Fonction exécutant une commande de la console.
+///
+/// Elle reçoit le contexte de la console et les arguments de la commande, et retourne
+/// le message à afficher ou une erreur.
+pub type Handler<C> = fn(&mut C, &[&str]) -> Result<String, String>;
+
+/// Commande enregistrée dans la console.
+struct Command<C> {
+    /// Nom de la commande.
+    name: &'static str,
+    /// Syntaxe de la commande, affichée par `help`.
+    usage: &'static str,
+    /// Fonction exécutant la commande.
+    handler: Handler<C>,
+}
+
+/// Console de développement qui agit sur un contexte de type `C`.
+pub struct Console<C> {
+    /// Indique si la console est ouverte.
+    open: bool,
+    /// Ligne en cours de saisie.
+    input: String,
+    /// Lignes affichées, de la plus ancienne à la plus récente.
+    lines: Vec<String>,
+    /// Commandes déjà saisies, pour les rappeler avec la flèche haut.
+    history: Vec<String>,
+    /// Position dans l'historique lors du rappel d'une commande.
+    recall: usize,
+    /// Commandes enregistrées.
+    commands: Vec<Command<C>>,
+}
+
+impl<C> Console<C> {
+    /// Nombre maximal de lignes conservées.
+    pub const MAX_LINES: usize = 100;
+    /// Profondeur maximale des fichiers chargés par `load`.
+    pub const MAX_DEPTH: usize = 8;
+    /// Hauteur de la console, en fraction de la hauteur de la vue.
+    pub const HEIGHT: f32 = 0.45;
+    /// Taille du texte de la console.
+    pub const FONT_SIZE: f32 = 20.0;
+
+    /// Crée une console fermée, sans autre commande que les commandes intégrées.
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            lines: Vec::new(),
+            history: Vec::new(),
+            recall: 0,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Enregistre une commande.
+    ///
+    /// # Paramètres
+    /// - `name`: Nom de la commande, premier mot de la ligne saisie.
+    /// - `usage`: Syntaxe et description courte, affichées par `help`.
+    /// - `handler`: Fonction exécutant la commande.
+    pub fn register(&mut self, name: &'static str, usage: &'static str, handler: Handler<C>) {
+        self.commands.retain(|command| command.name != name);
+        self.commands.push(Command {
+            name,
+            usage,
+            handler,
+        });
+    }
+
+    /// Indique si la console est ouverte.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Ajoute une ligne à l'affichage, en oubliant les plus anciennes.
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+        if self.lines.len() > Self::MAX_LINES {
+            self.lines.remove(0);
+        }
+    }
+
+    /// Exécute une ligne de commande et affiche son résultat.
+    ///
+    /// # Paramètres
+    /// - `line`: La ligne saisie.
+    /// - `context`: Le contexte sur lequel agissent les commandes.
+    pub fn execute(&mut self, line: &str, context: &mut C) {
+        self.print(format!("> {line}"));
+        self.run(line, context, 0);
+    }
+
+    /// Gère le clavier : ouverture, saisie, exécution et rappel des commandes.
+    ///
+    /// # Paramètres
+    /// - `context`: Le contexte sur lequel agissent les commandes.
+    pub fn update(&mut self, context: &mut C) {
+        if is_key_pressed(KeyCode::GraveAccent) {
+            self.open = !self.open;
+            // Le caractère de la touche d'ouverture ne doit pas être saisi
+            while get_char_pressed().is_some() {}
+            return;
+        }
+        if !self.open {
+            return;
+        }
+
+        while let Some(character) = get_char_pressed() {
+            if !character.is_control() && character != '`' {
+                self.input.push(character);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.open = false;
+        } else if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        } else if is_key_pressed(KeyCode::Up) && self.recall > 0 {
+            self.recall -= 1;
+            self.input = self.history[self.recall].clone();
+        } else if is_key_pressed(KeyCode::Enter) {
+            let line = std::mem::take(&mut self.input);
+            if !line.trim().is_empty() {
+                self.history.push(line.clone());
+            }
+            self.recall = self.history.len();
+            self.execute(&line, context);
+        }
+    }
+
+    /// Dessine la console, si elle est ouverte.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    pub fn draw(&self) {
+        if !self.open {
+            return;
+        }
+
+        let height = view::HEIGHT * Self::HEIGHT;
+        let line_height = Self::FONT_SIZE + 2.0;
+        draw_rectangle(
+            0.0,
+            0.0,
+            view::WIDTH,
+            height,
+            Color::from_rgba(0, 0, 0, 210),
+        );
+        draw_line(0.0, height, view::WIDTH, height, 1.0, GRAY);
+
+        let mut y = height - 10.0;
+        draw_text(
+            format!("> {}_", self.input),
+            10.0,
+            y,
+            Self::FONT_SIZE,
+            YELLOW,
+        );
+        for line in self.lines.iter().rev() {
+            y -= line_height;
+            if y < line_height {
+                break;
+            }
+            draw_text(line, 10.0, y, Self::FONT_SIZE, LIGHTGRAY);
+        }
+    }
+
+    /// Exécute une ligne de commande sans l'afficher.
+    ///
+    /// # Paramètres
+    /// - `line`: La ligne à exécuter.
+    /// - `context`: Le contexte sur lequel agissent les commandes.
+    /// - `depth`: Nombre de fichiers `load` imbriqués.
+    fn run(&mut self, line: &str, context: &mut C, depth: usize) {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return;
+        };
+        let args: Vec<&str> = words.collect();
+
+        match name {
+            "help" => {
+                self.print("help - list commands");
+                self.print("clear - clear the console");
+                self.print("load <file> - run the commands of a file");
+                let usages: Vec<String> = self
+                    .commands
+                    .iter()
+                    .map(|command| format!("{} {}", command.name, command.usage))
+                    .collect();
+                for usage in usages {
+                    self.print(usage);
+                }
+            }
+            "clear" => self.lines.clear(),
+            "load" => self.load(&args, context, depth),
+            _ => {
+                let Some(command) = self.commands.iter().find(|command| command.name == name)
+                else {
+                    self.print(format!("unknown command: {name} (try help)"));
+                    return;
+                };
+                match (command.handler)(context, &args) {
+                    Ok(message) if message.is_empty() => {}
+                    Ok(message) => self.print(message),
+                    Err(error) => self.print(format!("error: {error}")),
+                }
+            }
+        }
+    }
+
+    /// Exécute les commandes d'un fichier, une par ligne.
+    fn load(&mut self, args: &[&str], context: &mut C, depth: usize) {
+        let [path] = args else {
+            self.print("error: load <file>");
+            return;
+        };
+        if depth >= Self::MAX_DEPTH {
+            self.print(format!("error: {path}: too many nested loads"));
+            return;
+        }
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                for line in text.lines().map(str::trim) {
+                    if !line.is_empty() && !line.starts_with('#') {
+                        self.run(line, context, depth + 1);
+                    }
+                }
+            }
+            Err(error) => self.print(format!("error: {path}: {error}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(total: &mut i32, args: &[&str]) -> Result<String, String> {
+        let [value] = args else {
+            return Err("add <value>".to_string());
+        };
+        *total += value.parse::<i32>().map_err(|error| error.to_string())?;
+        Ok(format!("total {total}"))
+    }
+
+    #[test]
+    fn test_registered_command() {
+        let mut console = Console::new();
+        console.register("add", "<value> - add to the total", add);
+        let mut total = 0;
+
+        console.execute("add 5", &mut total);
+        console.execute("add", &mut total);
+        console.execute("fly", &mut total);
+
+        assert_eq!(total, 5);
+        assert_eq!(
+            console.lines,
+            [
+                "> add 5",
+                "total 5",
+                "> add",
+                "error: add <value>",
+                "> fly",
+                "unknown command: fly (try help)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_file() {
+        let path = std::env::temp_dir().join("asteroids-console-test.cfg");
+        fs::write(&path, "# commentaire\nadd 2\n\nadd 3\n").unwrap();
+        let mut console = Console::new();
+        console.register("add", "<value>", add);
+        let mut total = 0;
+
+        console.execute(&format!("load {}", path.display()), &mut total);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(total, 5);
+    }
+
+    #[test]
+    fn test_scrollback_is_bounded() {
+        let mut console: Console<()> = Console::new();
+        for i in 0..150 {
+            console.print(i.to_string());
+        }
+
+        assert_eq!(console.lines.len(), Console::<()>::MAX_LINES);
+        assert_eq!(console.lines[0], "50");
+    }
+}
@@ -0,0 +1,53 @@
+//! Module des commandes du vaisseau spatial.
+//!
+//! Ce module définit `ShipControl`, l'ensemble des commandes appliquées au vaisseau
+//! pendant une frame. Les commandes sont produites indifféremment par le joueur
+//! (clavier, manette, souris) ou par un pilote automatique, puis appliquées de la même façon.
+
+use crate::spaceship::Spaceship;
+use std::f32::consts::PI;
+
+/// Commandes appliquées au vaisseau spatial pendant une frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ShipControl {
+    /// Rotation demandée, de `-1.0` (pleine vitesse vers la gauche) à `1.0` (pleine vitesse vers la droite).
+    pub rotate: f32,
+    /// Intensité de la propulsion, de `0.0` (aucune) à `1.0` (pleine poussée).
+    pub thrust: f32,
+    /// Indique si un missile doit être tiré.
+    pub fire: bool,
+    /// Direction de tir (en radians) indépendante de l'orientation du vaisseau,
+    /// ou `None` pour tirer droit devant.
+    pub aim: Option<f32>,
+}
+
+/// Retourne l'écart angulaire signé entre deux angles, ramené dans `[-PI, PI]`.
+pub fn angle_diff(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// Convertit un écart angulaire en commande de rotation.
+///
+/// La commande est limitée à la vitesse de rotation du vaisseau : un grand écart
+/// donne une rotation à pleine vitesse, un petit écart une rotation juste suffisante.
+pub fn steer(diff: f32) -> f32 {
+    (diff / Spaceship::ROTATION_SPEED).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle_diff() {
+        assert!((angle_diff(0.0, PI / 2.0) - PI / 2.0).abs() < 1e-5);
+        assert!((angle_diff(0.1, 2.0 * PI - 0.1) + 0.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_steer_is_capped() {
+        assert_eq!(steer(PI), 1.0);
+        assert_eq!(steer(-PI), -1.0);
+        assert!((steer(Spaceship::ROTATION_SPEED / 2.0) - 0.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,228 @@
+//! Module de l'affichage de débogage pour un jeu Asteroids.
+//!
+//! Compilé uniquement avec la fonctionnalité `debug-overlay`, cet affichage superpose aux
+//! objets leur cercle de collision, leurs limites de `StellarObject` et leur vecteur
+//! vitesse. Il montre aussi les cellules d'une grille uniforme occupées par les objets
+//! (les cellules qu'une phase large de détection consulterait ; la détection actuelle
+//! teste toutes les paires) et met en évidence les paires entrées en collision.
+
+use crate::{
+    asteroid::Asteroid, missile::Missile, spaceship::Spaceship, stellarobject::StellarObject,
+};
+use macroquad::prelude::*;
+
+/// Paire d'objets entrés en collision, mémorisée pour l'affichage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pair {
+    /// Position et taille du premier objet.
+    first: (Vec2, f32),
+    /// Position et taille du second objet.
+    second: (Vec2, f32),
+}
+
+/// Affichage de débogage des collisions.
+pub struct DebugOverlay {
+    /// Indique si l'affichage est visible.
+    visible: bool,
+    /// Paires entrées en collision lors de la dernière frame.
+    pairs: Vec<Pair>,
+}
+
+impl DebugOverlay {
+    /// Taille des cellules de la grille, assez grandes pour contenir un grand astéroïde.
+    pub const CELL: f32 = Asteroid::LARGE * 2.0;
+    /// Facteur d'agrandissement des vecteurs vitesse, exprimés en pixels par frame.
+    pub const VELOCITY_SCALE: f32 = 10.0;
+
+    /// Crée un affichage de débogage masqué.
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            pairs: Vec::new(),
+        }
+    }
+
+    /// Affiche ou masque l'affichage de débogage.
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.pairs.clear();
+    }
+
+    /// Mémorise les paires d'objets en collision, avant leur traitement.
+    ///
+    /// Les paires sont détectées avec le même test que `handle_collisions` : astéroïde
+    /// contre astéroïde, vaisseau contre astéroïde et missile contre astéroïde.
+    ///
+    /// # Paramètres
+    /// - `asteroids`: Liste des astéroïdes.
+    /// - `spaceship`: Le vaisseau spatial.
+    /// - `missiles`: Liste des missiles actifs.
+    pub fn record(&mut self, asteroids: &[Asteroid], spaceship: &Spaceship, missiles: &[Missile]) {
+        self.pairs.clear();
+        if !self.visible {
+            return;
+        }
+
+        for (i, asteroid) in asteroids.iter().enumerate() {
+            for other in &asteroids[i + 1..] {
+                self.check(asteroid, other);
+            }
+            self.check(spaceship, asteroid);
+            for missile in missiles.iter().filter(|missile| missile.is_active()) {
+                self.check(missile, asteroid);
+            }
+        }
+    }
+
+    /// Dessine l'affichage de débogage, s'il est visible.
+    ///
+    /// À appeler avec la caméra du monde.
+    ///
+    /// # Paramètres
+    /// - `asteroids`: Liste des astéroïdes.
+    /// - `spaceship`: Le vaisseau spatial.
+    /// - `missiles`: Liste des missiles actifs.
+    pub fn draw(&self, asteroids: &[Asteroid], spaceship: &Spaceship, missiles: &[Missile]) {
+        if !self.visible {
+            return;
+        }
+
+        let mut objects: Vec<(&dyn StellarObject, Vec2)> = asteroids
+            .iter()
+            .map(|asteroid| (asteroid as &dyn StellarObject, asteroid.get_speed()))
+            .collect();
+        objects.push((spaceship, spaceship.get_velocity()));
+        objects.extend(
+            missiles
+                .iter()
+                .filter(|missile| missile.is_active())
+                .map(|missile| (missile as &dyn StellarObject, missile.get_velocity())),
+        );
+
+        // Cellules de la grille occupées
+        let all: Vec<&dyn StellarObject> = objects.iter().map(|&(object, _)| object).collect();
+        for (x, y) in occupied_cells(&all) {
+            let corner = vec2(x as f32, y as f32) * Self::CELL;
+            draw_rectangle(
+                corner.x,
+                corner.y,
+                Self::CELL,
+                Self::CELL,
+                Color::new(0.2, 0.4, 1.0, 0.08),
+            );
+            draw_rectangle_lines(
+                corner.x,
+                corner.y,
+                Self::CELL,
+                Self::CELL,
+                1.0,
+                Color::new(0.2, 0.4, 1.0, 0.4),
+            );
+        }
+
+        // Limites, cercles de collision et vecteurs vitesse
+        for (object, velocity) in objects {
+            let position = object.get_position();
+            let size = object.get_size();
+            draw_rectangle_lines(
+                position.x - size / 2.0,
+                position.y - size / 2.0,
+                size,
+                size,
+                1.0,
+                GRAY,
+            );
+            draw_circle_lines(position.x, position.y, size / 2.0, 1.0, LIME);
+            let tip = position + velocity * Self::VELOCITY_SCALE;
+            draw_line(position.x, position.y, tip.x, tip.y, 1.5, MAGENTA);
+        }
+
+        // Paires entrées en collision
+        for pair in &self.pairs {
+            for (position, size) in [pair.first, pair.second] {
+                draw_circle_lines(position.x, position.y, size / 2.0 + 2.0, 2.0, RED);
+            }
+            draw_line(
+                pair.first.0.x,
+                pair.first.0.y,
+                pair.second.0.x,
+                pair.second.0.y,
+                2.0,
+                RED,
+            );
+        }
+    }
+
+    /// Mémorise une paire d'objets si elle est en collision.
+    fn check(&mut self, first: &dyn StellarObject, second: &dyn StellarObject) {
+        if first.collide(second) {
+            self.pairs.push(Pair {
+                first: (first.get_position(), first.get_size()),
+                second: (second.get_position(), second.get_size()),
+            });
+        }
+    }
+}
+
+/// Retourne les cellules de la grille recouvertes par les limites des objets.
+///
+/// # Paramètres
+/// - `objects`: Les objets à placer dans la grille.
+///
+/// # Retour
+/// Les coordonnées des cellules occupées, triées et sans doublon.
+fn occupied_cells(objects: &[&dyn StellarObject]) -> Vec<(i32, i32)> {
+    let cell = |value: f32| (value / DebugOverlay::CELL).floor() as i32;
+    let mut cells = Vec::new();
+    for object in objects {
+        let half = object.get_size() / 2.0;
+        let min = object.get_position() - half;
+        let max = object.get_position() + half;
+        for x in cell(min.x)..=cell(max.x) {
+            for y in cell(min.y)..=cell(max.y) {
+                cells.push((x, y));
+            }
+        }
+    }
+    cells.sort_unstable();
+    cells.dedup();
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_occupied_cells() {
+        let inside = Asteroid::with_size(Asteroid::SMALL, vec2(60.0, 60.0));
+        // Astéroïde à cheval sur quatre cellules
+        let corner = Asteroid::with_size(Asteroid::MEDIUM, vec2(240.0, 120.0));
+
+        assert_eq!(occupied_cells(&[&inside]), vec![(0, 0)]);
+        assert_eq!(
+            occupied_cells(&[&inside, &corner, &inside]),
+            vec![(0, 0), (1, 0), (1, 1), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_record_collided_pairs() {
+        let asteroids = vec![
+            Asteroid::with_size(Asteroid::MEDIUM, vec2(100.0, 100.0)),
+            Asteroid::with_size(Asteroid::MEDIUM, vec2(110.0, 100.0)),
+            Asteroid::with_size(Asteroid::SMALL, vec2(500.0, 500.0)),
+        ];
+        let spaceship = Spaceship::without_texture(vec2(300.0, 300.0));
+        let missiles = vec![Missile::new(vec2(500.0, 500.0), 0.0)];
+
+        let mut overlay = DebugOverlay::new();
+        overlay.record(&asteroids, &spaceship, &missiles);
+        assert!(overlay.pairs.is_empty());
+
+        overlay.toggle();
+        overlay.record(&asteroids, &spaceship, &missiles);
+        assert_eq!(overlay.pairs.len(), 2);
+        assert_eq!(overlay.pairs[1].first, (vec2(500.0, 500.0), Missile::SIZE));
+    }
+}
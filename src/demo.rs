@@ -0,0 +1,120 @@
+//! Module du mode démonstration (« attract mode ») pour un jeu Asteroids.
+//!
+//! Ce module fait jouer une partie par le pilote automatique derrière l'écran titre.
+//! La démonstration utilise les mêmes fonctions de mise à jour, de collision et de
+//! dessin que le jeu, et redémarre dès que la partie se termine.
+
+use crate::{
+    apply_control,
+    asteroid::Asteroid,
+    draw, handle_collisions,
+    missile::Missile,
+    particles::ParticleSystem,
+    pilot::Pilot,
+    render::{Macroquad, Renderer},
+    spaceship::Spaceship,
+    starfield::Starfield,
+    update_model, view,
+};
+use macroquad::prelude::*;
+
+/// Partie de démonstration jouée par le pilote automatique.
+pub struct AttractDemo {
+    /// Astéroïdes de la démonstration.
+    asteroids: Vec<Asteroid>,
+    /// Vaisseau piloté par l'ordinateur.
+    spaceship: Spaceship,
+    /// Missiles actifs.
+    missiles: Vec<Missile>,
+    /// Pilote automatique qui contrôle le vaisseau.
+    pilot: Pilot,
+    /// Particules des explosions et de la propulsion.
+    particles: ParticleSystem,
+    /// Bouclier restant du vaisseau.
+    health: f32,
+    /// Fond étoilé, qui défile avec le vaisseau.
+    starfield: Starfield,
+    /// Texture du vaisseau, conservée pour redémarrer la démonstration.
+    texture: Texture2D,
+    /// Image de fond, conservée pour redémarrer la démonstration.
+    backdrop: Texture2D,
+}
+
+impl AttractDemo {
+    /// Nombre d'astéroïdes au lancement de la démonstration.
+    pub const ASTEROIDS: usize = 8;
+
+    /// Crée une nouvelle démonstration.
+    ///
+    /// # Paramètres
+    /// - `texture`: La texture du vaisseau spatial.
+    /// - `backdrop`: L'image dessinée derrière les étoiles.
+    pub fn new(texture: Texture2D, backdrop: Texture2D) -> Self {
+        Self {
+            asteroids: (0..Self::ASTEROIDS)
+                .map(|_| Asteroid::new(vec2(view::WIDTH, view::HEIGHT)))
+                .collect(),
+            spaceship: Spaceship::new(texture.clone()),
+            missiles: Vec::new(),
+            pilot: Pilot::new(),
+            particles: ParticleSystem::new(),
+            health: 3.0,
+            starfield: Starfield::new(Some(backdrop.clone())),
+            texture,
+            backdrop,
+        }
+    }
+
+    /// Fait avancer la démonstration d'une frame et la redémarre si la partie est terminée.
+    pub fn update(&mut self) {
+        let control = self.pilot.think(&self.spaceship, &self.asteroids);
+        let mut events = Vec::new();
+        apply_control(
+            &mut self.spaceship,
+            &mut self.missiles,
+            &control,
+            &mut events,
+        );
+        let bounds = vec2(view::WIDTH, view::HEIGHT);
+        update_model(
+            &mut self.asteroids,
+            &mut self.spaceship,
+            &mut self.missiles,
+            bounds,
+        );
+        self.particles.update();
+        self.starfield.update(self.spaceship.get_velocity());
+
+        if handle_collisions(
+            &mut self.asteroids,
+            &self.spaceship,
+            &mut self.missiles,
+            &mut events,
+        ) {
+            self.health -= 1.0;
+        }
+        self.particles.handle_events(&events);
+
+        if self.health < 0.0 || self.asteroids.is_empty() {
+            *self = Self::new(self.texture.clone(), self.backdrop.clone());
+        }
+    }
+
+    /// Dessine la démonstration, assombrie pour laisser le menu lisible.
+    pub fn draw(&self) {
+        let renderer = &mut Macroquad;
+        draw(
+            renderer,
+            &self.asteroids,
+            &self.spaceship,
+            &self.missiles,
+            &self.starfield,
+        );
+        self.particles.draw(renderer);
+        renderer.rectangle(
+            Vec2::ZERO,
+            vec2(view::WIDTH, view::HEIGHT),
+            Color::from_rgba(0, 0, 0, 150),
+        );
+    }
+}
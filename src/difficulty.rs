@@ -0,0 +1,220 @@
+//! Module des profils de difficulté pour un jeu Asteroids.
+//!
+//! Un profil règle le nombre d'astéroïdes de chaque vague, leur vitesse, le bouclier du
+//! vaisseau et les dégâts de chaque collision, la recharge des missiles, l'arrivée de
+//! renforts pendant la partie et le multiplicateur de score. Trois profils sont fournis
+//! (facile, moyen, difficile) ; l'écran « Custom » permet de régler chaque valeur.
+
+use crate::locale::tr;
+use crate::ui::Ui;
+
+/// Profil de difficulté d'une partie.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    /// Nombre de grands astéroïdes de chaque vague.
+    pub asteroids: usize,
+    /// Multiplicateur de la vitesse des astéroïdes.
+    pub speed: f32,
+    /// Points de bouclier de chaque vie.
+    pub health: f32,
+    /// Points de bouclier retirés par chaque collision.
+    pub damage: f32,
+    /// Nombre de frames entre deux tirs de missile.
+    pub fire_cooldown: u32,
+    /// Nombre de grands astéroïdes de renfort apparaissant par minute (`0.0` pour aucun).
+    pub spawn_rate: f32,
+    /// Multiplicateur des points gagnés.
+    pub score_multiplier: f32,
+}
+
+impl Difficulty {
+    /// Profil facile : peu d'astéroïdes, lents, et un bouclier renforcé.
+    pub const EASY: Difficulty = Difficulty {
+        asteroids: 5,
+        speed: 0.8,
+        health: 5.0,
+        damage: 1.0,
+        fire_cooldown: 8,
+        spawn_rate: 0.0,
+        score_multiplier: 0.5,
+    };
+    /// Profil moyen.
+    pub const MEDIUM: Difficulty = Difficulty {
+        asteroids: 30,
+        speed: 1.0,
+        health: 3.0,
+        damage: 1.0,
+        fire_cooldown: 10,
+        spawn_rate: 2.0,
+        score_multiplier: 1.0,
+    };
+    /// Profil difficile : un champ dense d'astéroïdes rapides et des renforts fréquents.
+    pub const HARD: Difficulty = Difficulty {
+        asteroids: 100,
+        speed: 1.3,
+        health: 3.0,
+        damage: 1.5,
+        fire_cooldown: 12,
+        spawn_rate: 6.0,
+        score_multiplier: 2.0,
+    };
+    /// Profil des niveaux de la campagne, des parties d'essai de l'éditeur et des modes
+    /// scriptés : moyen, sans renforts, pour que seuls les objets du niveau ou du script
+    /// apparaissent.
+    pub const LEVEL: Difficulty = Difficulty {
+        spawn_rate: 0.0,
+        ..Self::MEDIUM
+    };
+    /// Profils fournis, avec la clé de leur nom, dans l'ordre du menu.
+    pub const PRESETS: [(&'static str, Difficulty); 3] = [
+        ("menu.easy", Self::EASY),
+        ("menu.medium", Self::MEDIUM),
+        ("menu.hard", Self::HARD),
+    ];
+    /// Nombre de pas de simulation par minute, le jeu tournant à 60 images par seconde.
+    pub const STEPS_PER_MINUTE: f32 = 3600.0;
+
+    /// Retourne la part d'astéroïde de renfort qui apparaît à chaque pas de simulation.
+    pub fn spawn_per_step(&self) -> f32 {
+        self.spawn_rate / Self::STEPS_PER_MINUTE
+    }
+
+    /// Retourne le nom du profil fourni identique à celui-ci (`easy`, `medium` ou `hard`).
+    ///
+    /// # Retour
+    /// Le nom du profil, ou `None` pour un profil personnalisé ou de niveau.
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, preset)| preset == self)
+            .and_then(|(key, _)| key.strip_prefix("menu."))
+    }
+
+    /// Modifie une valeur du profil désignée par son nom.
+    ///
+    /// # Paramètres
+    /// - `name`: Le nom du champ (`asteroids`, `speed`, `health`, `damage`,
+    ///   `fire_cooldown`, `spawn_rate` ou `score_multiplier`).
+    /// - `value`: La nouvelle valeur, positive ou nulle.
+    ///
+    /// # Retour
+    /// Une erreur si le nom est inconnu ou la valeur invalide.
+    pub fn set(&mut self, name: &str, value: f32) -> Result<(), String> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(format!("invalid value for {name}: {value}"));
+        }
+        match name {
+            "asteroids" => self.asteroids = value.round() as usize,
+            "speed" => self.speed = value,
+            "health" => self.health = value.max(1.0),
+            "damage" => self.damage = value,
+            "fire_cooldown" => self.fire_cooldown = (value.round() as u32).max(1),
+            "spawn_rate" => self.spawn_rate = value,
+            "score_multiplier" => self.score_multiplier = value,
+            _ => return Err(format!("unknown difficulty setting: {name}")),
+        }
+        Ok(())
+    }
+
+    /// Ajoute à une interface une ligne de réglage par valeur du profil.
+    ///
+    /// Les valeurs sont bornées aux plages proposées par les curseurs.
+    ///
+    /// # Paramètres
+    /// - `ui`: L'interface du menu en cours de construction.
+    ///
+    /// # Retour
+    /// `true` si une valeur a été modifiée.
+    pub fn edit(&mut self, ui: &mut Ui) -> bool {
+        let mut asteroids = self.asteroids as f32;
+        let mut fire_cooldown = self.fire_cooldown as f32;
+        let changed = [
+            ui.slider(tr("custom.asteroids"), &mut asteroids, 1.0..=150.0, 1.0),
+            ui.slider(tr("custom.speed"), &mut self.speed, 0.5..=3.0, 0.1),
+            ui.slider(tr("custom.health"), &mut self.health, 1.0..=10.0, 1.0),
+            ui.slider(tr("custom.damage"), &mut self.damage, 0.5..=5.0, 0.5),
+            ui.slider(
+                tr("custom.fire_cooldown"),
+                &mut fire_cooldown,
+                2.0..=60.0,
+                1.0,
+            ),
+            ui.slider(
+                tr("custom.spawn_rate"),
+                &mut self.spawn_rate,
+                0.0..=30.0,
+                1.0,
+            ),
+            ui.slider(
+                tr("custom.score_multiplier"),
+                &mut self.score_multiplier,
+                0.5..=5.0,
+                0.5,
+            ),
+        ];
+        self.asteroids = asteroids.round() as usize;
+        self.fire_cooldown = fire_cooldown.round() as u32;
+        changed.contains(&true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{Layout, Menu, MenuInput};
+
+    #[test]
+    fn test_presets_grow_harder() {
+        let [(_, easy), (_, medium), (_, hard)] = Difficulty::PRESETS;
+
+        assert!(easy.asteroids < medium.asteroids && medium.asteroids < hard.asteroids);
+        assert!(easy.speed < hard.speed);
+        assert!(easy.health / easy.damage > hard.health / hard.damage);
+        assert!(easy.spawn_rate < hard.spawn_rate);
+        assert!(easy.score_multiplier < hard.score_multiplier);
+        assert_eq!(hard.spawn_per_step() * Difficulty::STEPS_PER_MINUTE, 6.0);
+        assert_eq!(hard.preset_name(), Some("hard"));
+        assert_eq!(Difficulty::LEVEL.preset_name(), None);
+    }
+
+    #[test]
+    fn test_set_by_name() {
+        let mut difficulty = Difficulty::MEDIUM;
+
+        assert_eq!(difficulty.set("speed", 2.5), Ok(()));
+        assert_eq!(difficulty.set("fire_cooldown", 0.2), Ok(()));
+        assert!(difficulty.set("gravity", 1.0).is_err());
+        assert!(difficulty.set("damage", -1.0).is_err());
+
+        assert_eq!(difficulty.speed, 2.5);
+        assert_eq!(difficulty.fire_cooldown, 1);
+        assert_eq!(difficulty.damage, Difficulty::MEDIUM.damage);
+    }
+
+    #[test]
+    fn test_edit_adjusts_focused_value() {
+        let mut menu = Menu::new();
+        let mut custom = Difficulty::MEDIUM;
+        let right = MenuInput {
+            right: true,
+            ..Default::default()
+        };
+
+        // Le premier curseur règle le nombre d'astéroïdes
+        let mut ui = menu.begin(right, Layout::centered(0.1));
+        assert!(custom.edit(&mut ui));
+        ui.finish();
+        assert_eq!(custom.asteroids, 31);
+
+        // Les valeurs restent dans les plages des curseurs
+        custom.fire_cooldown = 60;
+        for _ in 0..4 {
+            menu.navigate(true, 7);
+        }
+        let mut ui = menu.begin(right, Layout::centered(0.1));
+        assert!(!custom.edit(&mut ui));
+        ui.finish();
+        assert_eq!(custom.fire_cooldown, 60);
+        assert_eq!(custom.asteroids, 31);
+    }
+}
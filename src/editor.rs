@@ -0,0 +1,512 @@
+//! Module de l'éditeur de niveaux pour un jeu Asteroids.
+//!
+//! L'éditeur affiche l'arène entière avec les fonctions de dessin de la partie : un niveau
+//! édité a le même aspect qu'en jeu. Les outils se choisissent avec les touches 1 à 6 ou
+//! en cliquant dans le panneau de gauche :
+//!
+//! - **Clic gauche** : place l'objet de l'outil choisi. Pour un astéroïde, glisser règle sa
+//!   vitesse ; pour une zone dangereuse, son rayon.
+//! - **Clic droit** : supprime l'astéroïde, la zone ou le point d'apparition sous le curseur.
+//! - **C** / **T** : ajoute une vague lancée lorsque le champ est dégagé / après un délai.
+//! - **+** / **-** : change le nombre d'astéroïdes de la dernière vague ; **[** / **]** son
+//!   délai. **Retour arrière** supprime la dernière vague.
+//! - **S** enregistre le niveau, **P** lance une partie d'essai et **Échap** revient au
+//!   menu principal.
+
+use crate::difficulty::Difficulty;
+use crate::level::{AsteroidSpec, Hazard, Level, Tier, Trigger, WaveTrigger};
+use crate::locale::tr;
+use crate::render::{Macroquad, Renderer};
+use crate::spaceship::Spaceship;
+use crate::{draw_objects, play, view, Mode, Outcome, Session};
+use macroquad::prelude::*;
+use std::path::PathBuf;
+
+/// Outil de l'éditeur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    /// Place un astéroïde de la taille indiquée.
+    Asteroid(Tier),
+    /// Place une zone dangereuse.
+    Hazard,
+    /// Place un point d'apparition des vagues.
+    SpawnPoint,
+    /// Déplace le départ du vaisseau.
+    Ship,
+}
+
+impl Tool {
+    /// Liste des outils, dans l'ordre des touches 1 à 6.
+    const ALL: [Tool; 6] = [
+        Tool::Asteroid(Tier::Large),
+        Tool::Asteroid(Tier::Medium),
+        Tool::Asteroid(Tier::Small),
+        Tool::Hazard,
+        Tool::SpawnPoint,
+        Tool::Ship,
+    ];
+
+    /// Retourne le nom traduit de l'outil.
+    fn label(self) -> &'static str {
+        tr(match self {
+            Tool::Asteroid(Tier::Large) => "editor.large",
+            Tool::Asteroid(Tier::Medium) => "editor.medium",
+            Tool::Asteroid(Tier::Small) => "editor.small",
+            Tool::Hazard => "editor.hazard",
+            Tool::SpawnPoint => "editor.spawn_point",
+            Tool::Ship => "editor.ship",
+        })
+    }
+}
+
+/// Éditeur d'un niveau enregistré dans un fichier.
+pub struct Editor {
+    /// Niveau édité.
+    level: Level,
+    /// Fichier du niveau.
+    path: PathBuf,
+    /// Outil choisi.
+    tool: Tool,
+    /// Point de départ du glissement en cours, dans l'arène.
+    drag: Option<Vec2>,
+    /// Message affiché en bas de l'écran.
+    status: String,
+}
+
+impl Editor {
+    /// Longueur de glissement, en pixels, donnant une vitesse d'un pixel par frame.
+    pub const DRAG_SCALE: f32 = 60.0;
+    /// Rayon minimal d'une zone dangereuse.
+    pub const MIN_RADIUS: f32 = 30.0;
+    /// Distance en dessous de laquelle un clic droit désigne un objet.
+    pub const PICK_RADIUS: f32 = 20.0;
+    /// Nombre d'astéroïdes d'une nouvelle vague.
+    pub const WAVE_COUNT: usize = 4;
+    /// Délai d'une nouvelle vague minutée, et pas de son réglage, en secondes.
+    pub const WAVE_DELAY: f32 = 30.0;
+    /// Largeur du panneau des outils, en coordonnées logiques.
+    pub const PANEL_WIDTH: f32 = 300.0;
+    /// Taille du texte du panneau.
+    pub const FONT_SIZE: f32 = 20.0;
+
+    /// Ouvre un niveau, ou en crée un nouveau si le fichier n'existe pas.
+    ///
+    /// # Paramètres
+    /// - `path`: Le fichier du niveau.
+    /// - `arena`: Les dimensions de l'arène d'un nouveau niveau.
+    pub fn open(path: PathBuf, arena: Vec2) -> Self {
+        let (level, status) = if path.exists() {
+            match Level::load(&path) {
+                Ok(level) => (level, format!("{} {}", tr("editor.opened"), path.display())),
+                Err(error) => (Level::new(arena), format!("{} {error}", tr("editor.error"))),
+            }
+        } else {
+            let status = format!("{} {}", tr("editor.new_level"), path.display());
+            (Level::new(arena), status)
+        };
+        Self {
+            level,
+            path,
+            tool: Tool::ALL[0],
+            drag: None,
+            status,
+        }
+    }
+
+    /// Affiche l'éditeur jusqu'au retour au menu principal.
+    ///
+    /// # Paramètres
+    /// - `session`: Les ressources partagées avec le menu principal.
+    pub async fn run(mut self, session: &mut Session) {
+        let mut ship = Spaceship::new(session.texture_spaceship.clone());
+
+        loop {
+            clear_background(BLACK);
+            let camera = self.camera();
+            let (mx, my) = macroquad::input::mouse_position();
+            let cursor = camera.screen_to_world(vec2(mx, my));
+            let (sx, sy) = view::mouse_position();
+            let on_panel = sx < Self::PANEL_WIDTH;
+
+            // Choix de l'outil
+            for (index, key) in [
+                KeyCode::Key1,
+                KeyCode::Key2,
+                KeyCode::Key3,
+                KeyCode::Key4,
+                KeyCode::Key5,
+                KeyCode::Key6,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                if is_key_pressed(key) {
+                    self.tool = Tool::ALL[index];
+                }
+            }
+            if on_panel && is_mouse_button_pressed(MouseButton::Left) {
+                let row = ((sy - Self::FONT_SIZE) / (Self::FONT_SIZE * 1.5)).floor();
+                if let Some(&tool) = Tool::ALL.get(row as usize).filter(|_| row >= 0.0) {
+                    self.tool = tool;
+                }
+            }
+
+            // Placement et suppression des objets
+            if !on_panel && is_mouse_button_pressed(MouseButton::Left) {
+                self.press(cursor);
+            }
+            if is_mouse_button_released(MouseButton::Left) {
+                self.release(cursor);
+            }
+            if !on_panel && is_mouse_button_pressed(MouseButton::Right) {
+                self.erase(cursor);
+            }
+
+            // Vagues
+            if is_key_pressed(KeyCode::C) {
+                self.add_wave(Trigger::Cleared);
+            }
+            if is_key_pressed(KeyCode::T) {
+                self.add_wave(Trigger::After(Self::WAVE_DELAY));
+            }
+            if is_key_pressed(KeyCode::Equal) || is_key_pressed(KeyCode::KpAdd) {
+                self.adjust_wave(1, 0.0);
+            }
+            if is_key_pressed(KeyCode::Minus) || is_key_pressed(KeyCode::KpSubtract) {
+                self.adjust_wave(-1, 0.0);
+            }
+            if is_key_pressed(KeyCode::RightBracket) {
+                self.adjust_wave(0, Self::WAVE_DELAY / 6.0);
+            }
+            if is_key_pressed(KeyCode::LeftBracket) {
+                self.adjust_wave(0, -Self::WAVE_DELAY / 6.0);
+            }
+            if is_key_pressed(KeyCode::Backspace) && self.level.waves.pop().is_some() {
+                self.status = tr("editor.wave_removed").to_string();
+            }
+
+            // Enregistrement, partie d'essai et retour au menu
+            if is_key_pressed(KeyCode::S) {
+                self.status = match self.level.save(&self.path) {
+                    Ok(()) => format!("{} {}", tr("editor.saved"), self.path.display()),
+                    Err(error) => format!("{} {error}", tr("editor.error")),
+                };
+            }
+            if is_key_pressed(KeyCode::P) {
+                self.drag = None;
+                next_frame().await;
+                let level = &self.level;
+                while play(Difficulty::LEVEL, Mode::Level(level), session).await == Outcome::Restart
+                {
+                }
+                continue;
+            }
+            if is_key_pressed(KeyCode::Escape) {
+                next_frame().await;
+                return;
+            }
+
+            let renderer = &mut Macroquad;
+            set_camera(&camera);
+            self.draw_level(renderer, &mut ship, cursor);
+            view::set_screen_camera();
+            self.draw_panel(renderer);
+
+            next_frame().await;
+        }
+    }
+
+    /// Commence à placer un objet avec l'outil choisi.
+    ///
+    /// Les astéroïdes et les zones sont placés au relâchement du bouton, pour que le
+    /// glissement règle leur vitesse ou leur rayon.
+    ///
+    /// # Paramètres
+    /// - `point`: La position du clic dans l'arène.
+    fn press(&mut self, point: Vec2) {
+        let point = point.clamp(Vec2::ZERO, self.level.get_arena());
+        match self.tool {
+            Tool::Asteroid(_) | Tool::Hazard => self.drag = Some(point),
+            Tool::SpawnPoint => self.level.spawn_points.push(point.into()),
+            Tool::Ship => self.level.ship = point.into(),
+        }
+    }
+
+    /// Termine le placement commencé par `press`.
+    ///
+    /// # Paramètres
+    /// - `point`: La position du relâchement du bouton, dans l'arène.
+    fn release(&mut self, point: Vec2) {
+        let Some(start) = self.drag.take() else {
+            return;
+        };
+        match self.tool {
+            Tool::Asteroid(tier) => self.level.asteroids.push(AsteroidSpec {
+                tier,
+                position: start.into(),
+                velocity: ((point - start) / Self::DRAG_SCALE).into(),
+            }),
+            Tool::Hazard => self.level.hazards.push(Hazard {
+                position: start.into(),
+                radius: start.distance(point).max(Self::MIN_RADIUS),
+            }),
+            Tool::SpawnPoint | Tool::Ship => {}
+        }
+    }
+
+    /// Supprime l'objet le plus récent situé sous un point.
+    ///
+    /// # Paramètres
+    /// - `point`: La position du clic dans l'arène.
+    fn erase(&mut self, point: Vec2) {
+        let near = |position: (f32, f32), radius: f32| {
+            Vec2::from(position).distance(point) <= radius.max(Self::PICK_RADIUS)
+        };
+        let level = &mut self.level;
+        if let Some(index) = level
+            .asteroids
+            .iter()
+            .rposition(|asteroid| near(asteroid.position, asteroid.tier.size() / 2.0))
+        {
+            level.asteroids.remove(index);
+        } else if let Some(index) = level
+            .spawn_points
+            .iter()
+            .rposition(|&spawn| near(spawn, 0.0))
+        {
+            level.spawn_points.remove(index);
+        } else if let Some(index) = level
+            .hazards
+            .iter()
+            .rposition(|hazard| near(hazard.position, hazard.radius))
+        {
+            level.hazards.remove(index);
+        }
+    }
+
+    /// Ajoute une vague, composée d'astéroïdes de la taille de l'outil choisi.
+    ///
+    /// # Paramètres
+    /// - `when`: La condition de déclenchement de la vague.
+    fn add_wave(&mut self, when: Trigger) {
+        let tier = match self.tool {
+            Tool::Asteroid(tier) => tier,
+            _ => Tier::Large,
+        };
+        self.level.waves.push(WaveTrigger {
+            when,
+            tier,
+            count: Self::WAVE_COUNT,
+        });
+        self.status = format!("{} {}", tr("editor.wave_added"), self.level.waves.len());
+    }
+
+    /// Modifie la dernière vague.
+    ///
+    /// # Paramètres
+    /// - `count`: Nombre d'astéroïdes à ajouter (ou à retirer, s'il est négatif).
+    /// - `delay`: Secondes à ajouter au délai d'une vague minutée.
+    fn adjust_wave(&mut self, count: i32, delay: f32) {
+        let Some(wave) = self.level.waves.last_mut() else {
+            return;
+        };
+        wave.count = wave
+            .count
+            .saturating_add_signed(count as isize)
+            .clamp(1, Level::MAX_WAVE);
+        if let Trigger::After(seconds) = &mut wave.when {
+            *seconds = (*seconds + delay).max(0.0);
+        }
+    }
+
+    /// Retourne une caméra qui affiche l'arène entière, à droite du panneau.
+    fn camera(&self) -> Camera2D {
+        let arena = self.level.get_arena();
+        let usable = vec2(view::WIDTH - Self::PANEL_WIDTH, view::HEIGHT) * 0.95;
+        let scale = (arena.x / usable.x).max(arena.y / usable.y);
+        let size = vec2(view::WIDTH, view::HEIGHT) * scale;
+        // Centre de l'arène placé au centre de la zone à droite du panneau
+        let offset = vec2(Self::PANEL_WIDTH / 2.0, 0.0) * scale;
+        let corner = arena / 2.0 - size / 2.0 - offset;
+        view::camera(Rect::new(corner.x, corner.y, size.x, size.y))
+    }
+
+    /// Dessine l'arène et les objets du niveau, avec la caméra de l'éditeur.
+    fn draw_level(&self, renderer: &mut dyn Renderer, ship: &mut Spaceship, cursor: Vec2) {
+        let arena = self.level.get_arena();
+        renderer.rectangle_lines(Vec2::ZERO, arena, 4.0, DARKGRAY);
+
+        for hazard in &self.level.hazards {
+            hazard.draw(renderer);
+        }
+        ship.set_position(self.level.get_ship());
+        draw_objects(renderer, &self.level.spawn_asteroids(), ship, &[]);
+
+        // Vitesses des astéroïdes, à l'échelle du glissement qui les a réglées
+        for asteroid in &self.level.asteroids {
+            let position = Vec2::from(asteroid.position);
+            let tip = position + Vec2::from(asteroid.velocity) * Self::DRAG_SCALE;
+            renderer.line(position, tip, 3.0, ORANGE);
+        }
+        for &point in &self.level.spawn_points {
+            let point = Vec2::from(point);
+            renderer.line(point - 15.0, point + 15.0, 3.0, SKYBLUE);
+            renderer.line(
+                point + vec2(-15.0, 15.0),
+                point + vec2(15.0, -15.0),
+                3.0,
+                SKYBLUE,
+            );
+        }
+
+        if let Some(start) = self.drag {
+            match self.tool {
+                Tool::Hazard => renderer.circle_lines(
+                    start,
+                    start.distance(cursor).max(Self::MIN_RADIUS),
+                    2.0,
+                    WHITE,
+                ),
+                _ => renderer.line(start, cursor, 3.0, WHITE),
+            }
+        }
+    }
+
+    /// Dessine le panneau des outils, la liste des vagues et l'aide, avec la caméra de
+    /// l'interface.
+    fn draw_panel(&self, renderer: &mut dyn Renderer) {
+        let line = Self::FONT_SIZE * 1.5;
+        renderer.rectangle(
+            Vec2::ZERO,
+            vec2(Self::PANEL_WIDTH, view::HEIGHT),
+            Color::from_rgba(20, 20, 30, 230),
+        );
+
+        let mut y = Self::FONT_SIZE;
+        for (index, tool) in Tool::ALL.into_iter().enumerate() {
+            if tool == self.tool {
+                renderer.rectangle(vec2(0.0, y), vec2(Self::PANEL_WIDTH, line), DARKBLUE);
+            }
+            renderer.text(
+                &format!("{} {}", index + 1, tool.label()),
+                vec2(10.0, y + line * 0.7),
+                Self::FONT_SIZE,
+                WHITE,
+            );
+            y += line;
+        }
+
+        y += line;
+        renderer.text(tr("editor.waves"), vec2(10.0, y), Self::FONT_SIZE, GRAY);
+        for (index, wave) in self.level.waves.iter().enumerate() {
+            y += line;
+            let when = match wave.when {
+                Trigger::Cleared => tr("editor.cleared").to_string(),
+                Trigger::After(seconds) => format!("{} {seconds:.0}s", tr("editor.after")),
+            };
+            let tier = Tool::Asteroid(wave.tier).label();
+            renderer.text(
+                &format!("{}. {when}: {} x {tier}", index + 1, wave.count),
+                vec2(10.0, y),
+                Self::FONT_SIZE,
+                LIGHTGRAY,
+            );
+        }
+
+        let help = [
+            tr("editor.help_drag"),
+            tr("editor.help_delete"),
+            tr("editor.help_waves"),
+            tr("editor.help_keys"),
+        ];
+        for (index, text) in help.into_iter().enumerate() {
+            let y = view::HEIGHT - line * (help.len() + 1 - index) as f32;
+            renderer.text(text, vec2(10.0, y), Self::FONT_SIZE, GRAY);
+        }
+        renderer.text(
+            &self.status,
+            vec2(10.0, view::HEIGHT - line * 0.5),
+            Self::FONT_SIZE,
+            YELLOW,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor() -> Editor {
+        Editor::open(
+            PathBuf::from("/nonexistent/asteroids-level.ron"),
+            vec2(1000.0, 800.0),
+        )
+    }
+
+    #[test]
+    fn test_drag_sets_velocity_and_radius() {
+        let mut editor = editor();
+        assert!(editor.level.asteroids.is_empty());
+
+        editor.press(vec2(100.0, 100.0));
+        editor.release(vec2(160.0, 40.0));
+        assert_eq!(
+            editor.level.asteroids[0],
+            AsteroidSpec {
+                tier: Tier::Large,
+                position: (100.0, 100.0),
+                velocity: (1.0, -1.0),
+            }
+        );
+
+        editor.tool = Tool::Hazard;
+        editor.press(vec2(500.0, 400.0));
+        editor.release(vec2(505.0, 400.0));
+        assert_eq!(editor.level.hazards[0].radius, Editor::MIN_RADIUS);
+
+        // Les clics hors de l'arène sont ramenés sur ses bords
+        editor.tool = Tool::Ship;
+        editor.press(vec2(2000.0, -50.0));
+        assert_eq!(editor.level.ship, (1000.0, 0.0));
+    }
+
+    #[test]
+    fn test_erase_removes_object_under_cursor() {
+        let mut editor = editor();
+        editor.tool = Tool::SpawnPoint;
+        editor.press(vec2(300.0, 300.0));
+        editor.tool = Tool::Asteroid(Tier::Small);
+        editor.press(vec2(600.0, 600.0));
+        editor.release(vec2(600.0, 600.0));
+
+        editor.erase(vec2(50.0, 50.0));
+        assert_eq!(editor.level.spawn_points.len(), 1);
+        assert_eq!(editor.level.asteroids.len(), 1);
+
+        editor.erase(vec2(610.0, 605.0));
+        editor.erase(vec2(300.0, 310.0));
+        assert!(editor.level.asteroids.is_empty());
+        assert!(editor.level.spawn_points.is_empty());
+    }
+
+    #[test]
+    fn test_wave_editing() {
+        let mut editor = editor();
+        editor.adjust_wave(1, 0.0);
+        assert!(editor.level.waves.is_empty());
+
+        editor.tool = Tool::Asteroid(Tier::Medium);
+        editor.add_wave(Trigger::After(Editor::WAVE_DELAY));
+        assert_eq!(editor.status, "Wave added: 1");
+        editor.adjust_wave(-10, 5.0);
+
+        assert_eq!(
+            editor.level.waves,
+            [WaveTrigger {
+                when: Trigger::After(35.0),
+                tier: Tier::Medium,
+                count: 1,
+            }]
+        );
+    }
+}
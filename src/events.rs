@@ -0,0 +1,40 @@
+//! Module des événements de jeu.
+//!
+//! Les fonctions de collision ne font que signaler ce qui s'est passé pendant une frame
+//! sous la forme d'une liste de `GameEvent`. Les autres systèmes (récompenses,
+//! score, son, etc.) consomment ensuite ces événements sans dépendre des collisions.
+
+use macroquad::prelude::*;
+
+/// Événement survenu pendant une frame de jeu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    /// Un astéroïde a été détruit par un missile.
+    AsteroidDestroyed {
+        /// Taille de l'astéroïde détruit.
+        size: f32,
+        /// Position de l'astéroïde au moment de sa destruction.
+        position: Vec2,
+    },
+    /// Le vaisseau spatial a été percuté par un astéroïde.
+    SpaceshipHit {
+        /// Position du vaisseau au moment de l'impact.
+        position: Vec2,
+    },
+    /// Un missile a été tiré.
+    MissileFired {
+        /// Position de départ du missile.
+        position: Vec2,
+    },
+    /// Le vaisseau spatial a accéléré pendant la frame.
+    Thrust {
+        /// Position du vaisseau.
+        position: Vec2,
+        /// Orientation du vaisseau (en radians).
+        direction: f32,
+        /// Intensité de la propulsion, de `0.0` à `1.0`.
+        intensity: f32,
+    },
+    /// Le vaisseau spatial a été détruit et la partie est perdue.
+    GameOver,
+}
@@ -0,0 +1,424 @@
+//! Module des défis et du vaisseau fantôme pour un jeu Asteroids.
+//!
+//! Un défi est un champ d'astéroïdes généré à partir d'une graine (option `--seed`) : la
+//! simulation utilise un générateur aléatoire initialisé avec cette graine, si bien que
+//! les mêmes commandes produisent toujours la même partie. Les commandes du vaisseau sont
+//! enregistrées à chaque frame dans un `Replay`, avec les temps de passage (la frame de
+//! chaque astéroïde touché).
+//!
+//! La partie gagnée la plus rapide est enregistrée dans `replays/<graine>.ron`. Lors des
+//! parties suivantes, elle est rejouée par un vaisseau fantôme translucide, qui ne fait
+//! que rejouer les commandes et ne participe à aucune collision. À chaque astéroïde
+//! touché, l'écart avec le même passage de la meilleure partie est affiché : négatif si
+//! le joueur est en avance, positif s'il est en retard.
+
+use crate::asteroid::{self, Asteroid};
+use crate::control::ShipControl;
+use crate::level::{AsteroidSpec, Level, Tier};
+use crate::locale::tr;
+use crate::render::Renderer;
+use crate::spaceship::Spaceship;
+use crate::view;
+use ::rand::{rngs::SmallRng, SeedableRng};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Commandes d'une frame enregistrées : rotation, poussée et tir.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Input(pub f32, pub f32, pub bool);
+
+impl From<&ShipControl> for Input {
+    fn from(control: &ShipControl) -> Self {
+        Input(control.rotate, control.thrust, control.fire)
+    }
+}
+
+/// Enregistrement d'une partie de défi.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// Graine du défi.
+    pub seed: u64,
+    /// Commandes du vaisseau, une par frame.
+    pub inputs: Vec<Input>,
+    /// Nombre de frames écoulées à chaque astéroïde touché.
+    pub splits: Vec<usize>,
+}
+
+impl Replay {
+    /// Commence l'enregistrement d'une partie.
+    ///
+    /// # Paramètres
+    /// - `seed`: La graine du défi.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    /// Enregistre les commandes d'une frame.
+    pub fn record(&mut self, control: &ShipControl) {
+        self.inputs.push(control.into());
+    }
+
+    /// Enregistre un astéroïde touché à la frame en cours.
+    ///
+    /// # Retour
+    /// L'indice du passage.
+    pub fn split(&mut self) -> usize {
+        self.splits.push(self.inputs.len());
+        self.splits.len() - 1
+    }
+
+    /// Retourne la durée de la partie, en secondes.
+    pub fn get_time(&self) -> f32 {
+        frames_to_seconds(self.inputs.len())
+    }
+
+    /// Lit un enregistrement au format RON.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|error| error.to_string())
+    }
+
+    /// Écrit l'enregistrement au format RON, sur une seule ligne.
+    pub fn to_ron(&self) -> String {
+        ron::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Convertit un nombre de frames en secondes, le jeu tournant à 60 images par seconde.
+fn frames_to_seconds(frames: usize) -> f32 {
+    frames as f32 / Challenge::FRAME_RATE
+}
+
+/// Défi généré à partir d'une graine, avec la meilleure partie enregistrée.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    /// Graine du défi.
+    seed: u64,
+    /// Répertoire des meilleures parties.
+    directory: PathBuf,
+}
+
+impl Challenge {
+    /// Graine du défi par défaut.
+    pub const SEED: u64 = 1;
+    /// Répertoire des meilleures parties.
+    pub const DIRECTORY: &'static str = "replays";
+    /// Dimensions de l'arène, identiques pour toutes les parties d'un même défi.
+    pub const ARENA: Vec2 = vec2(1600.0, 1200.0);
+    /// Nombre de grands astéroïdes du défi.
+    pub const ASTEROIDS: usize = 8;
+    /// Nombre de frames par seconde de la simulation.
+    pub const FRAME_RATE: f32 = 60.0;
+
+    /// Crée un défi.
+    ///
+    /// # Paramètres
+    /// - `seed`: La graine du défi.
+    /// - `directory`: Le répertoire des meilleures parties.
+    pub fn new(seed: u64, directory: PathBuf) -> Self {
+        Self { seed, directory }
+    }
+
+    /// Retourne la graine du défi.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Retourne le générateur aléatoire de la simulation, initialisé avec la graine.
+    pub fn rng(&self) -> SmallRng {
+        SmallRng::seed_from_u64(self.seed)
+    }
+
+    /// Génère le niveau du défi : grands astéroïdes près des bords, à dégager.
+    pub fn level(&self) -> Level {
+        let mut rng = self.rng();
+        let mut level = Level::new(Self::ARENA);
+        level.asteroids = asteroid::with_rng(&mut rng, || {
+            (0..Self::ASTEROIDS)
+                .map(|_| {
+                    let asteroid = Asteroid::new(Self::ARENA);
+                    AsteroidSpec {
+                        tier: Tier::Large,
+                        position: asteroid.get_position().into(),
+                        velocity: asteroid.get_speed().into(),
+                    }
+                })
+                .collect()
+        });
+        level
+    }
+
+    /// Retourne le chemin de la meilleure partie du défi.
+    pub fn get_path(&self) -> PathBuf {
+        self.directory.join(format!("{}.ron", self.seed))
+    }
+
+    /// Charge la meilleure partie du défi.
+    ///
+    /// # Retour
+    /// La meilleure partie, ou `None` si aucune n'est enregistrée ou si le fichier est
+    /// invalide.
+    pub fn load_best(&self) -> Option<Replay> {
+        let text = fs::read_to_string(self.get_path()).ok()?;
+        Replay::parse(&text)
+            .inspect_err(|error| eprintln!("{}: {error}", self.get_path().display()))
+            .ok()
+            .filter(|replay| replay.seed == self.seed)
+    }
+
+    /// Enregistre une partie gagnée si elle est plus rapide que la meilleure.
+    ///
+    /// # Paramètres
+    /// - `replay`: La partie gagnée.
+    ///
+    /// # Retour
+    /// `true` si la partie est la nouvelle meilleure, ou une erreur d'écriture.
+    pub fn submit(&self, replay: &Replay) -> Result<bool, String> {
+        if let Some(best) = self.load_best() {
+            if best.inputs.len() <= replay.inputs.len() {
+                return Ok(false);
+            }
+        }
+        let path = self.get_path();
+        let error = |error: std::io::Error| format!("{}: {error}", path.display());
+        fs::create_dir_all(&self.directory).map_err(error)?;
+        fs::write(&path, replay.to_ron()).map_err(error)?;
+        Ok(true)
+    }
+}
+
+/// Vaisseau fantôme qui rejoue la meilleure partie, sans participer aux collisions.
+pub struct Ghost {
+    /// Partie rejouée.
+    replay: Replay,
+    /// Vaisseau rejouant les commandes.
+    spaceship: Spaceship,
+    /// Indice de la prochaine frame rejouée.
+    frame: usize,
+    /// Dernier écart de passage en secondes, et durée d'affichage restante.
+    split: Option<(f32, f32)>,
+}
+
+impl Ghost {
+    /// Couleur translucide du fantôme.
+    pub const COLOR: Color = Color::new(0.6, 0.85, 1.0, 0.4);
+    /// Durée d'affichage d'un écart de passage, en secondes.
+    pub const SPLIT_TIME: f32 = 2.0;
+
+    /// Crée le fantôme d'une partie.
+    ///
+    /// # Paramètres
+    /// - `replay`: La partie à rejouer.
+    /// - `spaceship`: Le vaisseau du fantôme.
+    /// - `spawn`: La position de départ.
+    pub fn new(replay: Replay, mut spaceship: Spaceship, spawn: Vec2) -> Self {
+        spaceship.set_position(spawn);
+        Self {
+            replay,
+            spaceship,
+            frame: 0,
+            split: None,
+        }
+    }
+
+    /// Rejoue la frame suivante, comme la simulation l'a jouée.
+    ///
+    /// # Paramètres
+    /// - `bounds`: Les dimensions de l'arène.
+    pub fn advance(&mut self, bounds: Vec2) {
+        let Some(&Input(rotate, thrust, _)) = self.replay.inputs.get(self.frame) else {
+            return;
+        };
+        self.spaceship.rotate(rotate);
+        self.spaceship.set_thrust(thrust);
+        self.spaceship.update(bounds);
+        self.frame += 1;
+    }
+
+    /// Indique si toute la partie a été rejouée.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.inputs.len()
+    }
+
+    /// Compare un passage du joueur au même passage de la meilleure partie.
+    ///
+    /// # Paramètres
+    /// - `index`: L'indice du passage.
+    /// - `frames`: Le nombre de frames jouées par le joueur.
+    ///
+    /// # Retour
+    /// L'écart en secondes, négatif si le joueur est en avance, ou `None` si la meilleure
+    /// partie n'a pas ce passage.
+    pub fn split(&mut self, index: usize, frames: usize) -> Option<f32> {
+        let best = *self.replay.splits.get(index)?;
+        let delta = frames_to_seconds(frames) - frames_to_seconds(best);
+        self.split = Some((delta, Self::SPLIT_TIME));
+        Some(delta)
+    }
+
+    /// Fait avancer la durée d'affichage de l'écart de passage.
+    ///
+    /// # Paramètres
+    /// - `delta`: La durée de la frame, en secondes.
+    pub fn update(&mut self, delta: f32) {
+        if let Some((_, remaining)) = &mut self.split {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                self.split = None;
+            }
+        }
+    }
+
+    /// Dessine le fantôme, tant qu'il rejoue la partie.
+    ///
+    /// À appeler avec la caméra du monde.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        if !self.is_finished() {
+            self.spaceship.draw_tinted(renderer, Self::COLOR);
+        }
+    }
+
+    /// Dessine le dernier écart de passage sous le chronomètre : vert si le joueur est en
+    /// avance, rouge s'il est en retard.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw_split(&self, renderer: &mut dyn Renderer) {
+        let Some((delta, _)) = self.split else {
+            return;
+        };
+        let text = format!("{} {}", tr("ghost.split"), format_split(delta));
+        let color = if delta <= 0.0 { GREEN } else { RED };
+        let size = renderer.measure_text(&text, 28.0);
+        renderer.text(
+            &text,
+            vec2((view::WIDTH - size.width) / 2.0, 80.0),
+            28.0,
+            color,
+        );
+    }
+}
+
+/// Annonce un nouveau meilleur temps sous le score final de l'écran de victoire.
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+/// - `time`: La durée de la partie, en secondes.
+pub fn draw_new_best(renderer: &mut dyn Renderer, time: f32) {
+    let text = format!("{} {time:.2} s", tr("ghost.best"));
+    let size = renderer.measure_text(&text, 36.0);
+    renderer.text(
+        &text,
+        vec2((view::WIDTH - size.width) / 2.0, view::HEIGHT * 0.7),
+        36.0,
+        Ghost::COLOR.with_alpha(1.0),
+    );
+}
+
+/// Écrit un écart de passage en secondes, avec son signe.
+fn format_split(delta: f32) -> String {
+    format!("{delta:+.2} s")
+}
+
+/// Retourne le répertoire par défaut des meilleures parties.
+pub fn default_directory() -> PathBuf {
+    Path::new(Challenge::DIRECTORY).to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn control(rotate: f32, thrust: f32) -> ShipControl {
+        ShipControl {
+            rotate,
+            thrust,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_challenge_is_seeded() {
+        let challenge = Challenge::new(7, default_directory());
+
+        assert_eq!(
+            challenge.level(),
+            Challenge::new(7, default_directory()).level()
+        );
+        assert_ne!(
+            challenge.level(),
+            Challenge::new(8, default_directory()).level()
+        );
+        assert_eq!(challenge.level().asteroids.len(), Challenge::ASTEROIDS);
+    }
+
+    #[test]
+    fn test_ghost_follows_the_recorded_ship() {
+        let bounds = Challenge::ARENA;
+        let spawn = bounds / 2.0;
+        let mut live = Spaceship::without_texture(spawn);
+        let mut replay = Replay::new(1);
+        for frame in 0..120 {
+            let input = control((frame as f32 / 10.0).sin(), (frame % 3) as f32 / 2.0);
+            replay.record(&input);
+            live.rotate(input.rotate);
+            live.set_thrust(input.thrust);
+            live.update(bounds);
+        }
+
+        let replay = Replay::parse(&replay.to_ron()).unwrap();
+        let mut ghost = Ghost::new(replay, Spaceship::without_texture(Vec2::ZERO), spawn);
+        while !ghost.is_finished() {
+            ghost.advance(bounds);
+        }
+
+        assert_eq!(ghost.spaceship.get_position(), live.get_position());
+        assert_eq!(ghost.spaceship.get_direction(), live.get_direction());
+    }
+
+    #[test]
+    fn test_splits_compare_with_best_run() {
+        let mut best = Replay::new(1);
+        for _ in 0..120 {
+            best.record(&ShipControl::default());
+        }
+        assert_eq!(best.split(), 0);
+        let mut ghost = Ghost::new(best, Spaceship::without_texture(Vec2::ZERO), Vec2::ZERO);
+
+        assert_eq!(ghost.split(0, 90), Some(-0.5));
+        assert_eq!(ghost.split(0, 180), Some(1.0));
+        assert_eq!(ghost.split(1, 200), None);
+        assert_eq!(format_split(-0.5), "-0.50 s");
+        assert_eq!(format_split(1.0), "+1.00 s");
+    }
+
+    #[test]
+    fn test_only_faster_runs_are_kept() {
+        let directory = std::env::temp_dir().join("asteroids-ghost-test");
+        let _ = fs::remove_dir_all(&directory);
+        let challenge = Challenge::new(3, directory.clone());
+        let run = |frames| {
+            let mut replay = Replay::new(3);
+            replay.inputs = vec![Input(0.0, 1.0, false); frames];
+            replay
+        };
+
+        assert_eq!(challenge.submit(&run(300)), Ok(true));
+        assert_eq!(challenge.submit(&run(400)), Ok(false));
+        assert_eq!(challenge.submit(&run(200)), Ok(true));
+        assert_eq!(
+            challenge.load_best().map(|best| best.get_time()),
+            Some(200.0 / 60.0)
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
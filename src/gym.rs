@@ -0,0 +1,496 @@
+//! Module de l'environnement d'apprentissage par renforcement.
+//!
+//! Ce module expose la simulation du jeu sous la forme d'un environnement de type « gym » :
+//! `reset(seed)` démarre une nouvelle partie et retourne une observation, puis `step(action)`
+//! applique une commande pendant une frame et retourne l'observation suivante, une récompense
+//! et un indicateur de fin de partie. La simulation tourne sans fenêtre.
+//!
+//! ## Protocole texte
+//! Lancé avec `--env` (observations par rayons) ou `--env-entities` (listes d'entités),
+//! le programme lit une commande par ligne sur l'entrée standard :
+//! - `reset <graine>` : répond par l'observation initiale ;
+//! - `step <rotation> <poussée> <tir 0|1>` : répond par `<récompense> <fin 0|1> <observation>` ;
+//! - `quit` : termine le programme.
+//!
+//! Les observations sont écrites sous forme de nombres séparés par des espaces.
+
+use crate::{
+    apply_control,
+    asteroid::{self, Asteroid},
+    control::ShipControl,
+    events::GameEvent,
+    handle_collisions,
+    missile::Missile,
+    spaceship::Spaceship,
+    update_model,
+};
+use ::rand::{rngs::SmallRng, SeedableRng};
+use macroquad::prelude::*;
+use std::f32::consts::PI;
+use std::io::{self, BufRead, Write};
+
+/// Forme des observations retournées par l'environnement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObservationKind {
+    /// Capteurs par lancer de rayons autour du vaisseau.
+    Rays,
+    /// Listes brutes des entités.
+    Entities,
+}
+
+/// État du vaisseau spatial dans une observation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipState {
+    /// Position du vaisseau.
+    pub position: Vec2,
+    /// Vitesse du vaisseau.
+    pub velocity: Vec2,
+    /// Rotation du vaisseau (en radians).
+    pub rotation: f32,
+}
+
+/// État d'un astéroïde dans une observation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsteroidState {
+    /// Position de l'astéroïde.
+    pub position: Vec2,
+    /// Vitesse de l'astéroïde.
+    pub speed: Vec2,
+    /// Taille de l'astéroïde.
+    pub size: f32,
+}
+
+/// Observation de l'état de la partie.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Observation {
+    /// Distances normalisées (de `0.0` à `1.0`) au premier astéroïde dans chaque direction,
+    /// en partant de l'avant du vaisseau, suivies de la vitesse du vaisseau.
+    Rays(Vec<f32>),
+    /// Listes brutes des entités.
+    Entities {
+        /// État du vaisseau.
+        spaceship: ShipState,
+        /// État des astéroïdes.
+        asteroids: Vec<AsteroidState>,
+        /// Positions des missiles actifs.
+        missiles: Vec<Vec2>,
+    },
+}
+
+impl Observation {
+    /// Écrit l'observation sous forme de nombres séparés par des espaces.
+    ///
+    /// Pour les entités, l'ordre est : vaisseau (`x y vx vy rotation`), nombre d'astéroïdes
+    /// puis `x y vx vy taille` pour chacun, nombre de missiles puis `x y` pour chacun.
+    pub fn to_line(&self) -> String {
+        let values = match self {
+            Observation::Rays(rays) => rays.clone(),
+            Observation::Entities {
+                spaceship,
+                asteroids,
+                missiles,
+            } => {
+                let mut values = vec![
+                    spaceship.position.x,
+                    spaceship.position.y,
+                    spaceship.velocity.x,
+                    spaceship.velocity.y,
+                    spaceship.rotation,
+                    asteroids.len() as f32,
+                ];
+                for asteroid in asteroids {
+                    values.extend([
+                        asteroid.position.x,
+                        asteroid.position.y,
+                        asteroid.speed.x,
+                        asteroid.speed.y,
+                        asteroid.size,
+                    ]);
+                }
+                values.push(missiles.len() as f32);
+                for missile in missiles {
+                    values.extend([missile.x, missile.y]);
+                }
+                values
+            }
+        };
+
+        values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Résultat d'une étape de simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// Observation après l'étape.
+    pub observation: Observation,
+    /// Récompense obtenue pendant l'étape.
+    pub reward: f32,
+    /// Indique si la partie est terminée.
+    pub done: bool,
+}
+
+/// Environnement d'apprentissage par renforcement sur la simulation du jeu.
+pub struct Env {
+    /// Dimensions de la zone de jeu.
+    bounds: Vec2,
+    /// Nombre d'astéroïdes au début de chaque partie.
+    asteroid_count: usize,
+    /// Forme des observations retournées.
+    kind: ObservationKind,
+    /// Générateur aléatoire propre à cet environnement.
+    rng: SmallRng,
+    /// Astéroïdes de la partie.
+    asteroids: Vec<Asteroid>,
+    /// Vaisseau contrôlé par l'agent.
+    spaceship: Spaceship,
+    /// Missiles actifs.
+    missiles: Vec<Missile>,
+    /// Bouclier restant du vaisseau.
+    health: f32,
+    /// Nombre d'étapes écoulées depuis le début de la partie.
+    steps: u32,
+}
+
+impl Env {
+    /// Dimensions par défaut de la zone de jeu.
+    pub const BOUNDS: Vec2 = Vec2::new(800.0, 600.0);
+    /// Nombre d'astéroïdes par défaut.
+    pub const ASTEROIDS: usize = 5;
+    /// Nombre de rayons des capteurs.
+    pub const RAYS: usize = 16;
+    /// Portée des rayons des capteurs.
+    pub const RAY_LENGTH: f32 = 500.0;
+    /// Nombre maximal d'étapes avant la fin d'une partie.
+    pub const MAX_STEPS: u32 = 10_000;
+    /// Bouclier du vaisseau au début d'une partie.
+    pub const HEALTH: f32 = 3.0;
+    /// Pénalité appliquée quand le vaisseau est percuté.
+    pub const HIT_PENALTY: f32 = 1.0;
+    /// Récompense obtenue quand tous les astéroïdes sont détruits.
+    pub const WIN_REWARD: f32 = 10.0;
+
+    /// Crée un nouvel environnement, initialisé avec la graine `0`.
+    ///
+    /// # Paramètres
+    /// - `bounds`: Les dimensions de la zone de jeu.
+    /// - `asteroid_count`: Le nombre d'astéroïdes au début de chaque partie.
+    /// - `kind`: La forme des observations retournées.
+    pub fn new(bounds: Vec2, asteroid_count: usize, kind: ObservationKind) -> Self {
+        let mut env = Self {
+            bounds,
+            asteroid_count,
+            kind,
+            rng: SmallRng::seed_from_u64(0),
+            asteroids: Vec::new(),
+            spaceship: Spaceship::without_texture(bounds / 2.0),
+            missiles: Vec::new(),
+            health: Self::HEALTH,
+            steps: 0,
+        };
+        env.reset(0);
+        env
+    }
+
+    /// Démarre une nouvelle partie.
+    ///
+    /// # Paramètres
+    /// - `seed`: La graine utilisée pour générer les astéroïdes.
+    ///
+    /// # Retour
+    /// L'observation initiale.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.rng = SmallRng::seed_from_u64(seed);
+        self.asteroids = asteroid::with_rng(&mut self.rng, || {
+            (0..self.asteroid_count)
+                .map(|_| Asteroid::new(self.bounds))
+                .collect()
+        });
+        self.spaceship = Spaceship::without_texture(self.bounds / 2.0);
+        self.missiles.clear();
+        self.health = Self::HEALTH;
+        self.steps = 0;
+        self.observe()
+    }
+
+    /// Applique une commande pendant une frame de simulation.
+    ///
+    /// # Paramètres
+    /// - `action`: La commande appliquée au vaisseau.
+    ///
+    /// # Retour
+    /// L'observation suivante, la récompense et l'indicateur de fin de partie.
+    pub fn step(&mut self, action: ShipControl) -> Step {
+        let mut events = Vec::new();
+        apply_control(
+            &mut self.spaceship,
+            &mut self.missiles,
+            &action,
+            &mut events,
+        );
+        update_model(
+            &mut self.asteroids,
+            &mut self.spaceship,
+            &mut self.missiles,
+            self.bounds,
+        );
+
+        let hit = asteroid::with_rng(&mut self.rng, || {
+            handle_collisions(
+                &mut self.asteroids,
+                &self.spaceship,
+                &mut self.missiles,
+                &mut events,
+            )
+        });
+        if hit {
+            self.health -= 1.0;
+        }
+        self.steps += 1;
+
+        let mut reward: f32 = events.iter().map(Self::reward_for).sum();
+        let won = self.asteroids.is_empty();
+        if won {
+            reward += Self::WIN_REWARD;
+        }
+
+        Step {
+            observation: self.observe(),
+            reward,
+            done: won || self.health < 0.0 || self.steps >= Self::MAX_STEPS,
+        }
+    }
+
+    /// Construit l'observation de l'état actuel de la partie.
+    pub fn observe(&self) -> Observation {
+        match self.kind {
+            ObservationKind::Rays => {
+                let mut values = self.cast_rays();
+                let velocity = self.spaceship.get_velocity();
+                values.extend([velocity.x, velocity.y]);
+                Observation::Rays(values)
+            }
+            ObservationKind::Entities => Observation::Entities {
+                spaceship: ShipState {
+                    position: self.spaceship.get_position(),
+                    velocity: self.spaceship.get_velocity(),
+                    rotation: self.spaceship.get_direction(),
+                },
+                asteroids: self
+                    .asteroids
+                    .iter()
+                    .map(|asteroid| AsteroidState {
+                        position: asteroid.get_position(),
+                        speed: asteroid.get_speed(),
+                        size: asteroid.get_size(),
+                    })
+                    .collect(),
+                missiles: self
+                    .missiles
+                    .iter()
+                    .filter(|missile| missile.is_active())
+                    .map(|missile| missile.get_position())
+                    .collect(),
+            },
+        }
+    }
+
+    /// Retourne la récompense associée à un événement de jeu.
+    ///
+    /// Les petits astéroïdes, plus difficiles à toucher, rapportent davantage.
+    fn reward_for(event: &GameEvent) -> f32 {
+        match *event {
+            GameEvent::AsteroidDestroyed { size, .. } => match size {
+                Asteroid::LARGE => 0.2,
+                Asteroid::MEDIUM => 0.5,
+                _ => 1.0,
+            },
+            GameEvent::SpaceshipHit { .. } => -Self::HIT_PENALTY,
+            GameEvent::MissileFired { .. } | GameEvent::Thrust { .. } | GameEvent::GameOver => 0.0,
+        }
+    }
+
+    /// Lance les rayons des capteurs autour du vaisseau.
+    ///
+    /// # Retour
+    /// Pour chaque rayon, la distance au premier astéroïde divisée par `RAY_LENGTH`.
+    fn cast_rays(&self) -> Vec<f32> {
+        let origin = self.spaceship.get_position();
+        let rotation = self.spaceship.get_direction();
+
+        (0..Self::RAYS)
+            .map(|ray| {
+                let angle = rotation + ray as f32 * 2.0 * PI / Self::RAYS as f32;
+                let direction = Vec2::from_angle(angle);
+                let distance = self
+                    .asteroids
+                    .iter()
+                    .filter_map(|asteroid| {
+                        ray_circle(
+                            origin,
+                            direction,
+                            asteroid.get_position(),
+                            asteroid.get_size() / 2.0,
+                        )
+                    })
+                    .fold(Self::RAY_LENGTH, f32::min);
+                distance / Self::RAY_LENGTH
+            })
+            .collect()
+    }
+}
+
+/// Calcule la distance entre l'origine d'un rayon et le premier point d'un cercle.
+///
+/// # Paramètres
+/// - `origin`: L'origine du rayon.
+/// - `direction`: La direction unitaire du rayon.
+/// - `center`: Le centre du cercle.
+/// - `radius`: Le rayon du cercle.
+///
+/// # Retour
+/// La distance d'intersection (`0.0` si l'origine est dans le cercle), ou `None` si le rayon
+/// ne touche pas le cercle.
+fn ray_circle(origin: Vec2, direction: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let to_center = center - origin;
+    let projection = to_center.dot(direction);
+    let distance_squared = to_center.length_squared() - projection * projection;
+    let radius_squared = radius * radius;
+    if distance_squared > radius_squared {
+        return None;
+    }
+
+    let half_chord = (radius_squared - distance_squared).sqrt();
+    if projection - half_chord >= 0.0 {
+        Some(projection - half_chord)
+    } else if projection + half_chord >= 0.0 {
+        Some(0.0)
+    } else {
+        None
+    }
+}
+
+/// Lit une commande `step` sous la forme `<rotation> <poussée> <tir 0|1>`.
+///
+/// La rotation va de `-1.0` à `1.0` et la poussée de `0.0` à `1.0`.
+fn parse_action<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<ShipControl> {
+    Some(ShipControl {
+        rotate: words.next()?.parse().ok()?,
+        thrust: words.next()?.parse().ok()?,
+        fire: words.next()? == "1",
+        aim: None,
+    })
+}
+
+/// Exécute l'environnement en lisant les commandes sur l'entrée standard.
+///
+/// Voir la documentation du module pour le protocole.
+///
+/// # Paramètres
+/// - `kind`: La forme des observations retournées.
+pub fn run_stdio(kind: ObservationKind) {
+    let mut env = Env::new(Env::BOUNDS, Env::ASTEROIDS, kind);
+    let mut stdout = io::stdout().lock();
+
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let mut words = line.split_whitespace();
+
+        let reply = match words.next() {
+            Some("reset") => {
+                let seed = words.next().and_then(|word| word.parse().ok()).unwrap_or(0);
+                env.reset(seed).to_line()
+            }
+            Some("step") => match parse_action(words) {
+                Some(action) => {
+                    let step = env.step(action);
+                    format!(
+                        "{} {} {}",
+                        step.reward,
+                        u8::from(step.done),
+                        step.observation.to_line()
+                    )
+                }
+                None => "error: step <rotation> <thrust> <fire 0|1>".to_string(),
+            },
+            Some("quit") => break,
+            _ => format!("error: unknown command `{line}`"),
+        };
+
+        if writeln!(stdout, "{reply}")
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_is_deterministic() {
+        let mut env = Env::new(Env::BOUNDS, Env::ASTEROIDS, ObservationKind::Entities);
+        let first = env.reset(7);
+        let second = env.reset(7);
+        assert_eq!(first, second);
+
+        let Observation::Entities { asteroids, .. } = first else {
+            panic!("observation par entités attendue");
+        };
+        assert_eq!(asteroids.len(), Env::ASTEROIDS);
+    }
+
+    #[test]
+    fn test_step_replays_identically() {
+        let mut env_a = Env::new(Env::BOUNDS, Env::ASTEROIDS, ObservationKind::Rays);
+        let mut env_b = Env::new(Env::BOUNDS, Env::ASTEROIDS, ObservationKind::Rays);
+        env_a.reset(3);
+        env_b.reset(3);
+
+        let action = ShipControl {
+            rotate: 1.0,
+            thrust: 1.0,
+            fire: true,
+            aim: None,
+        };
+        for _ in 0..100 {
+            assert_eq!(env_a.step(action), env_b.step(action));
+        }
+    }
+
+    #[test]
+    fn test_rays_observation() {
+        let mut env = Env::new(Env::BOUNDS, Env::ASTEROIDS, ObservationKind::Rays);
+        let step = env.step(ShipControl::default());
+
+        let Observation::Rays(values) = step.observation else {
+            panic!("observation par rayons attendue");
+        };
+        assert_eq!(values.len(), Env::RAYS + 2);
+        assert!(values[..Env::RAYS]
+            .iter()
+            .all(|&d| (0.0..=1.0).contains(&d)));
+        assert!(!step.done);
+    }
+
+    #[test]
+    fn test_ray_circle() {
+        let hit = ray_circle(vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(100.0, 0.0), 10.0);
+        assert_eq!(hit, Some(90.0));
+
+        let behind = ray_circle(vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(-100.0, 0.0), 10.0);
+        assert_eq!(behind, None);
+
+        let inside = ray_circle(vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(5.0, 0.0), 10.0);
+        assert_eq!(inside, Some(0.0));
+    }
+}
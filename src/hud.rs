@@ -0,0 +1,444 @@
+//! Module de l'interface de jeu (HUD) pour un jeu Asteroids.
+//!
+//! Ce module affiche par-dessus la partie le score, le vaisseau restant, le bouclier, la vague en
+//! cours, le nombre d'astéroïdes restants, l'arme et sa recharge, le temps écoulé et,
+//! sur demande, le nombre d'images par seconde.
+//!
+//! Chaque élément est placé par rapport à un coin ou un bord de la vue (`Anchor`) :
+//! la disposition suit la taille de la vue au lieu de dépendre de coordonnées fixes.
+
+use crate::{asteroid::Asteroid, events::GameEvent, locale::tr, render::Renderer, view};
+use macroquad::prelude::*;
+
+/// Point d'ancrage d'un élément de l'interface dans la vue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    /// Coin supérieur gauche.
+    TopLeft,
+    /// Milieu du bord supérieur.
+    TopCenter,
+    /// Coin supérieur droit.
+    TopRight,
+    /// Coin inférieur gauche.
+    BottomLeft,
+    /// Milieu du bord inférieur.
+    BottomCenter,
+    /// Coin inférieur droit.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Calcule le coin supérieur gauche d'un élément ancré dans une vue.
+    ///
+    /// # Paramètres
+    /// - `size`: Les dimensions de l'élément.
+    /// - `margin`: L'écart entre l'élément et le bord d'ancrage, vers l'intérieur de la vue.
+    /// - `area`: Les dimensions de la vue.
+    ///
+    /// # Retour
+    /// La position du coin supérieur gauche de l'élément.
+    pub fn place(self, size: Vec2, margin: Vec2, area: Vec2) -> Vec2 {
+        let x = match self {
+            Anchor::TopLeft | Anchor::BottomLeft => margin.x,
+            Anchor::TopCenter | Anchor::BottomCenter => (area.x - size.x) / 2.0 + margin.x,
+            Anchor::TopRight | Anchor::BottomRight => area.x - size.x - margin.x,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::TopCenter | Anchor::TopRight => margin.y,
+            Anchor::BottomLeft | Anchor::BottomCenter | Anchor::BottomRight => {
+                area.y - size.y - margin.y
+            }
+        };
+        vec2(x, y)
+    }
+}
+
+/// État de la partie affiché par l'interface à chaque frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HudStatus {
+    /// Nombre de vaisseaux restants, vaisseau actuel compris.
+    pub lives: u32,
+    /// Charge du bouclier, de `0.0` (vide) à `1.0` (plein).
+    pub shield: f32,
+    /// Numéro de la vague en cours, à partir de 1.
+    pub wave: u32,
+    /// Nombre d'astéroïdes restant dans le monde.
+    pub asteroids: usize,
+    /// Nom de l'arme équipée.
+    pub weapon: &'static str,
+    /// Fraction du temps de recharge de l'arme restante, de `0.0` (prête) à `1.0`, ou
+    /// `None` pour une arme sans recharge, dont la jauge n'est pas affichée.
+    pub reload: Option<f32>,
+    /// Indique si le son est coupé.
+    pub muted: bool,
+    /// Objectif du niveau joué, absent hors campagne et éditeur.
+    pub objective: Option<String>,
+}
+
+/// Interface affichée pendant la partie.
+pub struct Hud {
+    /// Score de la partie.
+    score: u32,
+    /// Temps de jeu écoulé, en secondes, hors pause.
+    elapsed: f32,
+    /// Indique si le nombre d'images par seconde est affiché.
+    show_fps: bool,
+    /// Multiplicateur des points gagnés.
+    multiplier: f32,
+    /// Message affiché et durée d'affichage restante, en secondes.
+    message: Option<(String, f32)>,
+}
+
+impl Hud {
+    /// Points gagnés par la destruction d'un grand, moyen et petit astéroïde.
+    pub const POINTS: [u32; 3] = [20, 50, 100];
+    /// Écart entre les éléments et les bords de la vue.
+    pub const MARGIN: f32 = 20.0;
+    /// Taille du texte de l'interface.
+    pub const FONT_SIZE: f32 = 28.0;
+    /// Dimensions des jauges du bouclier et de la recharge.
+    pub const GAUGE_SIZE: Vec2 = vec2(150.0, 16.0);
+    /// Durée d'affichage d'un message, en secondes.
+    pub const MESSAGE_TIME: f32 = 3.0;
+
+    /// Crée une interface pour une nouvelle partie.
+    ///
+    /// # Paramètres
+    /// - `show_fps`: Indique si le nombre d'images par seconde est affiché.
+    /// - `multiplier`: Multiplicateur des points gagnés, fixé par la difficulté.
+    pub fn new(show_fps: bool, multiplier: f32) -> Self {
+        Self {
+            score: 0,
+            elapsed: 0.0,
+            show_fps,
+            multiplier,
+            message: None,
+        }
+    }
+
+    /// Retourne le score de la partie.
+    pub fn get_score(&self) -> u32 {
+        self.score
+    }
+
+    /// Indique si le nombre d'images par seconde est affiché.
+    pub fn get_show_fps(&self) -> bool {
+        self.show_fps
+    }
+
+    /// Affiche ou masque le nombre d'images par seconde.
+    pub fn toggle_fps(&mut self) {
+        self.show_fps = !self.show_fps;
+    }
+
+    /// Modifie le multiplicateur des points gagnés par la suite.
+    pub fn set_multiplier(&mut self, multiplier: f32) {
+        self.multiplier = multiplier;
+    }
+
+    /// Affiche un message au centre de l'écran pendant `MESSAGE_TIME` secondes, à la
+    /// place du précédent.
+    pub fn show_message(&mut self, text: impl Into<String>) {
+        self.message = Some((text.into(), Self::MESSAGE_TIME));
+    }
+
+    /// Ajoute au score les astéroïdes détruits pendant une frame.
+    ///
+    /// Les petits astéroïdes, plus difficiles à toucher, rapportent le plus de points ;
+    /// les points sont multipliés selon la difficulté, puis arrondis.
+    ///
+    /// # Paramètres
+    /// - `events`: Les événements survenus pendant la frame.
+    pub fn handle_events(&mut self, events: &[GameEvent]) {
+        for event in events {
+            if let GameEvent::AsteroidDestroyed { size, .. } = *event {
+                self.score += (points(size) as f32 * self.multiplier).round() as u32;
+            }
+        }
+    }
+
+    /// Fait avancer le chronomètre de la partie.
+    ///
+    /// # Paramètres
+    /// - `delta`: La durée de la frame, en secondes.
+    pub fn update(&mut self, delta: f32) {
+        self.elapsed += delta;
+        if let Some((_, remaining)) = &mut self.message {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                self.message = None;
+            }
+        }
+    }
+
+    /// Dessine l'interface par-dessus la partie.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    /// - `status`: L'état de la partie à afficher.
+    pub fn draw(&self, renderer: &mut dyn Renderer, status: &HudStatus) {
+        let area = vec2(view::WIDTH, view::HEIGHT);
+        let margin = vec2(Self::MARGIN, Self::MARGIN);
+        let line = Self::FONT_SIZE + 6.0;
+
+        // En haut à gauche : score et vague
+        draw_label(
+            renderer,
+            &format!("{} {}", tr("game.score"), self.score),
+            Anchor::TopLeft,
+            margin,
+            WHITE,
+        );
+        draw_label(
+            renderer,
+            &format!("{} {}", tr("hud.wave"), status.wave),
+            Anchor::TopLeft,
+            margin + vec2(0.0, line),
+            LIGHTGRAY,
+        );
+
+        if let Some(objective) = &status.objective {
+            draw_label(
+                renderer,
+                objective,
+                Anchor::TopLeft,
+                margin + vec2(0.0, line * 2.0),
+                SKYBLUE,
+            );
+        }
+
+        // En haut au centre : temps écoulé et images par seconde
+        draw_label(
+            renderer,
+            &format_time(self.elapsed),
+            Anchor::TopCenter,
+            vec2(0.0, Self::MARGIN),
+            WHITE,
+        );
+        if self.show_fps {
+            draw_label(
+                renderer,
+                &format!("{} FPS", get_fps()),
+                Anchor::TopCenter,
+                vec2(0.0, Self::MARGIN + line),
+                GRAY,
+            );
+        }
+
+        // Au centre : message
+        if let Some((text, _)) = &self.message {
+            let size = renderer.measure_text(text, Self::FONT_SIZE);
+            renderer.text(
+                text,
+                vec2((area.x - size.width) / 2.0, area.y * 0.3),
+                Self::FONT_SIZE,
+                YELLOW,
+            );
+        }
+
+        // En bas à gauche : bouclier et vaisseaux restants
+        let shield = Anchor::BottomLeft.place(Self::GAUGE_SIZE, margin, area);
+        draw_gauge(renderer, shield, status.shield, GREEN);
+        let ship = Anchor::BottomLeft.place(vec2(16.0, 16.0), margin + vec2(0.0, line), area);
+        for life in 0..status.lives {
+            let corner = ship + vec2(life as f32 * 24.0, 0.0);
+            renderer.triangle(
+                corner + vec2(8.0, 0.0),
+                corner + vec2(0.0, 16.0),
+                corner + vec2(16.0, 16.0),
+                SKYBLUE,
+            );
+        }
+
+        // En bas à droite : arme, recharge et astéroïdes restants
+        if let Some(reload) = status.reload {
+            let gauge = Anchor::BottomRight.place(Self::GAUGE_SIZE, margin, area);
+            draw_gauge(renderer, gauge, 1.0 - reload, ORANGE);
+        }
+        draw_label(
+            renderer,
+            status.weapon,
+            Anchor::BottomRight,
+            margin + vec2(0.0, Self::GAUGE_SIZE.y + 6.0),
+            WHITE,
+        );
+        draw_label(
+            renderer,
+            &format!("{} {}", tr("hud.asteroids"), status.asteroids),
+            Anchor::BottomRight,
+            margin + vec2(0.0, Self::GAUGE_SIZE.y + 6.0 + line),
+            YELLOW,
+        );
+
+        // En bas au centre : son coupé
+        if status.muted {
+            draw_label(
+                renderer,
+                tr("hud.muted"),
+                Anchor::BottomCenter,
+                vec2(0.0, Self::MARGIN),
+                GRAY,
+            );
+        }
+    }
+}
+
+/// Retourne les points rapportés par la destruction d'un astéroïde.
+///
+/// # Paramètres
+/// - `size`: La taille de l'astéroïde détruit.
+fn points(size: f32) -> u32 {
+    if size >= Asteroid::LARGE {
+        Hud::POINTS[0]
+    } else if size >= Asteroid::MEDIUM {
+        Hud::POINTS[1]
+    } else {
+        Hud::POINTS[2]
+    }
+}
+
+/// Met en forme une durée en minutes et secondes (`mm:ss`).
+///
+/// # Paramètres
+/// - `seconds`: La durée, en secondes.
+fn format_time(seconds: f32) -> String {
+    let total = seconds.max(0.0) as u32;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
+
+/// Dessine un texte ancré dans la vue.
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+/// - `text`: Le texte à afficher.
+/// - `anchor`: Le point d'ancrage du texte.
+/// - `margin`: L'écart entre le texte et le bord d'ancrage.
+/// - `color`: La couleur du texte.
+fn draw_label(renderer: &mut dyn Renderer, text: &str, anchor: Anchor, margin: Vec2, color: Color) {
+    let size = renderer.measure_text(text, Hud::FONT_SIZE);
+    let corner = anchor.place(
+        vec2(size.width, Hud::FONT_SIZE),
+        margin,
+        vec2(view::WIDTH, view::HEIGHT),
+    );
+    // Le texte est dessiné à partir de sa ligne de base
+    renderer.text(
+        text,
+        vec2(corner.x, corner.y + size.offset_y),
+        Hud::FONT_SIZE,
+        color,
+    );
+}
+
+/// Dessine une jauge horizontale.
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+/// - `corner`: Le coin supérieur gauche de la jauge.
+/// - `fill`: Le remplissage de la jauge, de `0.0` à `1.0`.
+/// - `color`: La couleur de la partie remplie.
+fn draw_gauge(renderer: &mut dyn Renderer, corner: Vec2, fill: f32, color: Color) {
+    let size = Hud::GAUGE_SIZE;
+    renderer.rectangle(corner, size, Color::from_rgba(50, 50, 50, 200));
+    renderer.rectangle(corner, vec2(size.x * fill.clamp(0.0, 1.0), size.y), color);
+    renderer.rectangle_lines(corner, size, 1.0, GRAY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Svg;
+
+    #[test]
+    fn test_anchor_place() {
+        let area = vec2(1280.0, 720.0);
+        let size = vec2(100.0, 20.0);
+        let margin = vec2(10.0, 10.0);
+
+        assert_eq!(Anchor::TopLeft.place(size, margin, area), vec2(10.0, 10.0));
+        assert_eq!(
+            Anchor::TopCenter.place(size, Vec2::ZERO, area),
+            vec2(590.0, 0.0)
+        );
+        assert_eq!(
+            Anchor::BottomRight.place(size, margin, area),
+            vec2(1170.0, 690.0)
+        );
+        // La disposition suit les dimensions de la vue
+        assert_eq!(
+            Anchor::BottomLeft.place(size, margin, vec2(800.0, 600.0)),
+            vec2(10.0, 570.0)
+        );
+    }
+
+    #[test]
+    fn test_score_by_tier() {
+        let mut hud = Hud::new(false, 1.0);
+        let destroyed = |size| GameEvent::AsteroidDestroyed {
+            size,
+            position: vec2(0.0, 0.0),
+        };
+        hud.handle_events(&[
+            destroyed(Asteroid::LARGE),
+            destroyed(Asteroid::MEDIUM),
+            destroyed(Asteroid::SMALL),
+            GameEvent::MissileFired {
+                position: vec2(0.0, 0.0),
+            },
+        ]);
+
+        assert_eq!(hud.get_score(), 170);
+
+        let mut doubled = Hud::new(false, 2.0);
+        doubled.handle_events(&[destroyed(Asteroid::SMALL)]);
+        assert_eq!(doubled.get_score(), 200);
+    }
+
+    #[test]
+    fn test_message_expires() {
+        let mut hud = Hud::new(false, 1.0);
+        hud.show_message("Wave 2");
+        hud.update(Hud::MESSAGE_TIME - 0.5);
+        assert_eq!(
+            hud.message.as_ref().map(|(text, _)| text.as_str()),
+            Some("Wave 2")
+        );
+
+        hud.update(1.0);
+        assert_eq!(hud.message, None);
+    }
+
+    #[test]
+    fn test_draws_without_gpu() {
+        let mut svg = Svg::new(vec2(view::WIDTH, view::HEIGHT));
+        Hud::new(false, 1.0).draw(
+            &mut svg,
+            &HudStatus {
+                lives: 1,
+                shield: 0.5,
+                wave: 2,
+                asteroids: 7,
+                weapon: "Missile",
+                reload: Some(0.0),
+                muted: false,
+                objective: None,
+            },
+        );
+
+        let frame = svg.to_svg();
+        assert!(frame.contains(">Score 0</text>"));
+        assert!(frame.contains(">Asteroids 7</text>"));
+        assert_eq!(frame.matches("<polygon").count(), 1);
+        // Deux jauges : fond, remplissage et contour, après le fond de l'image
+        assert_eq!(frame.matches("<rect").count(), 1 + 2 * 3);
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0.0), "00:00");
+        assert_eq!(format_time(75.9), "01:15");
+        assert_eq!(format_time(3600.0), "60:00");
+    }
+}
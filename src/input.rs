@@ -0,0 +1,983 @@
+//! Module des entrées du joueur pour un jeu Asteroids.
+//!
+//! Ce module définit les actions du jeu (`Action`) et leurs associations (`Bindings`)
+//! avec les touches du clavier, les boutons et les joysticks des manettes. Chaque action
+//! peut avoir plusieurs associations. Les entrées du clavier et de toutes les manettes
+//! sont fusionnées pour produire les commandes du vaisseau.
+//!
+//! ## Fichier de configuration
+//! Les associations sont lues depuis `controls.cfg`, une ligne par action :
+//!
+//! ```text
+//! rotate_left = key:Left, stick:left
+//! fire = key:Space, button:FrontRightLower
+//! ```
+//!
+//! Les actions absentes du fichier gardent leurs associations par défaut.
+//!
+//! ## Contrôle analogique
+//! Les joysticks et les gâchettes donnent une valeur progressive : la vitesse de rotation
+//! et la poussée sont proportionnelles à leur inclinaison, au-delà d'une zone morte
+//! configurable. En mode « twin-stick », le joystick droit oriente les tirs
+//! indépendamment de la direction du vaisseau. Ces réglages sont lus dans le même fichier :
+//!
+//! ```text
+//! left_dead_zone = 0.2
+//! right_dead_zone = 0.3
+//! trigger_dead_zone = 0.1
+//! twin_stick = true
+//! ```
+//!
+//! ## Visée à la souris
+//! Avec le schéma de contrôle `scheme = mouse`, le vaisseau s'oriente vers le curseur
+//! à sa vitesse de rotation maximale, le bouton gauche tire et le bouton droit accélère.
+//! Le clavier et les manettes restent utilisables pour les autres actions.
+
+use crate::control::{angle_diff, steer, ShipControl};
+use crate::locale::tr;
+use crate::ui::Ui;
+use gamepads::{Button, Gamepad, Gamepads};
+use macroquad::prelude::*;
+use std::fs;
+use std::io;
+
+/// Action du jeu déclenchée par le joueur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Tourner à gauche.
+    RotateLeft,
+    /// Tourner à droite.
+    RotateRight,
+    /// Accélérer.
+    Thrust,
+    /// Tirer un missile.
+    Fire,
+    /// Mettre le jeu en pause.
+    Pause,
+}
+
+impl Action {
+    /// Liste de toutes les actions, dans l'ordre d'affichage.
+    pub const ALL: [Action; 5] = [
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Thrust,
+        Action::Fire,
+        Action::Pause,
+    ];
+
+    /// Retourne le nom de l'action utilisé dans le fichier de configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::RotateLeft => "rotate_left",
+            Action::RotateRight => "rotate_right",
+            Action::Thrust => "thrust",
+            Action::Fire => "fire",
+            Action::Pause => "pause",
+        }
+    }
+
+    /// Retourne le nom de l'action traduit dans la langue de l'interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::RotateLeft => tr("action.rotate_left"),
+            Action::RotateRight => tr("action.rotate_right"),
+            Action::Thrust => tr("action.thrust"),
+            Action::Fire => tr("action.fire"),
+            Action::Pause => tr("action.pause"),
+        }
+    }
+
+    /// Retrouve une action à partir de son nom dans le fichier de configuration.
+    ///
+    /// L'ancien nom `quit` est accepté et désigne la pause.
+    pub fn from_name(name: &str) -> Option<Action> {
+        if name == "quit" {
+            return Some(Action::Pause);
+        }
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// Direction du joystick gauche d'une manette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StickDirection {
+    /// Joystick vers la gauche.
+    Left,
+    /// Joystick vers la droite.
+    Right,
+    /// Joystick vers le haut.
+    Up,
+    /// Joystick vers le bas.
+    Down,
+}
+
+impl StickDirection {
+    /// Liste de toutes les directions.
+    pub const ALL: [StickDirection; 4] = [
+        StickDirection::Left,
+        StickDirection::Right,
+        StickDirection::Up,
+        StickDirection::Down,
+    ];
+
+    /// Retourne le nom de la direction utilisé dans le fichier de configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            StickDirection::Left => "left",
+            StickDirection::Right => "right",
+            StickDirection::Up => "up",
+            StickDirection::Down => "down",
+        }
+    }
+
+    /// Retourne l'inclinaison du joystick dans cette direction (négative dans la direction opposée).
+    fn deflection(self, stick: (f32, f32)) -> f32 {
+        match self {
+            StickDirection::Left => -stick.0,
+            StickDirection::Right => stick.0,
+            StickDirection::Up => stick.1,
+            StickDirection::Down => -stick.1,
+        }
+    }
+}
+
+/// Gâchette analogique d'une manette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerSide {
+    /// Gâchette gauche.
+    Left,
+    /// Gâchette droite.
+    Right,
+}
+
+impl TriggerSide {
+    /// Liste des deux gâchettes.
+    pub const ALL: [TriggerSide; 2] = [TriggerSide::Left, TriggerSide::Right];
+
+    /// Retourne le nom de la gâchette utilisé dans le fichier de configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            TriggerSide::Left => "left",
+            TriggerSide::Right => "right",
+        }
+    }
+}
+
+/// Association d'une action à une entrée physique.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Binding {
+    /// Touche du clavier.
+    Key(KeyCode),
+    /// Bouton d'une manette.
+    Button(Button),
+    /// Direction du joystick gauche d'une manette.
+    Stick(StickDirection),
+    /// Gâchette analogique d'une manette.
+    Trigger(TriggerSide),
+}
+
+impl Binding {
+    /// Inclinaison du joystick à partir de laquelle une direction est considérée comme active.
+    pub const STICK_THRESHOLD: f32 = 0.5;
+
+    /// Écrit l'association sous sa forme du fichier de configuration (par exemple `key:Space`).
+    pub fn to_config(self) -> String {
+        match self {
+            Binding::Key(key) => format!("key:{key:?}"),
+            Binding::Button(button) => format!("button:{button:?}"),
+            Binding::Stick(direction) => format!("stick:{}", direction.name()),
+            Binding::Trigger(side) => format!("trigger:{}", side.name()),
+        }
+    }
+
+    /// Lit une association depuis sa forme du fichier de configuration.
+    ///
+    /// # Retour
+    /// L'association, ou `None` si le texte n'est pas reconnu.
+    pub fn parse(text: &str) -> Option<Binding> {
+        let (kind, name) = text.trim().split_once(':')?;
+        match kind.trim() {
+            "key" => KEYS
+                .into_iter()
+                .find(|key| format!("{key:?}") == name.trim())
+                .map(Binding::Key),
+            "button" => Button::all()
+                .find(|button| format!("{button:?}") == name.trim())
+                .map(Binding::Button),
+            "stick" => StickDirection::ALL
+                .into_iter()
+                .find(|direction| direction.name() == name.trim())
+                .map(Binding::Stick),
+            "trigger" => TriggerSide::ALL
+                .into_iter()
+                .find(|side| side.name() == name.trim())
+                .map(Binding::Trigger),
+            _ => None,
+        }
+    }
+
+    /// Vérifie si l'entrée est maintenue sur le clavier ou sur l'une des manettes.
+    fn is_down(&self, gamepads: &Gamepads) -> bool {
+        match *self {
+            Binding::Key(key) => is_key_down(key),
+            Binding::Button(button) => gamepads
+                .all()
+                .any(|gamepad| gamepad.is_currently_pressed(button)),
+            Binding::Stick(direction) => gamepads
+                .all()
+                .any(|gamepad| direction.deflection(gamepad.left_stick()) > Self::STICK_THRESHOLD),
+            Binding::Trigger(side) => gamepads
+                .all()
+                .any(|gamepad| trigger(&gamepad, side) > Self::STICK_THRESHOLD),
+        }
+    }
+
+    /// Indique si l'entrée est un joystick ou une gâchette.
+    fn is_analog(&self) -> bool {
+        matches!(self, Binding::Stick(_) | Binding::Trigger(_))
+    }
+
+    /// Vérifie si la touche ou le bouton vient d'être enfoncé pendant cette frame.
+    ///
+    /// Un joystick ou une gâchette n'a pas d'état « vient d'être enfoncé » : son état
+    /// précédent est retenu par `InputState`, et cette méthode retourne `false`.
+    fn is_pressed(&self, gamepads: &Gamepads) -> bool {
+        match *self {
+            Binding::Key(key) => is_key_pressed(key),
+            Binding::Button(button) => gamepads
+                .all()
+                .any(|gamepad| gamepad.is_just_pressed(button)),
+            Binding::Stick(_) | Binding::Trigger(_) => false,
+        }
+    }
+
+    /// Retourne la valeur analogique de l'entrée, de `0.0` (relâchée) à `1.0` (enfoncée).
+    ///
+    /// Les touches et les boutons valent `0.0` ou `1.0`. Les joysticks et les gâchettes
+    /// donnent une valeur progressive après application de leur zone morte.
+    fn value(&self, gamepads: &Gamepads, analog: &AnalogSettings) -> f32 {
+        match *self {
+            Binding::Key(_) | Binding::Button(_) => {
+                if self.is_down(gamepads) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Binding::Stick(direction) => gamepads
+                .all()
+                .map(|gamepad| {
+                    let stick = radial_dead_zone(gamepad.left_stick(), analog.left_dead_zone);
+                    direction.deflection(stick).max(0.0)
+                })
+                .fold(0.0, f32::max),
+            Binding::Trigger(side) => gamepads
+                .all()
+                .map(|gamepad| linear_dead_zone(trigger(&gamepad, side), analog.trigger_dead_zone))
+                .fold(0.0, f32::max),
+        }
+    }
+}
+
+/// Schéma de contrôle du vaisseau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlScheme {
+    /// Rotation au clavier ou à la manette.
+    Standard,
+    /// Orientation vers le curseur de la souris.
+    Mouse,
+}
+
+impl ControlScheme {
+    /// Liste de tous les schémas, dans l'ordre d'affichage.
+    pub const ALL: [ControlScheme; 2] = [ControlScheme::Standard, ControlScheme::Mouse];
+
+    /// Retourne le nom du schéma utilisé dans le fichier de configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            ControlScheme::Standard => "standard",
+            ControlScheme::Mouse => "mouse",
+        }
+    }
+
+    /// Retrouve un schéma à partir de son nom dans le fichier de configuration.
+    pub fn from_name(name: &str) -> Option<ControlScheme> {
+        [ControlScheme::Standard, ControlScheme::Mouse]
+            .into_iter()
+            .find(|scheme| scheme.name() == name)
+    }
+}
+
+/// Réglages du contrôle analogique des manettes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalogSettings {
+    /// Zone morte radiale du joystick gauche, de `0.0` à `1.0`.
+    pub left_dead_zone: f32,
+    /// Zone morte radiale du joystick droit, de `0.0` à `1.0`.
+    pub right_dead_zone: f32,
+    /// Zone morte des gâchettes, de `0.0` à `1.0`.
+    pub trigger_dead_zone: f32,
+    /// Indique si le joystick droit oriente les tirs indépendamment du vaisseau.
+    pub twin_stick: bool,
+}
+
+impl Default for AnalogSettings {
+    fn default() -> Self {
+        Self {
+            left_dead_zone: 0.2,
+            right_dead_zone: 0.3,
+            trigger_dead_zone: 0.1,
+            twin_stick: false,
+        }
+    }
+}
+
+impl AnalogSettings {
+    /// Lit un réglage analogique depuis une ligne du fichier de configuration.
+    ///
+    /// # Paramètres
+    /// - `name`: Le nom du réglage.
+    /// - `value`: La valeur du réglage.
+    ///
+    /// # Retour
+    /// `true` si le nom correspond à un réglage analogique, sinon `false`. Une valeur
+    /// invalide laisse le réglage inchangé.
+    fn parse_setting(&mut self, name: &str, value: &str) -> bool {
+        let dead_zone = match name {
+            "left_dead_zone" => &mut self.left_dead_zone,
+            "right_dead_zone" => &mut self.right_dead_zone,
+            "trigger_dead_zone" => &mut self.trigger_dead_zone,
+            "twin_stick" => {
+                if let Ok(twin_stick) = value.parse() {
+                    self.twin_stick = twin_stick;
+                }
+                return true;
+            }
+            _ => return false,
+        };
+        if let Ok(parsed) = value.parse::<f32>() {
+            *dead_zone = parsed.clamp(0.0, 0.95);
+        }
+        true
+    }
+}
+
+/// Applique une zone morte radiale à un joystick.
+///
+/// L'inclinaison est remise à l'échelle pour varier de `0.0` au bord de la zone morte
+/// à `1.0` en butée, sans modifier sa direction.
+///
+/// # Paramètres
+/// - `stick`: L'inclinaison brute du joystick.
+/// - `dead_zone`: Le rayon de la zone morte.
+///
+/// # Retour
+/// L'inclinaison corrigée.
+fn radial_dead_zone(stick: (f32, f32), dead_zone: f32) -> (f32, f32) {
+    let stick = vec2(stick.0, stick.1);
+    let length = stick.length();
+    if length <= dead_zone {
+        return (0.0, 0.0);
+    }
+
+    let scaled = ((length - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    let stick = stick / length * scaled;
+    (stick.x, stick.y)
+}
+
+/// Applique une zone morte à une valeur analogique comprise entre `0.0` et `1.0`.
+fn linear_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value <= dead_zone {
+        0.0
+    } else {
+        ((value - dead_zone) / (1.0 - dead_zone)).min(1.0)
+    }
+}
+
+/// Retourne la valeur brute d'une gâchette d'une manette.
+fn trigger(gamepad: &Gamepad, side: TriggerSide) -> f32 {
+    match side {
+        TriggerSide::Left => gamepad.left_trigger(),
+        TriggerSide::Right => gamepad.right_trigger(),
+    }
+}
+
+/// Entrées lues pendant une frame selon les associations.
+///
+/// Un joystick ou une gâchette n'a pas d'état « vient d'être enfoncé » : l'état lu à la
+/// frame précédente est retenu ici, pour ne signaler que le moment où l'entrée dépasse
+/// son seuil, comme un bouton.
+#[derive(Debug, Default)]
+pub struct InputState {
+    /// Joysticks et gâchettes maintenus, avec l'action à laquelle ils sont associés.
+    held: Vec<(Action, Binding)>,
+    /// Actions dont une entrée vient d'être enfoncée pendant la frame lue.
+    pressed: Vec<Action>,
+}
+
+impl InputState {
+    /// Crée un état sans entrée maintenue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lit les entrées de la frame pour toutes les actions.
+    ///
+    /// À appeler une fois par frame, après avoir interrogé les manettes.
+    ///
+    /// # Paramètres
+    /// - `bindings`: Associations des actions aux entrées.
+    /// - `gamepads`: Manettes, déjà interrogées pour cette frame.
+    pub fn update(&mut self, bindings: &Bindings, gamepads: &Gamepads) {
+        // Les entrées qui ne sont plus associées à leur action sont oubliées
+        self.held
+            .retain(|(action, binding)| bindings.get(*action).contains(binding));
+        self.pressed.clear();
+        for action in Action::ALL {
+            // Toutes les entrées sont lues, pour que l'état des entrées analogiques reste à jour
+            let pressed = bindings
+                .get(action)
+                .iter()
+                .fold(false, |pressed, &binding| {
+                    let just_pressed = if binding.is_analog() {
+                        self.press(action, binding, binding.is_down(gamepads))
+                    } else {
+                        binding.is_pressed(gamepads)
+                    };
+                    pressed | just_pressed
+                });
+            if pressed {
+                self.pressed.push(action);
+            }
+        }
+    }
+
+    /// Vérifie si l'une des entrées associées à l'action vient d'être enfoncée pendant la
+    /// frame lue par `update`.
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Retient l'état d'une entrée analogique associée à une action.
+    ///
+    /// # Paramètres
+    /// - `action`: L'action lue.
+    /// - `binding`: L'entrée analogique.
+    /// - `down`: Indique si l'entrée dépasse son seuil.
+    ///
+    /// # Retour
+    /// `true` si l'entrée dépasse son seuil alors qu'elle ne le dépassait pas à la
+    /// lecture précédente.
+    fn press(&mut self, action: Action, binding: Binding, down: bool) -> bool {
+        let was_down = self.held.contains(&(action, binding));
+        if down && !was_down {
+            self.held.push((action, binding));
+        } else if !down {
+            self.held.retain(|&entry| entry != (action, binding));
+        }
+        down && !was_down
+    }
+}
+
+/// Associations de toutes les actions du jeu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bindings {
+    /// Associations de chaque action, dans l'ordre de `Action::ALL`.
+    bindings: Vec<(Action, Vec<Binding>)>,
+    /// Réglages du contrôle analogique.
+    pub analog: AnalogSettings,
+    /// Schéma de contrôle du vaisseau.
+    pub scheme: ControlScheme,
+}
+
+impl Default for Bindings {
+    /// Associations par défaut, identiques aux contrôles d'origine du jeu.
+    fn default() -> Self {
+        Self {
+            bindings: vec![
+                (
+                    Action::RotateLeft,
+                    vec![
+                        Binding::Key(KeyCode::Left),
+                        Binding::Stick(StickDirection::Left),
+                    ],
+                ),
+                (
+                    Action::RotateRight,
+                    vec![
+                        Binding::Key(KeyCode::Right),
+                        Binding::Stick(StickDirection::Right),
+                    ],
+                ),
+                (
+                    Action::Thrust,
+                    vec![
+                        Binding::Key(KeyCode::Up),
+                        Binding::Stick(StickDirection::Up),
+                        Binding::Trigger(TriggerSide::Left),
+                    ],
+                ),
+                (
+                    Action::Fire,
+                    vec![
+                        Binding::Key(KeyCode::Space),
+                        Binding::Button(Button::FrontRightLower),
+                    ],
+                ),
+                (
+                    Action::Pause,
+                    vec![
+                        Binding::Key(KeyCode::Escape),
+                        Binding::Button(Button::ActionRight),
+                        Binding::Button(Button::RightCenterCluster),
+                    ],
+                ),
+            ],
+            analog: AnalogSettings::default(),
+            scheme: ControlScheme::Standard,
+        }
+    }
+}
+
+impl Bindings {
+    /// Chemin du fichier de configuration des contrôles.
+    pub const FILE: &'static str = "controls.cfg";
+
+    /// Charge les associations depuis le fichier de configuration.
+    ///
+    /// # Retour
+    /// Les associations lues, ou les associations par défaut si le fichier n'existe pas.
+    pub fn load() -> Self {
+        fs::read_to_string(Self::FILE)
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    /// Enregistre les associations dans le fichier de configuration.
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(Self::FILE, self.to_config())
+    }
+
+    /// Lit les associations depuis le contenu d'un fichier de configuration.
+    ///
+    /// Les lignes vides, les commentaires (`#`) et les entrées non reconnues sont ignorés.
+    /// Les actions absentes gardent leurs associations par défaut.
+    pub fn parse(text: &str) -> Self {
+        let mut bindings = Self::default();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, values)) = line.split_once('=') else {
+                continue;
+            };
+            if name.trim() == "scheme" {
+                if let Some(scheme) = ControlScheme::from_name(values.trim()) {
+                    bindings.scheme = scheme;
+                }
+                continue;
+            }
+            if bindings.analog.parse_setting(name.trim(), values.trim()) {
+                continue;
+            }
+            let Some(action) = Action::from_name(name.trim()) else {
+                continue;
+            };
+
+            let parsed = values
+                .split(',')
+                .filter(|value| !value.trim().is_empty())
+                .filter_map(Binding::parse)
+                .collect();
+            *bindings.get_mut(action) = parsed;
+        }
+
+        bindings
+    }
+
+    /// Écrit les associations sous la forme du fichier de configuration.
+    pub fn to_config(&self) -> String {
+        let mut text =
+            String::from("# action = key:<touche>, button:<bouton>, stick:<direction>\n");
+        for (action, bindings) in &self.bindings {
+            let values: Vec<String> = bindings.iter().map(|binding| binding.to_config()).collect();
+            text.push_str(&format!("{} = {}\n", action.name(), values.join(", ")));
+        }
+        text.push_str(&format!(
+            "left_dead_zone = {}\nright_dead_zone = {}\ntrigger_dead_zone = {}\ntwin_stick = {}\n",
+            self.analog.left_dead_zone,
+            self.analog.right_dead_zone,
+            self.analog.trigger_dead_zone,
+            self.analog.twin_stick,
+        ));
+        text.push_str(&format!("scheme = {}\n", self.scheme.name()));
+        text
+    }
+
+    /// Retourne les associations d'une action.
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, bindings)| bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Ajoute une association à une action, si elle n'y est pas déjà.
+    pub fn add(&mut self, action: Action, binding: Binding) {
+        let bindings = self.get_mut(action);
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Supprime toutes les associations d'une action.
+    pub fn clear(&mut self, action: Action) {
+        self.get_mut(action).clear();
+    }
+
+    /// Ajoute à une interface une ligne par action, qui affiche ses associations, puis la
+    /// case du mode « twin-stick ».
+    ///
+    /// # Paramètres
+    /// - `ui`: L'interface du menu en cours de construction.
+    ///
+    /// # Retour
+    /// L'action validée par le joueur, à laquelle associer une nouvelle entrée.
+    pub fn edit(&mut self, ui: &mut Ui) -> Option<Action> {
+        let mut chosen = None;
+        for action in Action::ALL {
+            let values: Vec<String> = self
+                .get(action)
+                .iter()
+                .map(|binding| binding.to_config())
+                .collect();
+            if ui.entry(action.label(), &values.join(", ")) {
+                chosen = Some(action);
+            }
+        }
+        ui.toggle(tr("controls.twin_stick"), &mut self.analog.twin_stick);
+        chosen
+    }
+
+    /// Retourne la valeur analogique d'une action, la plus forte de ses entrées.
+    pub fn value(&self, action: Action, gamepads: &Gamepads) -> f32 {
+        self.get(action)
+            .iter()
+            .map(|binding| binding.value(gamepads, &self.analog))
+            .fold(0.0, f32::max)
+    }
+
+    /// Construit les commandes du vaisseau à partir du clavier et de toutes les manettes.
+    ///
+    /// Une action prend la valeur la plus forte de ses entrées : une manette au repos
+    /// n'annule donc pas une touche du clavier maintenue. En mode « twin-stick », le
+    /// joystick droit incliné au-delà de sa zone morte donne la direction de tir.
+    ///
+    /// # Paramètres
+    /// - `gamepads`: Manettes, déjà interrogées pour cette frame.
+    /// - `input`: Les entrées de la frame, qui indiquent si le tir vient d'être demandé.
+    pub fn read_control(&self, gamepads: &Gamepads, input: &InputState) -> ShipControl {
+        let mut control = ShipControl {
+            rotate: self.value(Action::RotateRight, gamepads)
+                - self.value(Action::RotateLeft, gamepads),
+            thrust: self.value(Action::Thrust, gamepads),
+            fire: input.is_pressed(Action::Fire),
+            aim: None,
+        };
+
+        if self.analog.twin_stick {
+            control.aim = gamepads.all().find_map(|gamepad| {
+                let (x, y) = radial_dead_zone(gamepad.right_stick(), self.analog.right_dead_zone);
+                // L'axe vertical du joystick est orienté vers le haut, celui de l'écran vers le bas.
+                (x != 0.0 || y != 0.0).then(|| (-y).atan2(x))
+            });
+        }
+
+        control
+    }
+
+    /// Retourne les associations modifiables d'une action.
+    fn get_mut(&mut self, action: Action) -> &mut Vec<Binding> {
+        let index = match self.bindings.iter().position(|(bound, _)| *bound == action) {
+            Some(index) => index,
+            None => {
+                self.bindings.push((action, Vec::new()));
+                self.bindings.len() - 1
+            }
+        };
+        &mut self.bindings[index].1
+    }
+}
+
+/// Oriente le vaisseau vers le curseur de la souris et applique ses boutons.
+///
+/// La rotation remplace celle du clavier et des manettes, limitée à la vitesse de rotation
+/// du vaisseau. Le bouton gauche tire et le bouton droit donne une poussée maximale.
+///
+/// # Paramètres
+/// - `control`: Les commandes lues sur le clavier et les manettes, à compléter.
+/// - `position`: La position du vaisseau.
+/// - `rotation`: La rotation actuelle du vaisseau (en radians).
+/// - `cursor`: La position du curseur, dans le même repère que le vaisseau.
+pub fn apply_mouse_aim(control: &mut ShipControl, position: Vec2, rotation: f32, cursor: Vec2) {
+    let offset = cursor - position;
+    if offset.length_squared() > f32::EPSILON {
+        control.rotate = steer(angle_diff(rotation, offset.y.atan2(offset.x)));
+    }
+    control.fire |= is_mouse_button_pressed(MouseButton::Left);
+    if is_mouse_button_down(MouseButton::Right) {
+        control.thrust = 1.0;
+    }
+}
+
+/// Attend la prochaine entrée du joueur pour l'associer à une action.
+///
+/// # Retour
+/// L'entrée enfoncée pendant cette frame (touche, bouton ou joystick), sinon `None`.
+pub fn capture_binding(gamepads: &Gamepads) -> Option<Binding> {
+    if let Some(key) = get_last_key_pressed().filter(|key| KEYS.contains(key)) {
+        return Some(Binding::Key(key));
+    }
+
+    for gamepad in gamepads.all() {
+        if let Some(button) = gamepad.all_just_pressed().next() {
+            return Some(Binding::Button(button));
+        }
+        if let Some(direction) = StickDirection::ALL
+            .into_iter()
+            .find(|direction| direction.deflection(gamepad.left_stick()) > Binding::STICK_THRESHOLD)
+        {
+            return Some(Binding::Stick(direction));
+        }
+        if let Some(side) = TriggerSide::ALL
+            .into_iter()
+            .find(|&side| trigger(&gamepad, side) > Binding::STICK_THRESHOLD)
+        {
+            return Some(Binding::Trigger(side));
+        }
+    }
+
+    None
+}
+
+/// Touches du clavier pouvant être associées à une action.
+const KEYS: [KeyCode; 90] = [
+    KeyCode::Space,
+    KeyCode::Apostrophe,
+    KeyCode::Comma,
+    KeyCode::Minus,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Semicolon,
+    KeyCode::Equal,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::LeftBracket,
+    KeyCode::Backslash,
+    KeyCode::RightBracket,
+    KeyCode::Escape,
+    KeyCode::Enter,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::Insert,
+    KeyCode::Delete,
+    KeyCode::Right,
+    KeyCode::Left,
+    KeyCode::Down,
+    KeyCode::Up,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Kp0,
+    KeyCode::Kp1,
+    KeyCode::Kp2,
+    KeyCode::Kp3,
+    KeyCode::Kp4,
+    KeyCode::Kp5,
+    KeyCode::Kp6,
+    KeyCode::Kp7,
+    KeyCode::Kp8,
+    KeyCode::Kp9,
+    KeyCode::KpEnter,
+    KeyCode::LeftShift,
+    KeyCode::LeftControl,
+    KeyCode::LeftAlt,
+    KeyCode::RightShift,
+    KeyCode::RightControl,
+    KeyCode::RightAlt,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{Layout, Menu, MenuInput};
+
+    #[test]
+    fn test_binding_round_trip() {
+        let bindings = [
+            Binding::Key(KeyCode::Space),
+            Binding::Button(Button::FrontRightLower),
+            Binding::Stick(StickDirection::Up),
+        ];
+        for binding in bindings {
+            assert_eq!(Binding::parse(&binding.to_config()), Some(binding));
+        }
+        assert_eq!(Binding::parse("key:NotAKey"), None);
+    }
+
+    #[test]
+    fn test_bindings_config_round_trip() {
+        let mut bindings = Bindings::default();
+        bindings.add(Action::Fire, Binding::Key(KeyCode::LeftControl));
+        bindings.clear(Action::Pause);
+
+        assert_eq!(Bindings::parse(&bindings.to_config()), bindings);
+    }
+
+    #[test]
+    fn test_edit_chooses_an_action_and_toggles_twin_stick() {
+        let mut bindings = Bindings::default();
+        let mut menu = Menu::new();
+        let layout = Layout::centered(0.1);
+        let activate = MenuInput {
+            activate: true,
+            ..Default::default()
+        };
+
+        let mut ui = menu.begin(activate, layout);
+        assert_eq!(bindings.edit(&mut ui), Some(Action::RotateLeft));
+        ui.finish();
+
+        // Le focus passe de la dernière action à la case du mode « twin-stick »
+        menu.navigate(false, Action::ALL.len() + 1);
+        let mut ui = menu.begin(activate, layout);
+        assert_eq!(bindings.edit(&mut ui), None);
+        ui.finish();
+        assert!(bindings.analog.twin_stick);
+    }
+
+    #[test]
+    fn test_analog_press_is_reported_once() {
+        let mut held = InputState::new();
+        let trigger = Binding::Trigger(TriggerSide::Right);
+
+        assert!(held.press(Action::Pause, trigger, true));
+        assert!(!held.press(Action::Pause, trigger, true));
+        assert!(held.press(Action::Fire, trigger, true));
+        assert!(!held.press(Action::Pause, trigger, false));
+        assert!(held.press(Action::Pause, trigger, true));
+    }
+
+    #[test]
+    fn test_legacy_quit_action() {
+        assert_eq!(Action::from_name("quit"), Some(Action::Pause));
+
+        let bindings = Bindings::parse("quit = key:Q\n");
+        assert_eq!(bindings.get(Action::Pause), &[Binding::Key(KeyCode::Q)]);
+    }
+
+    #[test]
+    fn test_radial_dead_zone() {
+        assert_eq!(radial_dead_zone((0.1, 0.1), 0.2), (0.0, 0.0));
+
+        let (x, y) = radial_dead_zone((0.6, 0.0), 0.2);
+        assert!((x - 0.5).abs() < 1e-5);
+        assert_eq!(y, 0.0);
+
+        let (x, y) = radial_dead_zone((1.0, 1.0), 0.2);
+        assert!((vec2(x, y).length() - 1.0).abs() < 1e-5);
+        assert!((x - y).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_parse_analog_settings() {
+        let bindings =
+            Bindings::parse("left_dead_zone = 0.35\ntwin_stick = true\nright_dead_zone = x\n");
+
+        assert_eq!(bindings.analog.left_dead_zone, 0.35);
+        assert!(bindings.analog.twin_stick);
+        assert_eq!(
+            bindings.analog.right_dead_zone,
+            AnalogSettings::default().right_dead_zone
+        );
+        assert_eq!(
+            Binding::parse("trigger:left"),
+            Some(Binding::Trigger(TriggerSide::Left))
+        );
+    }
+
+    #[test]
+    fn test_parse_control_scheme() {
+        let bindings = Bindings::parse("scheme = mouse\n");
+        assert_eq!(bindings.scheme, ControlScheme::Mouse);
+        assert_eq!(Bindings::parse(&bindings.to_config()), bindings);
+
+        let unknown = Bindings::parse("scheme = telepathy\n");
+        assert_eq!(unknown.scheme, ControlScheme::Standard);
+    }
+
+    #[test]
+    fn test_parse_keeps_defaults_for_missing_actions() {
+        let bindings = Bindings::parse("# commentaire\nthrust = key:W, key:Up\nunknown = key:A\n");
+
+        assert_eq!(
+            bindings.get(Action::Thrust),
+            &[Binding::Key(KeyCode::W), Binding::Key(KeyCode::Up)]
+        );
+        assert_eq!(
+            bindings.get(Action::Fire),
+            Bindings::default().get(Action::Fire)
+        );
+    }
+}
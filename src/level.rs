@@ -0,0 +1,648 @@
+//! Module des niveaux pour un jeu Asteroids.
+//!
+//! Un niveau décrit l'arène, la position de départ du vaisseau, les astéroïdes présents au
+//! lancement (taille, position et vitesse), les zones dangereuses, les points d'apparition,
+//! les déclencheurs des vagues suivantes, la condition de victoire et le niveau suivant.
+//! Les niveaux sont créés avec l'éditeur ou à la main, et enregistrés au format RON :
+//!
+//! ```text
+//! (
+//!     version: 1,
+//!     arena: (1600.0, 1200.0),
+//!     ship: (800.0, 600.0),
+//!     asteroids: [
+//!         (tier: Large, position: (200.0, 300.0), velocity: (1.0, 0.5)),
+//!     ],
+//!     hazards: [
+//!         (position: (1200.0, 400.0), radius: 90.0),
+//!     ],
+//!     spawn_points: [(100.0, 100.0)],
+//!     waves: [
+//!         (when: Cleared, tier: Medium, count: 4),
+//!         (when: After(30.0), tier: Large, count: 2),
+//!     ],
+//!     win: Survive(90.0),
+//!     next: Some("02.ron"),
+//! )
+//! ```
+//!
+//! La condition de victoire est `Clear` (dégager le champ une fois toutes les vagues
+//! lancées, par défaut), `Survive(secondes)` ou `Score(points)`. Le niveau suivant est un
+//! chemin relatif au fichier du niveau ; une campagne enchaîne ainsi les niveaux jusqu'à
+//! celui qui n'en indique aucun. Les niveaux de `levels/` sont intégrés au programme, et
+//! leur niveau suivant est cherché parmi eux. Les champs `version`, `hazards`, `spawn_points`, `waves`,
+//! `win` et `next` peuvent être omis.
+
+use crate::asteroid::Asteroid;
+use crate::locale::tr;
+use crate::render::Renderer;
+use macroquad::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Taille d'un astéroïde décrit dans un niveau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Tier {
+    /// Petit astéroïde.
+    Small,
+    /// Astéroïde moyen.
+    Medium,
+    /// Grand astéroïde.
+    Large,
+}
+
+impl Tier {
+    /// Tailles, de la plus petite à la plus grande.
+    pub const ALL: [Tier; 3] = [Tier::Small, Tier::Medium, Tier::Large];
+
+    /// Retourne la catégorie d'un astéroïde d'après sa taille.
+    pub fn from_size(size: f32) -> Self {
+        if size >= Asteroid::LARGE {
+            Tier::Large
+        } else if size >= Asteroid::MEDIUM {
+            Tier::Medium
+        } else {
+            Tier::Small
+        }
+    }
+
+    /// Retourne le nom de la catégorie (`small`, `medium` ou `large`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Tier::Small => "small",
+            Tier::Medium => "medium",
+            Tier::Large => "large",
+        }
+    }
+
+    /// Retourne la taille de l'astéroïde correspondant.
+    pub fn size(self) -> f32 {
+        match self {
+            Tier::Small => Asteroid::SMALL,
+            Tier::Medium => Asteroid::MEDIUM,
+            Tier::Large => Asteroid::LARGE,
+        }
+    }
+}
+
+/// Astéroïde présent au lancement d'un niveau.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AsteroidSpec {
+    /// Taille de l'astéroïde.
+    pub tier: Tier,
+    /// Position initiale.
+    pub position: (f32, f32),
+    /// Déplacement à chaque frame.
+    pub velocity: (f32, f32),
+}
+
+impl AsteroidSpec {
+    /// Crée l'astéroïde décrit.
+    pub fn spawn(&self) -> Asteroid {
+        let mut asteroid = Asteroid::with_size(self.tier.size(), self.position.into());
+        asteroid.set_speed(self.velocity.into());
+        asteroid
+    }
+}
+
+/// Zone circulaire qui use le bouclier du vaisseau tant qu'il s'y trouve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hazard {
+    /// Centre de la zone.
+    pub position: (f32, f32),
+    /// Rayon de la zone.
+    pub radius: f32,
+}
+
+impl Hazard {
+    /// Points de bouclier retirés à chaque pas de simulation passé dans la zone.
+    pub const DRAIN: f32 = 0.02;
+
+    /// Indique si un point se trouve dans la zone.
+    pub fn contains(&self, point: Vec2) -> bool {
+        Vec2::from(self.position).distance(point) <= self.radius
+    }
+
+    /// Dessine la zone dans le monde.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        let center = Vec2::from(self.position);
+        renderer.circle(center, self.radius, Color::new(0.8, 0.1, 0.3, 0.25));
+        renderer.circle_lines(center, self.radius, 2.0, Color::new(1.0, 0.2, 0.4, 0.8));
+    }
+}
+
+/// Condition qui déclenche une vague.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Trigger {
+    /// Le champ d'astéroïdes a été dégagé.
+    Cleared,
+    /// Le nombre de secondes de jeu indiqué s'est écoulé.
+    After(f32),
+}
+
+/// Vague d'astéroïdes lancée par un déclencheur, aux points d'apparition du niveau.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WaveTrigger {
+    /// Condition de déclenchement.
+    pub when: Trigger,
+    /// Taille des astéroïdes de la vague.
+    pub tier: Tier,
+    /// Nombre d'astéroïdes de la vague.
+    pub count: usize,
+}
+
+/// Condition de victoire d'un niveau.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Win {
+    /// Détruire tous les astéroïdes, une fois toutes les vagues lancées.
+    #[default]
+    Clear,
+    /// Survivre le nombre de secondes indiqué.
+    Survive(f32),
+    /// Atteindre le score indiqué.
+    Score(u32),
+}
+
+impl Win {
+    /// Indique si la condition est remplie.
+    ///
+    /// # Paramètres
+    /// - `cleared`: Indique si le champ est dégagé et toutes les vagues lancées.
+    /// - `elapsed`: Le temps de jeu écoulé, en secondes.
+    /// - `score`: Le score de la partie.
+    pub fn is_met(self, cleared: bool, elapsed: f32, score: u32) -> bool {
+        match self {
+            Win::Clear => cleared,
+            Win::Survive(seconds) => elapsed >= seconds,
+            Win::Score(points) => score >= points,
+        }
+    }
+
+    /// Décrit l'objectif restant, pour l'interface.
+    ///
+    /// # Paramètres
+    /// - `elapsed`: Le temps de jeu écoulé, en secondes.
+    pub fn describe(self, elapsed: f32) -> String {
+        match self {
+            Win::Clear => tr("level.clear").to_string(),
+            Win::Survive(seconds) => format!(
+                "{} {:.0}s",
+                tr("level.survive"),
+                (seconds - elapsed).max(0.0).ceil()
+            ),
+            Win::Score(points) => format!("{} {points}", tr("level.score")),
+        }
+    }
+}
+
+/// Niveau d'une campagne ou créé avec l'éditeur.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    /// Version du format du fichier.
+    #[serde(default = "Level::first_version")]
+    pub version: u32,
+    /// Dimensions de l'arène.
+    pub arena: (f32, f32),
+    /// Position de départ du vaisseau.
+    pub ship: (f32, f32),
+    /// Astéroïdes présents au lancement.
+    pub asteroids: Vec<AsteroidSpec>,
+    /// Zones dangereuses.
+    #[serde(default)]
+    pub hazards: Vec<Hazard>,
+    /// Points d'apparition des vagues ; les bords de l'arène sont utilisés s'il n'y en a aucun.
+    #[serde(default)]
+    pub spawn_points: Vec<(f32, f32)>,
+    /// Déclencheurs des vagues, dans l'ordre.
+    #[serde(default)]
+    pub waves: Vec<WaveTrigger>,
+    /// Condition de victoire.
+    #[serde(default)]
+    pub win: Win,
+    /// Chemin du niveau suivant, relatif au fichier de ce niveau.
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+impl Level {
+    /// Chemin du niveau ouvert par défaut dans l'éditeur.
+    pub const FILE: &'static str = "level.ron";
+    /// Version du format écrite par le jeu ; les versions plus récentes sont refusées.
+    pub const VERSION: u32 = 1;
+    /// Nombre maximal d'astéroïdes d'une vague.
+    pub const MAX_WAVE: usize = 50;
+
+    /// Crée un niveau vide, avec le vaisseau au centre de l'arène.
+    ///
+    /// # Paramètres
+    /// - `arena`: Les dimensions de l'arène.
+    pub fn new(arena: Vec2) -> Self {
+        Self {
+            version: Self::VERSION,
+            arena: arena.into(),
+            ship: (arena / 2.0).into(),
+            asteroids: Vec::new(),
+            hazards: Vec::new(),
+            spawn_points: Vec::new(),
+            waves: Vec::new(),
+            win: Win::Clear,
+            next: None,
+        }
+    }
+
+    /// Retourne les dimensions de l'arène.
+    pub fn get_arena(&self) -> Vec2 {
+        self.arena.into()
+    }
+
+    /// Retourne la position de départ du vaisseau.
+    pub fn get_ship(&self) -> Vec2 {
+        self.ship.into()
+    }
+
+    /// Crée les astéroïdes présents au lancement du niveau.
+    pub fn spawn_asteroids(&self) -> Vec<Asteroid> {
+        self.asteroids.iter().map(AsteroidSpec::spawn).collect()
+    }
+
+    /// Charge un niveau depuis un fichier.
+    ///
+    /// # Retour
+    /// Le niveau lu, ou un message décrivant l'erreur de lecture ou de syntaxe.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text =
+            fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+        Self::parse(&text).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    /// Enregistre le niveau dans un fichier.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_ron()).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    /// Lit un niveau depuis son texte au format RON.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let level: Self = ron::from_str(text).map_err(|error| error.to_string())?;
+        if level.version > Self::VERSION {
+            return Err(format!(
+                "level version {} is not supported (up to {})",
+                level.version,
+                Self::VERSION
+            ));
+        }
+        level.validate()?;
+        Ok(level)
+    }
+
+    /// Vérifie que les dimensions de l'arène, les rayons des zones et la taille des vagues
+    /// sont jouables.
+    ///
+    /// # Retour
+    /// Un message décrivant la première valeur refusée.
+    fn validate(&self) -> Result<(), String> {
+        let (width, height) = self.arena;
+        if !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0) {
+            return Err(format!("invalid arena: {width} x {height}"));
+        }
+        if let Some(hazard) = self
+            .hazards
+            .iter()
+            .find(|hazard| !(hazard.radius.is_finite() && hazard.radius >= 0.0))
+        {
+            return Err(format!("invalid hazard radius: {}", hazard.radius));
+        }
+        if let Some(wave) = self.waves.iter().find(|wave| wave.count > Self::MAX_WAVE) {
+            return Err(format!(
+                "wave of {} asteroids (up to {})",
+                wave.count,
+                Self::MAX_WAVE
+            ));
+        }
+        Ok(())
+    }
+
+    /// Retourne le chemin du niveau suivant.
+    ///
+    /// # Paramètres
+    /// - `path`: Le fichier de ce niveau.
+    ///
+    /// # Retour
+    /// Le chemin du niveau suivant, ou `None` pour le dernier niveau.
+    pub fn next_path(&self, path: &Path) -> Option<PathBuf> {
+        let next = self.next.as_ref()?;
+        Some(path.parent().unwrap_or(Path::new("")).join(next))
+    }
+
+    /// Version des fichiers écrits avant l'ajout du champ `version`.
+    fn first_version() -> u32 {
+        1
+    }
+
+    /// Écrit le niveau au format RON, indenté pour être lu et modifié à la main.
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .expect("un niveau est toujours sérialisable")
+    }
+}
+
+/// Campagne qui enchaîne des niveaux.
+pub struct Campaign {
+    /// Fichier du niveau en cours ; pour la campagne fournie avec le jeu, son nom dans
+    /// `SHIPPED`.
+    path: PathBuf,
+    /// Indique si les niveaux sont ceux intégrés au programme.
+    shipped: bool,
+}
+
+impl Campaign {
+    /// Premier niveau de la campagne fournie avec le jeu.
+    pub const START: &'static str = "01.ron";
+    /// Niveaux de la campagne fournie avec le jeu, intégrés au programme : (nom, texte).
+    pub const SHIPPED: &'static [(&'static str, &'static str)] = &[
+        ("01.ron", include_str!("../levels/01.ron")),
+        ("02.ron", include_str!("../levels/02.ron")),
+        ("03.ron", include_str!("../levels/03.ron")),
+    ];
+
+    /// Commence la campagne fournie avec le jeu, quel que soit le répertoire courant.
+    pub fn shipped() -> Self {
+        Self {
+            path: PathBuf::from(Self::START),
+            shipped: true,
+        }
+    }
+
+    /// Commence une campagne depuis des fichiers.
+    ///
+    /// # Paramètres
+    /// - `start`: Le fichier du premier niveau.
+    pub fn new(start: PathBuf) -> Self {
+        Self {
+            path: start,
+            shipped: false,
+        }
+    }
+
+    /// Charge le niveau en cours.
+    pub fn load(&self) -> Result<Level, String> {
+        if !self.shipped {
+            return Level::load(&self.path);
+        }
+        let (_, text) = Self::SHIPPED
+            .iter()
+            .find(|(name, _)| Path::new(name) == self.path)
+            .ok_or_else(|| format!("{}: no such shipped level", self.path.display()))?;
+        Level::parse(text).map_err(|error| format!("{}: {error}", self.path.display()))
+    }
+
+    /// Passe au niveau suivant, après la victoire.
+    ///
+    /// # Paramètres
+    /// - `level`: Le niveau qui vient d'être gagné.
+    ///
+    /// # Retour
+    /// `true` s'il reste un niveau, `false` si la campagne est terminée.
+    pub fn advance(&mut self, level: &Level) -> bool {
+        match level.next_path(&self.path) {
+            Some(next) => {
+                self.path = next;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Déroulement des vagues d'un niveau pendant une partie.
+pub struct Waves {
+    /// Déclencheurs qui n'ont pas encore lancé leur vague, dans l'ordre.
+    pending: Vec<WaveTrigger>,
+    /// Points d'apparition des vagues.
+    spawn_points: Vec<Vec2>,
+    /// Indice du prochain point d'apparition utilisé.
+    next_point: usize,
+    /// Temps de jeu écoulé, en secondes.
+    elapsed: f32,
+}
+
+impl Waves {
+    /// Prépare le déroulement des vagues d'un niveau.
+    pub fn new(level: &Level) -> Self {
+        Self {
+            pending: level.waves.clone(),
+            spawn_points: level
+                .spawn_points
+                .iter()
+                .map(|&point| point.into())
+                .collect(),
+            next_point: 0,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Indique si toutes les vagues ont été lancées.
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Retourne le temps de jeu écoulé, en secondes.
+    pub fn get_elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Fait avancer le temps et lance les vagues dont la condition est remplie.
+    ///
+    /// Les vagues minutées partent dès que leur temps est écoulé ; lorsque le champ est
+    /// dégagé, la première vague en attente de cette condition part. Les astéroïdes
+    /// apparaissent tour à tour à chaque point d'apparition.
+    ///
+    /// # Paramètres
+    /// - `delta`: La durée écoulée, en secondes.
+    /// - `cleared`: Indique si le champ d'astéroïdes est dégagé.
+    /// - `arena`: Les dimensions de l'arène.
+    ///
+    /// # Retour
+    /// Les astéroïdes des vagues lancées.
+    pub fn update(&mut self, delta: f32, cleared: bool, arena: Vec2) -> Vec<Asteroid> {
+        self.elapsed += delta;
+        let elapsed = self.elapsed;
+        let mut fired = Vec::new();
+        self.pending.retain(|wave| match wave.when {
+            Trigger::After(seconds) if elapsed >= seconds => {
+                fired.push(*wave);
+                false
+            }
+            _ => true,
+        });
+        if cleared && fired.is_empty() {
+            if let Some(index) = self
+                .pending
+                .iter()
+                .position(|wave| wave.when == Trigger::Cleared)
+            {
+                fired.push(self.pending.remove(index));
+            }
+        }
+
+        let mut asteroids = Vec::new();
+        for wave in fired {
+            for _ in 0..wave.count {
+                let position = self.next_spawn_point(arena);
+                asteroids.push(Asteroid::with_size(wave.tier.size(), position));
+            }
+        }
+        asteroids
+    }
+
+    /// Retourne le prochain point d'apparition, ou un point au hasard près d'un bord.
+    fn next_spawn_point(&mut self, arena: Vec2) -> Vec2 {
+        if self.spawn_points.is_empty() {
+            return Asteroid::new(arena).get_position();
+        }
+        let point = self.spawn_points[self.next_point % self.spawn_points.len()];
+        self.next_point += 1;
+        point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Level {
+        let mut level = Level::new(vec2(1600.0, 1200.0));
+        level.asteroids.push(AsteroidSpec {
+            tier: Tier::Large,
+            position: (200.0, 300.0),
+            velocity: (1.0, 0.5),
+        });
+        level.hazards.push(Hazard {
+            position: (1200.0, 400.0),
+            radius: 90.0,
+        });
+        level.spawn_points = vec![(100.0, 100.0), (1500.0, 100.0)];
+        level.waves = vec![
+            WaveTrigger {
+                when: Trigger::Cleared,
+                tier: Tier::Medium,
+                count: 3,
+            },
+            WaveTrigger {
+                when: Trigger::After(30.0),
+                tier: Tier::Large,
+                count: 2,
+            },
+        ];
+        level
+    }
+
+    #[test]
+    fn test_ron_round_trip() {
+        let level = sample();
+        let text = level.to_ron();
+
+        assert!(text.contains("tier: Large"));
+        assert_eq!(Level::parse(&text), Ok(level));
+        assert!(Level::parse("(arena: (1.0, 1.0))").is_err());
+    }
+
+    #[test]
+    fn test_version_and_defaults() {
+        let minimal = "(arena: (800.0, 600.0), ship: (400.0, 300.0), asteroids: [])";
+        let level = Level::parse(minimal).unwrap();
+        assert_eq!(level.version, 1);
+        assert_eq!(level.win, Win::Clear);
+        assert_eq!(level.next, None);
+
+        let future = format!("(version: {}, {}", Level::VERSION + 1, &minimal[1..]);
+        assert!(Level::parse(&future).unwrap_err().contains("not supported"));
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        let waves = "waves: [(when: Cleared, tier: Large, count: 1)]";
+        let negative =
+            format!("(arena: (-800.0, 600.0), ship: (0.0, 0.0), asteroids: [], {waves})");
+        assert!(Level::parse(&negative).unwrap_err().contains("arena"));
+        let infinite = "(arena: (inf, 600.0), ship: (0.0, 0.0), asteroids: [])";
+        assert!(Level::parse(infinite).unwrap_err().contains("arena"));
+
+        let mut level = sample();
+        level.hazards[0].radius = -1.0;
+        assert!(Level::parse(&level.to_ron())
+            .unwrap_err()
+            .contains("radius"));
+
+        let mut level = sample();
+        level.waves[0].count = Level::MAX_WAVE + 1;
+        assert!(Level::parse(&level.to_ron()).unwrap_err().contains("wave"));
+        level.waves[0].count = Level::MAX_WAVE;
+        assert!(Level::parse(&level.to_ron()).is_ok());
+    }
+
+    #[test]
+    fn test_win_conditions() {
+        assert!(Win::Clear.is_met(true, 0.0, 0));
+        assert!(!Win::Clear.is_met(false, 100.0, 1000));
+        assert!(Win::Survive(60.0).is_met(false, 60.0, 0));
+        assert!(!Win::Survive(60.0).is_met(true, 59.0, 0));
+        assert!(Win::Score(500).is_met(false, 0.0, 520));
+    }
+
+    #[test]
+    fn test_shipped_campaign() {
+        let files = Path::new(env!("CARGO_MANIFEST_DIR")).join("levels");
+        for mut campaign in [
+            Campaign::shipped(),
+            Campaign::new(files.join(Campaign::START)),
+        ] {
+            let mut levels = 0;
+            loop {
+                let level = campaign.load().unwrap();
+                assert_eq!(level.version, Level::VERSION);
+                levels += 1;
+                if !campaign.advance(&level) {
+                    break;
+                }
+                assert!(levels < 20, "la campagne tourne en boucle");
+            }
+            assert_eq!(levels, Campaign::SHIPPED.len());
+        }
+    }
+
+    #[test]
+    fn test_spawn_asteroids_keeps_velocity() {
+        let asteroids = sample().spawn_asteroids();
+
+        assert_eq!(asteroids.len(), 1);
+        assert_eq!(asteroids[0].get_size(), Asteroid::LARGE);
+        assert_eq!(asteroids[0].get_position(), vec2(200.0, 300.0));
+        assert_eq!(asteroids[0].get_speed(), vec2(1.0, 0.5));
+    }
+
+    #[test]
+    fn test_waves_fire_in_order() {
+        let level = sample();
+        let arena = level.get_arena();
+        let mut waves = Waves::new(&level);
+
+        assert!(waves.update(1.0, false, arena).is_empty());
+
+        let cleared = waves.update(1.0, true, arena);
+        assert_eq!(cleared.len(), 3);
+        assert_eq!(cleared[0].get_position(), vec2(100.0, 100.0));
+        assert_eq!(cleared[1].get_position(), vec2(1500.0, 100.0));
+        assert_eq!(cleared[2].get_position(), vec2(100.0, 100.0));
+        assert!(!waves.is_done());
+
+        let timed = waves.update(28.0, false, arena);
+        assert_eq!(timed.len(), 2);
+        assert_eq!(timed[0].get_size(), Asteroid::LARGE);
+        assert!(waves.is_done());
+    }
+}
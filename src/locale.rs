@@ -0,0 +1,466 @@
+//! Module de localisation pour un jeu Asteroids.
+//!
+//! Les textes affichés à l'écran sont désignés par une clé (`"menu.easy"`, `"game.over"`…)
+//! et traduits avec `tr` dans la langue choisie. Chaque langue fournit une table de
+//! traductions ; une clé absente d'une table est affichée en anglais.
+//!
+//! La langue se change depuis l'écran des contrôles et est enregistrée dans
+//! `language.cfg` :
+//!
+//! ```text
+//! language = fr
+//! ```
+//!
+//! La console de développement n'est pas traduite.
+
+use std::cell::Cell;
+use std::fs;
+use std::io;
+
+/// Table de traductions : couples (clé, texte).
+type Table = &'static [(&'static str, &'static str)];
+
+thread_local! {
+    /// Langue actuelle de l'interface.
+    static LANGUAGE: Cell<Language> = const { Cell::new(Language::English) };
+}
+
+/// Langue de l'interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Anglais, langue par défaut.
+    English,
+    /// Français.
+    French,
+}
+
+impl Language {
+    /// Liste de toutes les langues disponibles.
+    pub const ALL: [Language; 2] = [Language::English, Language::French];
+    /// Chemin du fichier de configuration de la langue.
+    pub const FILE: &'static str = "language.cfg";
+
+    /// Retourne le code de la langue utilisé dans le fichier de configuration.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+        }
+    }
+
+    /// Retourne le nom de la langue, écrit dans cette langue.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+        }
+    }
+
+    /// Retrouve une langue à partir de son code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// Retourne la langue suivante, pour parcourir les langues disponibles.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&language| language == self);
+        Self::ALL[(index.unwrap_or(0) + 1) % Self::ALL.len()]
+    }
+
+    /// Charge la langue depuis le fichier de configuration.
+    ///
+    /// # Retour
+    /// La langue lue, ou l'anglais si le fichier n'existe pas ou est invalide.
+    pub fn load() -> Self {
+        fs::read_to_string(Self::FILE)
+            .ok()
+            .and_then(|text| Self::parse(&text))
+            .unwrap_or(Language::English)
+    }
+
+    /// Enregistre la langue dans le fichier de configuration.
+    pub fn save(self) -> io::Result<()> {
+        fs::write(Self::FILE, format!("language = {}\n", self.code()))
+    }
+
+    /// Lit la langue depuis le contenu d'un fichier de configuration.
+    ///
+    /// Les lignes vides et les commentaires (`#`) sont ignorés.
+    pub fn parse(text: &str) -> Option<Self> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .find(|(name, _)| name.trim() == "language")
+            .and_then(|(_, value)| Self::from_code(value.trim()))
+    }
+
+    /// Retourne la table de traductions de la langue.
+    fn table(self) -> Table {
+        match self {
+            Language::English => ENGLISH,
+            Language::French => FRENCH,
+        }
+    }
+}
+
+/// Change la langue de l'interface.
+pub fn set_language(language: Language) {
+    LANGUAGE.with(|cell| cell.set(language));
+}
+
+/// Retourne la langue actuelle de l'interface.
+pub fn get_language() -> Language {
+    LANGUAGE.with(Cell::get)
+}
+
+/// Traduit un texte dans la langue actuelle.
+///
+/// # Paramètres
+/// - `key`: La clé du texte.
+///
+/// # Retour
+/// Le texte traduit, le texte anglais si la traduction manque, ou la clé elle-même si
+/// le texte n'existe dans aucune langue.
+pub fn tr(key: &str) -> &str {
+    resolve(get_language().table(), key)
+}
+
+/// Cherche une clé dans une table, puis dans la table anglaise.
+fn resolve(table: Table, key: &str) -> &str {
+    let find = |table: Table| {
+        table
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|&(_, text)| text)
+    };
+    find(table).or_else(|| find(ENGLISH)).unwrap_or(key)
+}
+
+/// Textes anglais, utilisés aussi pour les traductions manquantes.
+const ENGLISH: Table = &[
+    ("menu.title", "Select Difficulty"),
+    ("menu.easy", "Easy"),
+    ("menu.medium", "Medium"),
+    ("menu.hard", "Hard"),
+    ("menu.custom", "Custom"),
+    ("menu.campaign", "Campaign"),
+    ("menu.modes", "Game Modes"),
+    ("menu.challenge", "Ghost Challenge"),
+    ("menu.editor", "Level Editor"),
+    ("menu.achievements", "Achievements"),
+    ("menu.controls", "Controls"),
+    ("menu.keys_pad", "Keys/Pad"),
+    ("menu.mouse_aim", "Mouse aim"),
+    ("menu.scheme", "Aiming"),
+    ("menu.volume", "Volume"),
+    ("menu.show_fps", "Show FPS"),
+    ("custom.title", "Custom Difficulty"),
+    ("custom.asteroids", "Asteroids per wave"),
+    ("custom.speed", "Asteroid speed"),
+    ("custom.health", "Shield"),
+    ("custom.damage", "Damage per hit"),
+    ("custom.fire_cooldown", "Fire cooldown"),
+    ("custom.spawn_rate", "Reinforcements/min"),
+    ("custom.score_multiplier", "Score multiplier"),
+    ("custom.play", "Play"),
+    ("custom.back", "Back"),
+    ("modes.title", "Game Modes"),
+    ("modes.back", "Back"),
+    ("modes.error", "Script error, see the terminal"),
+    ("achievements.title", "Achievements"),
+    ("achievements.unlocked", "Unlocked"),
+    ("achievements.locked", "Locked"),
+    ("achievements.toast", "Achievement unlocked"),
+    ("achievements.back", "Back"),
+    ("achievements.first_win.name", "Field Cleared"),
+    ("achievements.first_win.description", "Win a game"),
+    ("achievements.pebble_crusher.name", "Pebble Crusher"),
+    (
+        "achievements.pebble_crusher.description",
+        "Destroy 100 small asteroids",
+    ),
+    ("achievements.boulder_breaker.name", "Boulder Breaker"),
+    (
+        "achievements.boulder_breaker.description",
+        "Destroy 50 large asteroids",
+    ),
+    ("achievements.survivor.name", "Survivor"),
+    ("achievements.survivor.description", "Survive for 5 minutes"),
+    ("achievements.untouchable.name", "Untouchable"),
+    (
+        "achievements.untouchable.description",
+        "Clear Hard without taking damage",
+    ),
+    ("achievements.sharpshooter.name", "Sharpshooter"),
+    (
+        "achievements.sharpshooter.description",
+        "Win using only 10 missiles",
+    ),
+    ("stats.title", "Game Summary"),
+    ("stats.duration", "Duration"),
+    ("stats.shots", "Shots fired"),
+    ("stats.hits", "Hits"),
+    ("stats.accuracy", "Accuracy"),
+    ("stats.destroyed", "Destroyed (S / M / L)"),
+    ("stats.collisions", "Collisions"),
+    ("stats.damage", "Damage taken"),
+    ("stats.distance", "Distance travelled"),
+    ("stats.thrust_time", "Time thrusting"),
+    ("stats.continue", "Continue"),
+    ("ghost.split", "Split"),
+    ("ghost.best", "New best time:"),
+    ("tui.shield", "Shield"),
+    ("tui.help", "Arrows: steer and thrust  Space: fire  Q: quit"),
+    ("tui.again", "R: play again  Q: quit"),
+    ("editor.large", "Large asteroid"),
+    ("editor.medium", "Medium asteroid"),
+    ("editor.small", "Small asteroid"),
+    ("editor.hazard", "Hazard"),
+    ("editor.spawn_point", "Spawn point"),
+    ("editor.ship", "Ship start"),
+    ("editor.waves", "Waves"),
+    ("editor.cleared", "cleared"),
+    ("editor.after", "after"),
+    ("editor.opened", "Opened"),
+    ("editor.new_level", "New level"),
+    ("editor.saved", "Saved"),
+    ("editor.error", "Error:"),
+    ("editor.wave_added", "Wave added:"),
+    ("editor.wave_removed", "Wave removed"),
+    ("editor.help_drag", "Drag: velocity / radius"),
+    ("editor.help_delete", "Right click: delete"),
+    ("editor.help_waves", "C/T: add wave  +/-  [/]"),
+    ("editor.help_keys", "S: save  P: play  Esc: menu"),
+    ("level.clear", "Clear the field"),
+    ("level.survive", "Survive"),
+    ("level.score", "Reach score"),
+    ("game.over", "Game Over"),
+    ("game.win", "You Win!"),
+    ("game.score", "Score"),
+    ("hud.wave", "Wave"),
+    ("hud.asteroids", "Asteroids"),
+    ("hud.muted", "Muted"),
+    ("hud.missile", "Missile"),
+    ("pause.title", "Paused"),
+    ("pause.resume", "Resume"),
+    ("pause.restart", "Restart"),
+    ("pause.settings", "Settings"),
+    ("pause.quit", "Quit to Menu"),
+    ("controls.twin_stick", "Twin-stick aiming (right stick)"),
+    ("controls.language", "Language"),
+    ("controls.back", "Back"),
+    (
+        "controls.capture",
+        "Press a key, a button, a trigger or move the stick (Esc: cancel)",
+    ),
+    (
+        "controls.help",
+        "Enter/A: bind  Backspace/X: clear  T: twin-stick  L: language  Esc/B: save and return",
+    ),
+    ("action.rotate_left", "Rotate left"),
+    ("action.rotate_right", "Rotate right"),
+    ("action.thrust", "Thrust"),
+    ("action.fire", "Fire"),
+    ("action.pause", "Pause"),
+];
+
+/// Textes français.
+const FRENCH: Table = &[
+    ("menu.title", "Choisir la difficulté"),
+    ("menu.easy", "Facile"),
+    ("menu.medium", "Moyen"),
+    ("menu.hard", "Difficile"),
+    ("menu.custom", "Personnalisée"),
+    ("menu.campaign", "Campagne"),
+    ("menu.modes", "Modes de jeu"),
+    ("menu.challenge", "Défi fantôme"),
+    ("menu.editor", "Éditeur de niveaux"),
+    ("menu.achievements", "Succès"),
+    ("menu.controls", "Contrôles"),
+    ("menu.keys_pad", "Clavier"),
+    ("menu.mouse_aim", "Souris"),
+    ("menu.scheme", "Visée"),
+    ("menu.volume", "Volume"),
+    ("menu.show_fps", "Afficher les FPS"),
+    ("custom.title", "Difficulté personnalisée"),
+    ("custom.asteroids", "Astéroïdes par vague"),
+    ("custom.speed", "Vitesse des astéroïdes"),
+    ("custom.health", "Bouclier"),
+    ("custom.damage", "Dégâts par impact"),
+    ("custom.fire_cooldown", "Recharge des tirs"),
+    ("custom.spawn_rate", "Renforts par minute"),
+    ("custom.score_multiplier", "Multiplicateur de score"),
+    ("custom.play", "Jouer"),
+    ("custom.back", "Retour"),
+    ("modes.title", "Modes de jeu"),
+    ("modes.back", "Retour"),
+    ("modes.error", "Erreur du script, voir le terminal"),
+    ("achievements.title", "Succès"),
+    ("achievements.unlocked", "Débloqué"),
+    ("achievements.locked", "Verrouillé"),
+    ("achievements.toast", "Succès débloqué"),
+    ("achievements.back", "Retour"),
+    ("achievements.first_win.name", "Champ dégagé"),
+    ("achievements.first_win.description", "Gagner une partie"),
+    ("achievements.pebble_crusher.name", "Broyeur de cailloux"),
+    (
+        "achievements.pebble_crusher.description",
+        "Détruire 100 petits astéroïdes",
+    ),
+    ("achievements.boulder_breaker.name", "Briseur de rochers"),
+    (
+        "achievements.boulder_breaker.description",
+        "Détruire 50 grands astéroïdes",
+    ),
+    ("achievements.survivor.name", "Survivant"),
+    ("achievements.survivor.description", "Survivre 5 minutes"),
+    ("achievements.untouchable.name", "Intouchable"),
+    (
+        "achievements.untouchable.description",
+        "Gagner en Difficile sans subir de dégâts",
+    ),
+    ("achievements.sharpshooter.name", "Tireur d'élite"),
+    (
+        "achievements.sharpshooter.description",
+        "Gagner en tirant au plus 10 missiles",
+    ),
+    ("stats.title", "Résumé de la partie"),
+    ("stats.duration", "Durée"),
+    ("stats.shots", "Tirs"),
+    ("stats.hits", "Touchés"),
+    ("stats.accuracy", "Précision"),
+    ("stats.destroyed", "Détruits (P / M / G)"),
+    ("stats.collisions", "Collisions"),
+    ("stats.damage", "Dégâts subis"),
+    ("stats.distance", "Distance parcourue"),
+    ("stats.thrust_time", "Temps d'accélération"),
+    ("stats.continue", "Continuer"),
+    ("ghost.split", "Passage"),
+    ("ghost.best", "Nouveau meilleur temps :"),
+    ("tui.shield", "Bouclier"),
+    ("tui.help", "Flèches : piloter  Espace : tirer  Q : quitter"),
+    ("tui.again", "R : rejouer  Q : quitter"),
+    ("editor.large", "Grand astéroïde"),
+    ("editor.medium", "Astéroïde moyen"),
+    ("editor.small", "Petit astéroïde"),
+    ("editor.hazard", "Zone dangereuse"),
+    ("editor.spawn_point", "Point d'apparition"),
+    ("editor.ship", "Départ du vaisseau"),
+    ("editor.waves", "Vagues"),
+    ("editor.cleared", "champ dégagé"),
+    ("editor.after", "après"),
+    ("editor.opened", "Ouvert :"),
+    ("editor.new_level", "Nouveau niveau :"),
+    ("editor.saved", "Enregistré :"),
+    ("editor.error", "Erreur :"),
+    ("editor.wave_added", "Vague ajoutée :"),
+    ("editor.wave_removed", "Vague supprimée"),
+    ("editor.help_drag", "Glisser : vitesse / rayon"),
+    ("editor.help_delete", "Clic droit : supprimer"),
+    ("editor.help_waves", "C/T : ajouter une vague  +/-  [/]"),
+    (
+        "editor.help_keys",
+        "S : enregistrer  P : jouer  Échap : menu",
+    ),
+    ("level.clear", "Dégagez le champ"),
+    ("level.survive", "Survivez"),
+    ("level.score", "Atteignez le score"),
+    ("game.over", "Perdu !"),
+    ("game.win", "Gagné !"),
+    ("game.score", "Score"),
+    ("hud.wave", "Vague"),
+    ("hud.asteroids", "Astéroïdes"),
+    ("hud.muted", "Son coupé"),
+    ("hud.missile", "Missile"),
+    ("pause.title", "Pause"),
+    ("pause.resume", "Reprendre"),
+    ("pause.restart", "Recommencer"),
+    ("pause.settings", "Paramètres"),
+    ("pause.quit", "Menu principal"),
+    ("controls.twin_stick", "Visée twin-stick (joystick droit)"),
+    ("controls.language", "Langue"),
+    ("controls.back", "Retour"),
+    (
+        "controls.capture",
+        "Appuyez sur une touche, un bouton, une gâchette ou bougez le joystick (Échap : annuler)",
+    ),
+    (
+        "controls.help",
+        "Entrée/A : associer  Retour/X : effacer  T : twin-stick  L : langue  Échap/B : enregistrer",
+    ),
+    ("action.rotate_left", "Tourner à gauche"),
+    ("action.rotate_right", "Tourner à droite"),
+    ("action.thrust", "Accélérer"),
+    ("action.fire", "Tirer"),
+    ("action.pause", "Pause"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::Achievements;
+
+    #[test]
+    fn test_shipped_locales_are_complete() {
+        for language in Language::ALL {
+            let table = language.table();
+            for (key, _) in ENGLISH {
+                assert!(
+                    table.iter().any(|(name, _)| name == key),
+                    "clé « {key} » absente de la langue {}",
+                    language.code()
+                );
+            }
+            for (key, _) in table {
+                assert!(
+                    ENGLISH.iter().any(|(name, _)| name == key),
+                    "clé « {key} » de la langue {} inconnue en anglais",
+                    language.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_achievements_are_translated() {
+        let definitions = Achievements::parse_definitions(Achievements::DEFINITIONS).unwrap();
+        for language in Language::ALL {
+            for achievement in &definitions {
+                for key in [&achievement.name, &achievement.description] {
+                    assert!(
+                        language.table().iter().any(|(name, _)| name == key),
+                        "clé « {key} » absente de la langue {}",
+                        language.code()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_key_falls_back_to_english() {
+        let partial: Table = &[("menu.easy", "Facile")];
+
+        assert_eq!(resolve(partial, "menu.easy"), "Facile");
+        assert_eq!(resolve(partial, "menu.hard"), "Hard");
+        assert_eq!(resolve(partial, "no.such.key"), "no.such.key");
+
+        set_language(Language::French);
+        assert_eq!(tr("pause.resume"), "Reprendre");
+        set_language(Language::English);
+        assert_eq!(tr("pause.resume"), "Resume");
+    }
+
+    #[test]
+    fn test_parse_language() {
+        assert_eq!(
+            Language::parse("# langue\nlanguage = fr\n"),
+            Some(Language::French)
+        );
+        assert_eq!(Language::parse("language = xx"), None);
+        assert_eq!(Language::French.next(), Language::English);
+    }
+}
@@ -7,171 +7,376 @@
 //! - **Flèche droite** : Tourner à droite
 //! - **Flèche haut** : Accélérer
 //! - **Espace** : Tirer un missile
-//! - **Échap** : Quitter le jeu
+//! - **Échap** : Mettre le jeu en pause (quitter le jeu depuis le menu principal)
+//! - **M** : Couper ou rétablir le son
+//! - **`** (accent grave) : Ouvrir ou fermer la console de développement
+//! - **F3** : Afficher ou masquer le nombre d'images par seconde
+//! - **F12** : Enregistrer une image SVG du monde
+//! - **F2** : Afficher ou masquer l'affichage de débogage des collisions (fonctionnalité
+//!   `debug-overlay` uniquement)
 //!
 //! ## Contrôles controller
 //! - **Joystick gauche vers la gauche** : Tourner à gauche
 //! - **Joystick gauche vers la droite** : Tourner à droite
-//! - **Joystick gauche vers le haut** : Accélérer
+//! - **Joystick gauche vers le haut** ou **gâchette gauche** : Accélérer (poussée proportionnelle)
 //! - **R1** : Tirer un missile
-//! - **B** : Quitter le jeu
+//! - **B** ou **Start** : Mettre le jeu en pause
+//!
+//! ## Contrôles souris
+//! Le schéma « Mouse aim », choisi dans le menu, oriente le vaisseau vers le curseur.
+//! - **Bouton gauche** : Tirer un missile
+//! - **Bouton droit** : Accélérer
+//!
+//! Les contrôles peuvent être modifiés depuis l'écran « Controls » du menu ; ils sont
+//! enregistrés dans `controls.cfg`.
+//!
+//! ## Monde et caméra
+//! Le monde est plus grand que la fenêtre (option `--world <largeur>x<hauteur>`) : la
+//! caméra suit le vaisseau, tremble lors des impacts, et une minicarte montre le monde entier.
+//!
+//! ## Résolution
+//! Le jeu est dessiné dans une résolution logique fixe, mise à l'échelle avec des bandes
+//! noires : redimensionner la fenêtre ne modifie ni la zone de jeu ni la disposition des menus.
+//!
+//! ## Interface
+//! L'interface affiche le score, le vaisseau restant, le bouclier, la vague, les astéroïdes
+//! restants, la recharge des missiles et le temps écoulé.
+//!
+//! ## Difficulté
+//! Les profils Easy, Medium et Hard, ou le profil réglé depuis l'écran « Custom », fixent
+//! le nombre et la vitesse des astéroïdes, le bouclier, les dégâts des collisions, la
+//! recharge des missiles, les renforts et le multiplicateur de score.
+//!
+//! ## Éditeur de niveaux
+//! L'éditeur, ouvert depuis le menu principal, place des astéroïdes, des zones
+//! dangereuses, des points d'apparition et des vagues, enregistre le niveau au format RON
+//! (`level.ron`, ou le fichier de l'option `--level`) et permet de l'essayer aussitôt.
+//!
+//! ## Campagne
+//! La campagne enchaîne les niveaux du répertoire `levels/`, intégrés au programme (ou
+//! ceux de l'option `--campaign <fichier>`), chacun avec sa condition de victoire : dégager le champ,
+//! survivre un temps donné ou atteindre un score.
+//!
+//! ## Modes de jeu
+//! Les modes du répertoire `modes/`, intégrés au programme et complétés par ceux de
+//! l'option `--modes <répertoire>`, sont des scripts Rhai qui réagissent aux événements
+//! de la partie (astéroïde détruit, champ dégagé, minuterie écoulée) pour faire apparaître
+//! des astéroïdes, modifier la difficulté, afficher des messages ou donner la victoire.
+//!
+//! ## Succès
+//! Des succès, décrits dans `achievements.ron`, sont débloqués par les événements des
+//! parties ; une notification les annonce et l'écran « Achievements » du menu montre
+//! leur avancement, enregistré dans `achievements.cfg`.
+//!
+//! ## Statistiques
+//! Chaque partie compte les tirs, la précision, les astéroïdes touchés par taille, les
+//! dégâts subis, la distance parcourue et le temps passé à accélérer. Un résumé s'affiche
+//! en fin de partie et une ligne est ajoutée à `stats.csv`, exportable en CSV ou en JSON
+//! avec `--export-stats <fichier>`.
+//!
+//! ## Défi fantôme
+//! Le défi génère un champ d'astéroïdes à partir d'une graine (option `--seed <nombre>`).
+//! La partie gagnée la plus rapide est enregistrée dans `replays/` et rejouée par un
+//! vaisseau fantôme translucide lors des parties suivantes ; à chaque astéroïde touché,
+//! l'écart avec la meilleure partie indique si le joueur est en avance ou en retard.
+//!
+//! ## Rendu
+//! Les objets du monde se dessinent à travers un moteur de rendu : la fenêtre Macroquad,
+//! un terminal (option `--tui`, partie jouable dessinée en caractères colorés) ou une
+//! image SVG. **F12** enregistre une image SVG du monde dans `snapshots/`.
+//!
+//! ## Pause
+//! Le menu de pause propose de reprendre, recommencer la partie, modifier les contrôles
+//! ou revenir au menu principal. Il s'ouvre aussi lorsque la fenêtre perd le focus.
+//!
+//! ## Mode démonstration
+//! Pendant la sélection de la difficulté, une partie jouée par le pilote automatique
+//! s'affiche en arrière-plan.
 
+use ::rand::{rngs::SmallRng, SeedableRng};
+use achievements::{Achievements, Run, Toasts};
+use assets::Assets;
 use asteroid::Asteroid;
-use gamepads::Gamepads;
+use audio::{AudioManager, AudioSettings};
+use camera::GameCamera;
+use console::Console;
+use control::ShipControl;
+use demo::AttractDemo;
+use difficulty::Difficulty;
+use editor::Editor;
+use events::GameEvent;
+use gamepads::{Button, Gamepads};
+use ghost::{Challenge, Ghost, Replay};
+use hud::{Hud, HudStatus};
+use input::{Action, Bindings, ControlScheme, InputState};
+use level::{Campaign, Hazard, Level, Waves, Win};
+use locale::{tr, Language};
 use macroquad::prelude::*;
 use missile::Missile;
+use particles::ParticleSystem;
+use pause::{FocusWatcher, PauseChoice, PauseMenu};
+use render::{Macroquad, Renderer, Svg};
+use script::{Command, ModeScript, Script, Snapshot};
 use spaceship::Spaceship;
+use starfield::Starfield;
+use stats::{Entry, History};
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
-use stellarobject::StellarObject; //sudo apt-get install libudev-dev
+use stellarobject::StellarObject;
+use ui::{Layout, Menu, MenuInput}; //sudo apt-get install libudev-dev
 
+mod achievements;
+mod assets;
 mod asteroid;
+mod audio;
+mod camera;
+mod console;
+mod control;
+#[cfg(feature = "debug-overlay")]
+mod debug;
+mod demo;
+mod difficulty;
+mod editor;
+mod events;
+mod ghost;
+mod gym;
+mod hud;
+mod input;
+mod level;
+mod locale;
 mod missile;
+mod particles;
+mod pause;
+mod pilot;
+mod render;
+mod script;
 mod spaceship;
+mod starfield;
+mod stats;
 mod stellarobject;
+mod tui;
+mod ui;
+mod view;
 
 /// Dessine l'état actuel du jeu.
 ///
 /// Cela inclut le fond d'écran, les astéroïdes, le vaisseau spatial et les missiles.
 ///
 /// # Paramètres
+/// - `renderer`: Le moteur de rendu des objets.
 /// - `asteroids`: Liste des astéroïdes à dessiner.
 /// - `spaceship`: Instance du vaisseau spatial.
 /// - `missiles`: Liste des missiles actifs.
-/// - `texture`: Texture de fond à utiliser.
-fn draw(asteroids: &[Asteroid], spaceship: &Spaceship, missiles: &[Missile], texture: &Texture2D) {
-    draw_background(texture);
-    draw_asteroids(asteroids);
-    spaceship.draw();
+/// - `starfield`: Fond étoilé à dessiner derrière les objets.
+fn draw(
+    renderer: &mut dyn Renderer,
+    asteroids: &[Asteroid],
+    spaceship: &Spaceship,
+    missiles: &[Missile],
+    starfield: &Starfield,
+) {
+    starfield.draw(renderer);
+    draw_objects(renderer, asteroids, spaceship, missiles);
+}
+
+/// Dessine les astéroïdes, le vaisseau spatial et les missiles.
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+/// - `asteroids`: Liste des astéroïdes à dessiner.
+/// - `spaceship`: Instance du vaisseau spatial.
+/// - `missiles`: Liste des missiles actifs.
+fn draw_objects(
+    renderer: &mut dyn Renderer,
+    asteroids: &[Asteroid],
+    spaceship: &Spaceship,
+    missiles: &[Missile],
+) {
+    draw_asteroids(renderer, asteroids);
+    spaceship.draw(renderer);
     for missile in missiles {
-        missile.draw();
+        missile.draw(renderer);
     }
 }
 
-/// Dessine le fond d'écran.
+/// Dessine le monde d'une partie : bords, zones dangereuses, fantôme et objets.
 ///
 /// # Paramètres
-/// - `background_texture`: La texture du fond.
-fn draw_background(background_texture: &Texture2D) {
-    draw_texture(background_texture, 0.0, 0.0, WHITE);
+/// - `renderer`: Le moteur de rendu.
+/// - `state`: L'état de la partie.
+/// - `ghost`: Le fantôme du défi, s'il y en a un.
+/// - `world`: Les dimensions du monde.
+fn draw_world(renderer: &mut dyn Renderer, state: &GameState, ghost: Option<&Ghost>, world: Vec2) {
+    renderer.rectangle_lines(Vec2::ZERO, world, 2.0, DARKGRAY);
+    for hazard in &state.hazards {
+        hazard.draw(renderer);
+    }
+    if let Some(ghost) = ghost {
+        ghost.draw(renderer);
+    }
+    draw_objects(
+        renderer,
+        &state.asteroids,
+        &state.spaceship,
+        &state.missiles,
+    );
 }
 
 /// Affiche l'écran de fin de partie avec le texte "Game Over".
-fn draw_game_over() {
-    let screen_width = screen_width();
-    let screen_height = screen_height();
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+fn draw_game_over(renderer: &mut dyn Renderer) {
+    let screen_width = view::WIDTH;
+    let screen_height = view::HEIGHT;
     let font_size = screen_height * 0.1;
-    draw_text(
-        "Game Over",
-        screen_width * 0.4,
-        screen_height * 0.5,
+    renderer.text(
+        tr("game.over"),
+        vec2(screen_width * 0.4, screen_height * 0.5),
         font_size,
         RED,
     );
 }
 
 /// Affiche l'écran de victoire avec le texte "You Win!".
-fn draw_you_win() {
-    let screen_width = screen_width();
-    let screen_height = screen_height();
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+fn draw_you_win(renderer: &mut dyn Renderer) {
+    let screen_width = view::WIDTH;
+    let screen_height = view::HEIGHT;
     let font_size = screen_height * 0.1;
-    draw_text(
-        "You Win!",
-        screen_width * 0.4,
-        screen_height * 0.5,
+    renderer.text(
+        tr("game.win"),
+        vec2(screen_width * 0.4, screen_height * 0.5),
         font_size,
         GREEN,
     );
 }
 
-/// Affiche l'écran un affichage visuel bouclier.
-fn draw_health_bar(health: f32) {
-    let bar_width = 100.0;
-    let bar_height = 20.0;
-    let bar_x = 20.0;
-    let bar_y = screen_height() - bar_height - 20.0;
-
-    draw_rectangle(
-        bar_x,
-        bar_y,
-        bar_width,
-        bar_height,
-        Color::from_rgba(50, 50, 50, 200),
+/// Affiche le score final sous l'écran de fin de partie ou de victoire.
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+/// - `score`: Le score obtenu pendant la partie.
+fn draw_final_score(renderer: &mut dyn Renderer, score: u32) {
+    renderer.text(
+        &format!("{} {score}", tr("game.score")),
+        vec2(view::WIDTH * 0.45, view::HEIGHT * 0.6),
+        view::HEIGHT * 0.05,
+        WHITE,
     );
+}
 
-    let current_health_width = bar_width * health;
-    draw_rectangle(bar_x, bar_y, current_health_width, bar_height, GREEN);
+/// Dessine un viseur à la position du curseur de la souris.
+///
+/// # Paramètres
+/// - `renderer`: Le moteur de rendu.
+fn draw_crosshair(renderer: &mut dyn Renderer) {
+    let (mx, my) = view::mouse_position();
+    let cursor = vec2(mx, my);
+    renderer.circle_lines(cursor, 8.0, 1.5, WHITE);
+    for direction in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+        renderer.line(
+            cursor + direction * 4.0,
+            cursor + direction * 12.0,
+            1.5,
+            WHITE,
+        );
+    }
 }
 
 /// Dessine tous les astéroïdes de la liste.
 ///
 /// # Paramètres
+/// - `renderer`: Le moteur de rendu.
 /// - `asteroids`: Liste des astéroïdes à dessiner.
-fn draw_asteroids(asteroids: &[Asteroid]) {
+fn draw_asteroids(renderer: &mut dyn Renderer, asteroids: &[Asteroid]) {
     for asteroid in asteroids {
-        draw_circle(
-            asteroid.get_position().x,
-            asteroid.get_position().y,
-            asteroid.get_size() / 2.0,
-            YELLOW,
-        );
+        renderer.circle(asteroid.get_position(), asteroid.get_size() / 2.0, YELLOW);
     }
 }
 
 /// Gère les entrées utilisateur pour contrôler le vaisseau et tirer des missiles.
 ///
+/// Les entrées du clavier et de toutes les manettes sont fusionnées selon les
+/// associations configurées.
+///
 /// # Paramètres
 /// - `spaceship`: Référence mutable au vaisseau spatial.
 /// - `missiles`: Liste des missiles actifs.
 /// - `gamepads`: Référence mutable aux manettes pour gérer les entrées.
+/// - `bindings`: Associations des actions aux entrées.
+/// - `input`: Les entrées de la frame, lues après avoir interrogé les manettes.
+/// - `events`: Liste des événements de la frame.
+/// - `cursor`: Position du curseur de la souris dans le monde.
 ///
 /// # Retour
-/// `true` si l'utilisateur souhaite quitter le jeu, sinon `false`.
+/// Les commandes appliquées, ou `None` si l'utilisateur souhaite mettre le jeu en pause.
 fn handle_input(
     spaceship: &mut Spaceship,
     missiles: &mut Vec<Missile>,
     gamepads: &mut Gamepads,
-) -> bool {
-    if is_key_down(KeyCode::Escape) {
-        return true;
-    }
+    bindings: &Bindings,
+    input: &mut InputState,
+    events: &mut Vec<GameEvent>,
+    cursor: Vec2,
+) -> Option<ShipControl> {
+    gamepads.poll();
+    input.update(bindings, gamepads);
 
-    if is_key_down(KeyCode::Left) {
-        spaceship.rotate_left();
+    if input.is_pressed(Action::Pause) {
+        return None;
     }
-    if is_key_down(KeyCode::Right) {
-        spaceship.rotate_right();
-    }
-    spaceship.set_push(is_key_down(KeyCode::Up));
 
-    if is_key_pressed(KeyCode::Space) {
-        let missile = Missile::new(spaceship.get_position(), spaceship.get_direction());
-        missiles.push(missile);
+    let mut control = bindings.read_control(gamepads, input);
+    if bindings.scheme == ControlScheme::Mouse {
+        input::apply_mouse_aim(
+            &mut control,
+            spaceship.get_position(),
+            spaceship.get_direction(),
+            cursor,
+        );
     }
+    apply_control(spaceship, missiles, &control, events);
 
-    gamepads.poll();
-
-    for gamepad in gamepads.all() {
-        let left_stick = gamepad.left_stick();
-        if gamepad.is_just_pressed(gamepads::Button::ActionRight) {
-            return true;
-        }
-
-        if left_stick.0 < -0.5 {
-            spaceship.rotate_left();
-        } else if left_stick.0 > 0.5 {
-            spaceship.rotate_right();
-        }
-
-        spaceship.set_push(left_stick.1 > 0.5);
+    Some(control)
+}
 
-        if gamepad.is_just_pressed(gamepads::Button::FrontRightLower) {
-            let missile = Missile::new(spaceship.get_position(), spaceship.get_direction());
-            missiles.push(missile);
-        }
+/// Applique les commandes d'une frame au vaisseau spatial.
+///
+/// Les commandes peuvent provenir du joueur ou du pilote automatique.
+///
+/// # Paramètres
+/// - `spaceship`: Référence mutable au vaisseau spatial.
+/// - `missiles`: Liste des missiles actifs.
+/// - `control`: Les commandes à appliquer.
+/// - `events`: Liste des événements de la frame, complétée par les tirs et la propulsion.
+fn apply_control(
+    spaceship: &mut Spaceship,
+    missiles: &mut Vec<Missile>,
+    control: &ShipControl,
+    events: &mut Vec<GameEvent>,
+) {
+    spaceship.rotate(control.rotate);
+    spaceship.set_thrust(control.thrust);
+    if control.thrust > 0.0 {
+        events.push(GameEvent::Thrust {
+            position: spaceship.get_position(),
+            direction: spaceship.get_direction(),
+            intensity: control.thrust.min(1.0),
+        });
     }
 
-    false
+    if control.fire && spaceship.try_fire() {
+        let direction = control.aim.unwrap_or(spaceship.get_direction());
+        let missile = Missile::new(spaceship.get_position(), direction);
+        events.push(GameEvent::MissileFired {
+            position: spaceship.get_position(),
+        });
+        missiles.push(missile);
+    }
 }
 
 /// Met à jour les positions et les états des objets dans le jeu.
@@ -180,18 +385,20 @@ fn handle_input(
 /// - `asteroids`: Liste des astéroïdes.
 /// - `spaceship`: Référence mutable au vaisseau spatial.
 /// - `missiles`: Liste des missiles actifs.
+/// - `bounds`: Les dimensions de la zone de jeu.
 fn update_model(
     asteroids: &mut [Asteroid],
     spaceship: &mut Spaceship,
     missiles: &mut Vec<Missile>,
+    bounds: Vec2,
 ) {
     for asteroid in asteroids {
-        asteroid.move_object();
+        asteroid.move_object(bounds);
     }
-    spaceship.update();
+    spaceship.update(bounds);
     missiles.retain(|missile| missile.is_active()); // Recevoir uniquement les missiles en état d'activation.
     for missile in missiles {
-        missile.update();
+        missile.update(bounds);
     }
 }
 
@@ -201,6 +408,7 @@ fn update_model(
 /// - `asteroids`: Liste des astéroïdes.
 /// - `spaceship`: Référence au vaisseau spatial.
 /// - `missiles`: Liste des missiles actifs.
+/// - `events`: Liste des événements de la frame, complétée par les collisions détectées.
 ///
 /// # Retour
 /// `true` si le jeu doit se terminer en raison d'une collision ou de la victoire, sinon `false`.
@@ -208,6 +416,7 @@ fn handle_collisions(
     asteroids: &mut Vec<Asteroid>,
     spaceship: &Spaceship,
     missiles: &mut [Missile],
+    events: &mut Vec<GameEvent>,
 ) -> bool {
     let mut new_asteroids = vec![];
     let mut to_remove = vec![];
@@ -218,9 +427,16 @@ fn handle_collisions(
         spaceship,
         &mut new_asteroids,
         &mut to_remove,
+        events,
     );
 
-    handle_missile_asteroid_collisions(missiles, asteroids, &mut new_asteroids, &mut to_remove);
+    handle_missile_asteroid_collisions(
+        missiles,
+        asteroids,
+        &mut new_asteroids,
+        &mut to_remove,
+        events,
+    );
 
     remove_collided_asteroids(asteroids, &to_remove);
 
@@ -233,20 +449,40 @@ fn handle_collisions(
     false
 }
 
-/// Gère la condition de victoire en vérifiant si la liste des astéroïdes est vide.
+/// Crée une vague de grands astéroïdes à des positions aléatoires du monde.
+///
+/// # Paramètres
+/// - `asteroids`: Liste des astéroïdes, complétée par la nouvelle vague.
+/// - `count`: Nombre d'astéroïdes de la vague.
+/// - `speed`: Multiplicateur de la vitesse des astéroïdes.
+/// - `world`: Les dimensions du monde.
+fn spawn_wave(asteroids: &mut Vec<Asteroid>, count: usize, speed: f32, world: Vec2) {
+    for _ in 0..count {
+        let mut asteroid = Asteroid::new(world);
+        asteroid.scale_speed(speed);
+        asteroids.push(asteroid);
+    }
+}
+
+/// Gère la condition de victoire.
+///
+/// Hors niveau, la partie est gagnée lorsque tous les astéroïdes ont été détruits ; dans
+/// un niveau, lorsque sa condition de victoire est remplie ; dans un mode scripté,
+/// lorsque le script l'annonce.
 ///
 /// # Paramètres
-/// * `asteroids` - Une slice à un vecteur d'astéroïdes, représentant les objets restants dans le jeu.
+/// * `renderer` - Le moteur de rendu de l'écran de victoire.
+/// * `state` - L'état de la partie.
+/// * `score` - Le score de la partie.
 ///
 /// # Retour
-/// Retourne `true` si tous les astéroïdes ont été détruits et le joueur a gagné, sinon retourne `false`.
-fn handle_win(asteroids: &[Asteroid]) -> bool {
-    if asteroids.is_empty() {
-        draw_you_win();
-        return true;
+/// Retourne `true` si le joueur a gagné, sinon retourne `false`.
+fn handle_win(renderer: &mut dyn Renderer, state: &GameState, score: u32) -> bool {
+    let won = state.is_won(score);
+    if won {
+        draw_you_win(renderer);
     }
-
-    false
+    won
 }
 
 /// Détecte et gère les collisions entre astéroïdes.
@@ -297,6 +533,7 @@ fn handle_asteroid_collisions(
 /// - `spaceship`: Référence au vaisseau spatial.
 /// - `new_asteroids`: Liste des nouveaux fragments d'astéroïdes.
 /// - `to_remove`: Liste des indices des astéroïdes à supprimer.
+/// - `events`: Liste des événements de la frame.
 ///
 /// # Retour
 /// `true` si une collision est détectée, sinon `false`.
@@ -305,9 +542,14 @@ fn handle_spaceship_asteroid_collision(
     spaceship: &Spaceship,
     new_asteroids: &mut Vec<Asteroid>,
     to_remove: &mut Vec<usize>,
+    events: &mut Vec<GameEvent>,
 ) -> bool {
     for (asteroid_index, asteroid) in asteroids.iter_mut().enumerate() {
         if asteroid.collide(spaceship) {
+            events.push(GameEvent::SpaceshipHit {
+                position: spaceship.get_position(),
+            });
+
             match asteroid.get_size() {
                 Asteroid::LARGE => {
                     new_asteroids.extend(asteroid.split());
@@ -335,11 +577,13 @@ fn handle_spaceship_asteroid_collision(
 /// - `asteroids`: Liste des astéroïdes.
 /// - `new_asteroids`: Liste des nouveaux fragments d'astéroïdes.
 /// - `to_remove`: Liste des indices des astéroïdes à supprimer.
+/// - `events`: Liste des événements de la frame.
 fn handle_missile_asteroid_collisions(
     missiles: &mut [Missile],
     asteroids: &mut [Asteroid],
     new_asteroids: &mut Vec<Asteroid>,
     to_remove: &mut Vec<usize>,
+    events: &mut Vec<GameEvent>,
 ) {
     for missile in missiles.iter_mut() {
         if !missile.is_active() {
@@ -349,6 +593,10 @@ fn handle_missile_asteroid_collisions(
         for (asteroid_index, asteroid) in asteroids.iter_mut().enumerate() {
             if missile.collide(asteroid) {
                 missile.deactivate();
+                events.push(GameEvent::AsteroidDestroyed {
+                    size: asteroid.get_size(),
+                    position: asteroid.get_position(),
+                });
 
                 match asteroid.get_size() {
                     Asteroid::LARGE => {
@@ -387,150 +635,1334 @@ fn remove_collided_asteroids(asteroids: &mut Vec<Asteroid>, to_remove: &[usize])
     }
 }
 
-/// Fonction principale qui initialise et exécute le jeu.
+/// Affiche l'écran de configuration des contrôles.
 ///
-/// Cette fonction initialise le jeu, gère l'affichage des menus, les choix de difficulté,
-/// et lance la boucle principale du jeu.
-#[macroquad::main("Asteroids game")]
-async fn main() {
-    let mut gamepads = Gamepads::new();
-    let background_texture = load_texture("../img/asteroide.png").await.unwrap();
-    background_texture.set_filter(FilterMode::Nearest);
-    let texture_spaceship = load_texture("../img/spaceship.png").await.unwrap();
-    background_texture.set_filter(FilterMode::Nearest);
+/// Le joueur choisit une action au clavier, à la croix directionnelle ou à la souris, la
+/// valide (Entrée, bouton A ou clic), puis appuie sur la touche, le bouton ou la direction
+/// du joystick à lui associer. Retour arrière ou le bouton X efface les associations de
+/// l'action qui a le focus, T active ou désactive le mode « twin-stick » et L change de
+/// langue. Échap, le bouton B ou « Back » enregistre les contrôles et revient à l'écran
+/// précédent.
+///
+/// # Paramètres
+/// - `bindings`: Associations à modifier.
+/// - `gamepads`: Référence mutable aux manettes pour gérer les entrées.
+async fn controls_menu(bindings: &mut Bindings, gamepads: &mut Gamepads) {
+    let mut menu = Menu::new();
+    let layout = Layout {
+        x: view::WIDTH * 0.1,
+        width: view::WIDTH * 0.8,
+        row_height: view::HEIGHT * 0.06,
+        spacing: view::HEIGHT * 0.012,
+        ..Layout::centered(0.06)
+    };
+    let mut capturing = None;
+
+    // Attente de la frame suivante, pour que la validation qui a ouvert l'écran ne soit pas
+    // relue par le menu
+    next_frame().await;
+    loop {
+        clear_background(BLACK);
+        view::set_screen_camera();
+        gamepads.poll();
+
+        let mut menu_input = MenuInput::read(gamepads);
+        if let Some(action) = capturing {
+            // Échap annule la capture sans modifier les associations
+            if is_key_pressed(KeyCode::Escape) {
+                capturing = None;
+            } else if let Some(binding) = input::capture_binding(gamepads) {
+                bindings.add(action, binding);
+                capturing = None;
+            }
+            // Le menu ne réagit pas aux entrées pendant la capture
+            menu_input = MenuInput {
+                mouse: menu_input.mouse,
+                ..Default::default()
+            };
+        } else {
+            let clear = is_key_pressed(KeyCode::Backspace)
+                || is_key_pressed(KeyCode::Delete)
+                || gamepads
+                    .all()
+                    .any(|gamepad| gamepad.is_just_pressed(Button::ActionLeft));
+            if let Some(&action) = Action::ALL.get(menu.get_focus()).filter(|_| clear) {
+                bindings.clear(action);
+            }
+            // Raccourcis clavier du mode « twin-stick » et de la langue
+            if is_key_pressed(KeyCode::T) {
+                bindings.analog.twin_stick = !bindings.analog.twin_stick;
+            }
+            if is_key_pressed(KeyCode::L) {
+                let language = locale::get_language().next();
+                locale::set_language(language);
+                if let Err(error) = language.save() {
+                    eprintln!("Impossible d'enregistrer {}: {error}", Language::FILE);
+                }
+            }
+        }
 
-    let mut difficulty = 0;
+        let mut language = Language::ALL
+            .iter()
+            .position(|&language| language == locale::get_language())
+            .unwrap_or(0);
+        let names: Vec<&str> = Language::ALL
+            .iter()
+            .map(|language| language.name())
+            .collect();
+        let mut ui = menu.begin(menu_input, layout);
+        ui.title(tr("menu.controls"));
+        if let Some(action) = bindings.edit(&mut ui) {
+            capturing = Some(action);
+        }
+        if ui.list(tr("controls.language"), &mut language, &names) {
+            let language = Language::ALL[language];
+            locale::set_language(language);
+            if let Err(error) = language.save() {
+                eprintln!("Impossible d'enregistrer {}: {error}", Language::FILE);
+            }
+        }
+        let back = ui.button(tr("controls.back")) || menu_input.back;
+        ui.end();
+
+        let help = if capturing.is_some() {
+            tr("controls.capture")
+        } else {
+            tr("controls.help")
+        };
+        draw_text(
+            help,
+            view::WIDTH * 0.1,
+            view::HEIGHT * 0.9,
+            view::HEIGHT * 0.035,
+            GRAY,
+        );
 
-    let mut selected_difficulty = false;
+        // Attente de la frame suivante, pour que le retour ne soit pas relu par l'écran
+        // précédent
+        next_frame().await;
+        if back {
+            if let Err(error) = bindings.save() {
+                eprintln!("Impossible d'enregistrer {}: {error}", Bindings::FILE);
+            }
+            return;
+        }
+    }
+}
 
-    // Sélection de la difficulté dans le menu
-    while !selected_difficulty {
+/// Affiche l'écran de réglage de la difficulté personnalisée.
+///
+/// Chaque valeur du profil se règle avec un curseur ; « Play » lance une partie avec ce
+/// profil, « Back » ou Échap revient au menu principal en conservant les réglages.
+///
+/// # Paramètres
+/// - `custom`: Le profil personnalisé à modifier.
+/// - `gamepads`: Référence mutable aux manettes pour gérer les entrées.
+///
+/// # Retour
+/// `true` si le joueur lance une partie, `false` s'il revient au menu principal.
+async fn custom_menu(custom: &mut Difficulty, gamepads: &mut Gamepads) -> bool {
+    let mut menu = Menu::new();
+    let layout = Layout {
+        row_height: view::HEIGHT * 0.06,
+        spacing: view::HEIGHT * 0.012,
+        ..Layout::centered(0.06)
+    };
+
+    loop {
         clear_background(BLACK);
+        view::set_screen_camera();
+        gamepads.poll();
+
+        let menu_input = MenuInput::read(gamepads);
+        let mut ui = menu.begin(menu_input, layout);
+        ui.title(tr("custom.title"));
+        custom.edit(&mut ui);
+        let play = ui.button(tr("custom.play"));
+        let back = ui.button(tr("custom.back")) || menu_input.back;
+        ui.end();
+
+        // Attente de la frame suivante, pour que la validation ne soit pas relue par le menu
+        next_frame().await;
+        if play || back {
+            return play;
+        }
+    }
+}
 
-        // Paramètres pour la taille des boutons et du texte dans le menu
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+/// Affiche la liste des modes de jeu intégrés et de ceux du répertoire du joueur.
+///
+/// # Paramètres
+/// - `directory`: Le répertoire optionnel des modes du joueur.
+/// - `gamepads`: Référence mutable aux manettes pour gérer les entrées.
+///
+/// # Retour
+/// Le mode choisi, ou `None` si le joueur revient au menu principal.
+async fn modes_menu(directory: Option<&Path>, gamepads: &mut Gamepads) -> Option<ModeScript> {
+    let modes = Script::list(directory);
+    let mut menu = Menu::new();
 
-        let button_width = screen_width * 0.4;
-        let button_height = screen_height * 0.1;
-        let font_size = screen_height * 0.05;
-        let title_font_size = screen_height * 0.08;
+    loop {
+        clear_background(BLACK);
+        view::set_screen_camera();
+        gamepads.poll();
+
+        let mut chosen = None;
+        let menu_input = MenuInput::read(gamepads);
+        let mut ui = menu.begin(menu_input, Layout::centered(0.15));
+        ui.title(tr("modes.title"));
+        for mode in &modes {
+            if ui.button(&mode.name) {
+                chosen = Some(mode.clone());
+            }
+        }
+        let back = ui.button(tr("modes.back")) || menu_input.back;
+        ui.end();
 
-        let button_x = (screen_width - button_width) / 2.0;
-        let easy_y = screen_height * 0.3;
-        let medium_y = easy_y + button_height + screen_height * 0.05;
-        let hard_y = medium_y + button_height + screen_height * 0.05;
+        // Attente de la frame suivante, pour que la validation ne soit pas relue par le menu
+        next_frame().await;
+        if chosen.is_some() || back {
+            return chosen;
+        }
+    }
+}
 
+/// Affiche les succès et leur avancement, jusqu'à ce que le joueur revienne au menu.
+///
+/// # Paramètres
+/// - `achievements`: Les succès et la progression du joueur.
+/// - `gamepads`: Référence mutable aux manettes pour gérer les entrées.
+async fn achievements_menu(achievements: &Achievements, gamepads: &mut Gamepads) {
+    let mut menu = Menu::new();
+
+    loop {
+        clear_background(BLACK);
+        view::set_screen_camera();
+        gamepads.poll();
+
+        let title = tr("achievements.title");
+        let size = measure_text(title, None, 50, 1.0);
         draw_text(
-            "Select Difficulty",
-            screen_width * 0.3,
-            screen_height * 0.2,
-            title_font_size,
+            title,
+            (view::WIDTH - size.width) / 2.0,
+            view::HEIGHT * 0.1,
+            50.0,
             WHITE,
         );
+        achievements.draw_list(view::HEIGHT * 0.2);
 
-        // Sélection de la difficulté en fonction de la position du clic de la souris
-        if is_mouse_button_pressed(MouseButton::Left) {
-            let (mx, my) = mouse_position();
+        let menu_input = MenuInput::read(gamepads);
+        let mut ui = menu.begin(menu_input, Layout::centered(0.86));
+        let back = ui.button(tr("achievements.back")) || menu_input.back;
+        ui.end();
 
-            if mx >= button_x
-                && mx <= button_x + button_width
-                && my >= easy_y
-                && my <= easy_y + button_height
-            {
-                difficulty = 5;
-                selected_difficulty = true;
-            } else if mx >= button_x
-                && mx <= button_x + button_width
-                && my >= medium_y
-                && my <= medium_y + button_height
-            {
-                difficulty = 30;
-                selected_difficulty = true;
-            } else if mx >= button_x
-                && mx <= button_x + button_width
-                && my >= hard_y
-                && my <= hard_y + button_height
-            {
-                difficulty = 100;
-                selected_difficulty = true;
+        // Attente de la frame suivante, pour que la validation ne soit pas relue par le menu
+        next_frame().await;
+        if back {
+            return;
+        }
+    }
+}
+
+/// Point d'entrée du programme.
+///
+/// Avec l'argument `--env` (ou `--env-entities`), lance l'environnement d'apprentissage
+/// par renforcement sur l'entrée et la sortie standard, sans ouvrir de fenêtre ; avec
+/// `--export-stats <fichier>`, exporte l'historique des parties en CSV ou en JSON ; avec
+/// `--tui`, lance une partie dans le terminal, dessinée en caractères.
+/// Sinon, ouvre la fenêtre et lance le jeu ; l'option `--world <largeur>x<hauteur>`
+/// choisit les dimensions du monde, l'option `--assets <répertoire>` remplace les
+/// images intégrées, l'option `--level <fichier>` choisit le niveau ouvert par l'éditeur,
+/// l'option `--campaign <fichier>` le premier niveau de la campagne, l'option
+/// `--modes <répertoire>` des modes de jeu supplémentaires et l'option `--seed <nombre>`
+/// la graine du défi fantôme.
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("--env") => gym::run_stdio(gym::ObservationKind::Rays),
+        Some("--env-entities") => gym::run_stdio(gym::ObservationKind::Entities),
+        Some("--export-stats") => stats::export_stdio(args.get(2).map(String::as_str)),
+        Some("--tui") => tui::run(
+            option_value(&args, "--seed")
+                .and_then(|seed| seed.parse().ok())
+                .unwrap_or_default(),
+            option_value(&args, "--world")
+                .and_then(camera::parse_world_size)
+                .unwrap_or(GameCamera::WORLD_SIZE),
+        ),
+        _ => {
+            let world = option_value(&args, "--world")
+                .and_then(camera::parse_world_size)
+                .unwrap_or(GameCamera::WORLD_SIZE);
+            let assets = option_value(&args, "--assets").map(PathBuf::from);
+            let level = PathBuf::from(option_value(&args, "--level").unwrap_or(Level::FILE));
+            let campaign = option_value(&args, "--campaign").map(PathBuf::from);
+            let modes = option_value(&args, "--modes").map(PathBuf::from);
+            let seed = option_value(&args, "--seed")
+                .and_then(|seed| seed.parse().ok())
+                .unwrap_or(Challenge::SEED);
+            let conf = Conf {
+                window_title: "Asteroids game".to_owned(),
+                window_width: view::WIDTH as i32,
+                window_height: view::HEIGHT as i32,
+                window_resizable: true,
+                ..Default::default()
+            };
+            macroquad::Window::from_config(conf, game(world, assets, level, campaign, modes, seed))
+        }
+    }
+}
+
+/// Retourne la valeur d'une option de la ligne de commande.
+///
+/// # Paramètres
+/// - `args`: Les arguments de la ligne de commande.
+/// - `name`: Le nom de l'option, par exemple `--world`.
+///
+/// # Retour
+/// L'argument qui suit l'option, ou `None` si l'option est absente.
+fn option_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let index = args.iter().position(|arg| arg == name)?;
+    args.get(index + 1).map(String::as_str)
+}
+
+/// Ressources partagées par le menu principal et les parties.
+struct Session {
+    /// Manettes connectées.
+    gamepads: Gamepads,
+    /// Associations des actions aux entrées.
+    bindings: Bindings,
+    /// Observateur de la perte de focus de la fenêtre.
+    focus: FocusWatcher,
+    /// Gestionnaire des effets sonores, alimenté par les événements de la partie.
+    audio: AudioManager,
+    /// L'image de fond, dessinée derrière les étoiles.
+    background_texture: Texture2D,
+    /// La texture du vaisseau spatial.
+    texture_spaceship: Texture2D,
+    /// Les dimensions du monde.
+    world: Vec2,
+    /// Indique si le nombre d'images par seconde est affiché pendant les parties.
+    show_fps: bool,
+    /// Profil de difficulté réglé depuis l'écran « Custom ».
+    custom: Difficulty,
+    /// Fichier du niveau ouvert par l'éditeur.
+    level: PathBuf,
+    /// Fichier du premier niveau de la campagne, ou `None` pour la campagne intégrée.
+    campaign: Option<PathBuf>,
+    /// Répertoire optionnel des modes de jeu du joueur.
+    modes: Option<PathBuf>,
+    /// Graine du défi fantôme.
+    seed: u64,
+    /// Succès et progression du joueur.
+    achievements: Achievements,
+}
+
+/// État d'une partie en cours, sur lequel agissent aussi les commandes de la console.
+struct GameState {
+    /// Astéroïdes présents dans le monde.
+    asteroids: Vec<Asteroid>,
+    /// Le vaisseau spatial du joueur.
+    spaceship: Spaceship,
+    /// Missiles actifs.
+    missiles: Vec<Missile>,
+    /// Points de bouclier restants.
+    health: f32,
+    /// Numéro de la vague en cours, à partir de 1.
+    wave: u32,
+    /// Indique si les collisions épargnent le bouclier du vaisseau.
+    god_mode: bool,
+    /// Nombre de pas de simulation par frame (`1.0` pour une vitesse normale).
+    time_scale: f32,
+    /// Position du curseur de la souris dans le monde.
+    cursor: Vec2,
+    /// Profil de difficulté de la partie.
+    difficulty: Difficulty,
+    /// Part accumulée du prochain astéroïde de renfort.
+    reinforcement: f32,
+    /// Position de départ du vaisseau.
+    spawn: Vec2,
+    /// Zones dangereuses du niveau.
+    hazards: Vec<Hazard>,
+    /// Vagues du niveau, ou `None` pour les vagues fixées par la difficulté.
+    waves: Option<Waves>,
+    /// Condition de victoire du niveau.
+    win: Win,
+    /// Script du mode de jeu, qui décide des vagues et de la victoire. Il est retiré s'il
+    /// s'interrompt sur une erreur : la partie est alors gagnée en vidant le champ.
+    script: Option<Script>,
+    /// Indique si le script a annoncé la victoire.
+    won: bool,
+    /// Générateur aléatoire des astéroïdes, initialisé avec la graine d'un défi.
+    rng: SmallRng,
+    /// Indique si une commande de la console a modifié la partie.
+    tainted: bool,
+}
+
+impl GameState {
+    /// Échelle de temps maximale acceptée par la console.
+    const MAX_TIME_SCALE: f32 = 4.0;
+    /// Nombre maximal d'astéroïdes créés par la commande `spawn` de la console.
+    const MAX_SPAWN: usize = 50;
+
+    /// Crée l'état d'une nouvelle partie, sans astéroïde.
+    ///
+    /// # Paramètres
+    /// - `spaceship`: Le vaisseau du joueur, placé au centre du monde.
+    /// - `world`: Les dimensions du monde.
+    /// - `difficulty`: Le profil de difficulté de la partie.
+    fn new(mut spaceship: Spaceship, world: Vec2, difficulty: Difficulty) -> Self {
+        spaceship.set_position(world / 2.0);
+        spaceship.set_fire_cooldown(difficulty.fire_cooldown);
+        Self {
+            asteroids: Vec::new(),
+            spaceship,
+            missiles: Vec::new(),
+            health: difficulty.health,
+            wave: 1,
+            god_mode: false,
+            time_scale: 1.0,
+            cursor: world / 2.0,
+            difficulty,
+            reinforcement: 0.0,
+            spawn: world / 2.0,
+            hazards: Vec::new(),
+            waves: None,
+            win: Win::Clear,
+            script: None,
+            won: false,
+            rng: SmallRng::from_entropy(),
+            tainted: false,
+        }
+    }
+
+    /// Place les objets d'un niveau : astéroïdes, départ du vaisseau, zones et vagues, et
+    /// retient sa condition de victoire.
+    ///
+    /// # Paramètres
+    /// - `level`: Le niveau à jouer.
+    fn load_level(&mut self, level: &Level) {
+        self.asteroids = level.spawn_asteroids();
+        for asteroid in &mut self.asteroids {
+            asteroid.scale_speed(self.difficulty.speed);
+        }
+        self.spawn = level.get_ship();
+        self.spaceship.set_position(self.spawn);
+        self.hazards = level.hazards.clone();
+        self.waves = Some(Waves::new(level));
+        self.win = level.win;
+    }
+
+    /// Déplace les objets du monde d'un pas de simulation.
+    ///
+    /// # Paramètres
+    /// - `world`: Les dimensions du monde.
+    fn update_objects(&mut self, world: Vec2) {
+        update_model(
+            &mut self.asteroids,
+            &mut self.spaceship,
+            &mut self.missiles,
+            world,
+        );
+    }
+
+    /// Gère les collisions, les zones dangereuses et les renforts d'un pas de simulation.
+    ///
+    /// # Paramètres
+    /// - `events`: Liste des événements de la frame, complétée par les collisions.
+    /// - `world`: Les dimensions du monde.
+    ///
+    /// # Retour
+    /// Les dégâts subis par le bouclier pendant ce pas.
+    fn apply_collisions(&mut self, events: &mut Vec<GameEvent>, world: Vec2) -> f32 {
+        let collision = asteroid::with_rng(&mut self.rng, || {
+            handle_collisions(
+                &mut self.asteroids,
+                &self.spaceship,
+                &mut self.missiles,
+                events,
+            )
+        });
+        let mut damage = 0.0;
+        if collision && !self.god_mode {
+            damage += self.difficulty.damage;
+        }
+        let position = self.spaceship.get_position();
+        if !self.god_mode && self.hazards.iter().any(|hazard| hazard.contains(position)) {
+            damage += Hazard::DRAIN;
+        }
+        self.health -= damage;
+        self.reinforce(world);
+        damage
+    }
+
+    /// Indique si la partie est gagnée.
+    ///
+    /// # Paramètres
+    /// - `score`: Le score de la partie.
+    fn is_won(&self, score: u32) -> bool {
+        match &self.waves {
+            Some(waves) => self.win.is_met(
+                self.asteroids.is_empty() && waves.is_done(),
+                waves.get_elapsed(),
+                score,
+            ),
+            None if self.script.is_some() => self.won,
+            None => self.asteroids.is_empty(),
+        }
+    }
+
+    /// Lance le script d'un mode de jeu et applique les actions de son `on_start`.
+    ///
+    /// # Paramètres
+    /// - `script`: Le script du mode.
+    /// - `hud`: L'interface de la partie.
+    /// - `world`: Les dimensions du monde.
+    fn start_script(&mut self, mut script: Script, hud: &mut Hud, world: Vec2) {
+        let commands = script.start();
+        self.script = Some(script);
+        self.apply_script(commands, hud, world);
+    }
+
+    /// Transmet au script du mode les événements d'une frame et applique ses actions.
+    ///
+    /// # Paramètres
+    /// - `delta`: La durée écoulée, en secondes.
+    /// - `events`: Les événements de la frame.
+    /// - `hud`: L'interface de la partie, qui fournit le score et affiche les messages.
+    /// - `world`: Les dimensions du monde.
+    fn update_script(&mut self, delta: f32, events: &[GameEvent], hud: &mut Hud, world: Vec2) {
+        let Some(script) = &mut self.script else {
+            return;
+        };
+        let snapshot = Snapshot {
+            score: hud.get_score(),
+            wave: self.wave,
+            asteroids: self.asteroids.len(),
+            elapsed: 0.0,
+        };
+        let commands = script.update(delta, snapshot, events);
+        self.apply_script(commands, hud, world);
+    }
+
+    /// Applique les actions demandées par le script ; un script en erreur est arrêté et
+    /// l'erreur est signalée.
+    ///
+    /// # Paramètres
+    /// - `commands`: Les actions du script, ou l'erreur qui l'a interrompu.
+    /// - `hud`: L'interface de la partie.
+    /// - `world`: Les dimensions du monde.
+    fn apply_script(&mut self, commands: Result<Vec<Command>, String>, hud: &mut Hud, world: Vec2) {
+        let result = commands.and_then(|commands| {
+            commands
+                .into_iter()
+                .try_for_each(|command| self.apply(command, hud, world))
+        });
+        if let Err(error) = result {
+            eprintln!("Erreur du script : {error}");
+            hud.show_message(tr("modes.error"));
+            self.script = None;
+        }
+    }
+
+    /// Applique une action demandée par le script.
+    ///
+    /// # Retour
+    /// Une erreur si l'action modifie une valeur de difficulté inconnue ou invalide.
+    fn apply(&mut self, command: Command, hud: &mut Hud, world: Vec2) -> Result<(), String> {
+        match command {
+            Command::Spawn { tier, count, at } => {
+                for _ in 0..count {
+                    let position = at.unwrap_or_else(|| Asteroid::new(world).get_position());
+                    let mut asteroid = Asteroid::with_size(tier.size(), position);
+                    asteroid.scale_speed(self.difficulty.speed);
+                    self.asteroids.push(asteroid);
+                }
             }
+            Command::SetDifficulty(name, value) => {
+                self.difficulty.set(&name, value)?;
+                self.health = self.health.min(self.difficulty.health);
+                self.spaceship
+                    .set_fire_cooldown(self.difficulty.fire_cooldown);
+                hud.set_multiplier(self.difficulty.score_multiplier);
+            }
+            Command::Message(text) => hud.show_message(text),
+            Command::NextWave => self.wave += 1,
+            Command::Win => self.won = true,
         }
+        Ok(())
+    }
 
-        // Affichage des boutons de sélection de difficulté
-        draw_rectangle(button_x, easy_y, button_width, button_height, DARKGRAY);
-        draw_text(
-            "Easy",
-            button_x + button_width * 0.4,
-            easy_y + button_height * 0.6,
-            font_size,
-            WHITE,
+    /// Fait apparaître au bord du monde les astéroïdes de renfort prévus pour un pas de
+    /// simulation.
+    ///
+    /// # Paramètres
+    /// - `world`: Les dimensions du monde.
+    fn reinforce(&mut self, world: Vec2) {
+        self.reinforcement += self.difficulty.spawn_per_step();
+        let count = self.reinforcement.floor();
+        self.reinforcement -= count;
+        spawn_wave(
+            &mut self.asteroids,
+            count as usize,
+            self.difficulty.speed,
+            world,
         );
+    }
 
-        draw_rectangle(button_x, medium_y, button_width, button_height, DARKGRAY);
-        draw_text(
-            "Medium",
-            button_x + button_width * 0.35,
-            medium_y + button_height * 0.6,
-            font_size,
-            WHITE,
+    /// Décrit l'état de la partie sous forme de texte, une ligne par valeur ou par objet.
+    fn dump(&self) -> String {
+        let ship = &self.spaceship;
+        let mut text = format!(
+            "health {}\nwave {}\ngod_mode {}\ntime_scale {}\n",
+            self.health, self.wave, self.god_mode, self.time_scale
+        );
+        text += &format!(
+            "spaceship {} {} {} {} {}\n",
+            ship.get_position().x,
+            ship.get_position().y,
+            ship.get_velocity().x,
+            ship.get_velocity().y,
+            ship.get_direction()
         );
+        for missile in &self.missiles {
+            text += &format!(
+                "missile {} {}\n",
+                missile.get_position().x,
+                missile.get_position().y
+            );
+        }
+        for asteroid in &self.asteroids {
+            text += &format!(
+                "asteroid {} {} {} {} {}\n",
+                asteroid.get_size(),
+                asteroid.get_position().x,
+                asteroid.get_position().y,
+                asteroid.get_speed().x,
+                asteroid.get_speed().y
+            );
+        }
+        text
+    }
+}
+
+/// Crée la console de développement avec les commandes agissant sur la partie.
+fn dev_console() -> Console<GameState> {
+    let mut console: Console<GameState> = Console::new();
+    console.register(
+        "spawn",
+        "<small|medium|large> [count] - spawn up to 50 asteroids at the cursor",
+        |state, args| {
+            let size = match args.first().copied() {
+                Some("small") => Asteroid::SMALL,
+                Some("medium") => Asteroid::MEDIUM,
+                Some("large") => Asteroid::LARGE,
+                _ => return Err("spawn <small|medium|large> [count]".to_string()),
+            };
+            let count = match args.get(1) {
+                Some(count) => count
+                    .parse()
+                    .map_err(|_| format!("invalid count: {count}"))?,
+                None => 1,
+            };
+            if count > GameState::MAX_SPAWN {
+                let max = GameState::MAX_SPAWN;
+                return Err(format!("spawn count must be at most {max}"));
+            }
+            for _ in 0..count {
+                let mut asteroid = Asteroid::with_size(size, state.cursor);
+                asteroid.scale_speed(state.difficulty.speed);
+                state.asteroids.push(asteroid);
+            }
+            state.tainted = true;
+            Ok(format!("spawned {count} asteroid(s)"))
+        },
+    );
+    console.register(
+        "health",
+        "<value> - set the shield of the current ship",
+        |state, args| {
+            let health: f32 = args
+                .first()
+                .and_then(|value| value.parse().ok())
+                .filter(|value: &f32| *value > 0.0)
+                .ok_or("health <value above 0>")?;
+            state.health = health;
+            state.tainted = true;
+            Ok(format!("health {health}"))
+        },
+    );
+    console.register("god", "[on|off] - toggle invulnerability", |state, args| {
+        state.god_mode = match args.first().copied() {
+            Some("on") => true,
+            Some("off") => false,
+            None => !state.god_mode,
+            Some(other) => return Err(format!("expected on or off, got {other}")),
+        };
+        state.tainted = true;
+        Ok(format!(
+            "god mode {}",
+            if state.god_mode { "on" } else { "off" }
+        ))
+    });
+    console.register(
+        "timescale",
+        "<factor> - set the simulation speed (0 to 4)",
+        |state, args| {
+            let scale: f32 = args
+                .first()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| (0.0..=GameState::MAX_TIME_SCALE).contains(value))
+                .ok_or("timescale <factor between 0 and 4>")?;
+            state.time_scale = scale;
+            state.tainted = true;
+            Ok(format!("time scale {scale}"))
+        },
+    );
+    console.register(
+        "dump",
+        "<file> - write the game state to a file",
+        |state, args| {
+            let [path] = args else {
+                return Err("dump <file>".to_string());
+            };
+            std::fs::write(path, state.dump()).map_err(|error| format!("{path}: {error}"))?;
+            Ok(format!("state written to {path}"))
+        },
+    );
+    console
+}
+
+/// Fonction principale qui initialise et exécute le jeu.
+///
+/// Cette fonction initialise le jeu, gère l'affichage des menus, les choix de difficulté,
+/// et lance la boucle principale du jeu.
+///
+/// # Paramètres
+/// - `world`: Les dimensions du monde dans lequel se déroulent les parties.
+/// - `assets`: Répertoire optionnel dont les images remplacent les images intégrées.
+/// - `level`: Le fichier du niveau ouvert par l'éditeur.
+/// - `campaign`: Le fichier optionnel du premier niveau de la campagne, à la place de la
+///   campagne intégrée.
+/// - `modes`: Le répertoire optionnel des modes de jeu du joueur.
+/// - `seed`: La graine du défi fantôme.
+async fn game(
+    world: Vec2,
+    assets: Option<PathBuf>,
+    level: PathBuf,
+    campaign: Option<PathBuf>,
+    modes: Option<PathBuf>,
+    seed: u64,
+) {
+    let Assets {
+        spaceship: texture_spaceship,
+        background: background_texture,
+    } = Assets::load(assets.as_deref());
+    locale::set_language(Language::load());
+
+    let mut attract_demo = AttractDemo::new(texture_spaceship.clone(), background_texture.clone());
+    let mut session = Session {
+        gamepads: Gamepads::new(),
+        bindings: Bindings::load(),
+        focus: FocusWatcher::new(),
+        audio: AudioManager::new(AudioSettings::load()).await,
+        background_texture,
+        texture_spaceship,
+        world,
+        show_fps: false,
+        custom: Difficulty::MEDIUM,
+        level,
+        campaign,
+        modes,
+        seed,
+        achievements: Achievements::load(),
+    };
+
+    let mut main_menu = Menu::new();
+
+    // Retour au menu principal à la fin de chaque partie
+    loop {
+        // Sélection de la difficulté dans le menu
+        let difficulty = loop {
+            clear_background(BLACK);
+            view::set_screen_camera();
+
+            // Échap ferme le jeu depuis le menu principal
+            if is_key_pressed(KeyCode::Escape) {
+                return;
+            }
+
+            // Partie de démonstration jouée par le pilote automatique en arrière-plan
+            attract_demo.update();
+            attract_demo.draw();
+
+            // Boutons de sélection de la difficulté et réglages
+            session.gamepads.poll();
+            let mut chosen = None;
+            let mut open_controls = false;
+            let mut open_custom = false;
+            let mut open_editor = false;
+            let mut open_campaign = false;
+            let mut open_modes = false;
+            let mut open_challenge = false;
+            let mut open_achievements = false;
+            let mut scheme = ControlScheme::ALL
+                .iter()
+                .position(|&scheme| scheme == session.bindings.scheme)
+                .unwrap_or(0);
+            let mut ui =
+                main_menu.begin(MenuInput::read(&session.gamepads), Layout::centered(0.12));
+            ui.title(tr("menu.title"));
+            for (label, preset) in Difficulty::PRESETS {
+                if ui.button(tr(label)) {
+                    chosen = Some(preset);
+                }
+            }
+            if ui.button(tr("menu.custom")) {
+                open_custom = true;
+            }
+            if ui.button(tr("menu.campaign")) {
+                open_campaign = true;
+            }
+            if ui.button(tr("menu.modes")) {
+                open_modes = true;
+            }
+            if ui.button(tr("menu.challenge")) {
+                open_challenge = true;
+            }
+            if ui.button(tr("menu.editor")) {
+                open_editor = true;
+            }
+            if ui.button(tr("menu.achievements")) {
+                open_achievements = true;
+            }
+            if ui.button(tr("menu.controls")) {
+                open_controls = true;
+            }
+            if ui.list(
+                tr("menu.scheme"),
+                &mut scheme,
+                &[tr("menu.keys_pad"), tr("menu.mouse_aim")],
+            ) {
+                session.bindings.scheme = ControlScheme::ALL[scheme];
+                if let Err(error) = session.bindings.save() {
+                    eprintln!("Impossible d'enregistrer {}: {error}", Bindings::FILE);
+                }
+            }
+            let mut settings = session.audio.get_settings();
+            if ui.slider(tr("menu.volume"), &mut settings.master, 0.0..=1.0, 0.1) {
+                session.audio.set_settings(settings);
+            }
+            ui.toggle(tr("menu.show_fps"), &mut session.show_fps);
+            ui.end();
+
+            if open_controls {
+                controls_menu(&mut session.bindings, &mut session.gamepads).await;
+                continue;
+            }
+            if open_campaign {
+                next_frame().await;
+                play_campaign(&mut session).await;
+                continue;
+            }
+            if open_modes {
+                let directory = session.modes.clone();
+                if let Some(script) = modes_menu(directory.as_deref(), &mut session.gamepads).await
+                {
+                    let mode = Mode::Script(&script);
+                    while play(Difficulty::LEVEL, mode, &mut session).await == Outcome::Restart {}
+                }
+                continue;
+            }
+            if open_challenge {
+                next_frame().await;
+                let challenge = Challenge::new(session.seed, ghost::default_directory());
+                let mode = Mode::Challenge(&challenge);
+                while play(Difficulty::LEVEL, mode, &mut session).await == Outcome::Restart {}
+                continue;
+            }
+            if open_achievements {
+                achievements_menu(&session.achievements, &mut session.gamepads).await;
+                continue;
+            }
+            if open_editor {
+                let editor = Editor::open(session.level.clone(), session.world);
+                editor.run(&mut session).await;
+                continue;
+            }
+            if open_custom {
+                if custom_menu(&mut session.custom, &mut session.gamepads).await {
+                    break session.custom;
+                }
+                continue;
+            }
+            if let Some(preset) = chosen {
+                break preset;
+            }
+
+            next_frame().await;
+        };
+
+        // Relance de la partie tant que le joueur choisit « Restart »
+        while play(difficulty, Mode::Waves, &mut session).await == Outcome::Restart {}
+    }
+}
+
+/// Déroulement d'une partie.
+#[derive(Debug, Clone, Copy)]
+enum Mode<'a> {
+    /// Vagues d'astéroïdes aléatoires fixées par la difficulté.
+    Waves,
+    /// Niveau de la campagne ou de l'éditeur.
+    Level(&'a Level),
+    /// Mode de jeu scripté, intégré au programme ou chargé depuis un fichier.
+    Script(&'a ModeScript),
+    /// Défi généré à partir d'une graine, contre le fantôme de la meilleure partie.
+    Challenge(&'a Challenge),
+}
+
+impl Mode<'_> {
+    /// Retourne le nom du mode enregistré dans l'historique des parties.
+    ///
+    /// # Paramètres
+    /// - `difficulty`: Le profil de difficulté de la partie.
+    fn name(&self, difficulty: &Difficulty) -> String {
+        match self {
+            Mode::Waves => difficulty.preset_name().unwrap_or("custom").to_string(),
+            Mode::Level(_) => "level".to_string(),
+            Mode::Script(script) => format!("mode:{}", script.name),
+            Mode::Challenge(challenge) => format!("challenge:{}", challenge.get_seed()),
+        }
+    }
+}
+
+/// Issue d'une partie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// Le joueur a gagné.
+    Won,
+    /// Le vaisseau a été détruit.
+    Lost,
+    /// Le joueur a choisi de recommencer la partie.
+    Restart,
+    /// Le joueur a abandonné la partie pour revenir au menu.
+    Quit,
+}
+
+impl Outcome {
+    /// Retourne le nom de l'issue enregistré dans l'historique des parties.
+    fn name(self) -> &'static str {
+        match self {
+            Outcome::Won => "won",
+            Outcome::Lost => "lost",
+            Outcome::Restart => "restart",
+            Outcome::Quit => "quit",
+        }
+    }
+}
+
+/// Joue les niveaux de la campagne l'un après l'autre, jusqu'au dernier ou à une défaite.
+///
+/// Un niveau perdu ou abandonné ramène au menu principal ; un niveau qui ne peut pas être
+/// chargé interrompt la campagne.
+///
+/// # Paramètres
+/// - `session`: Les ressources partagées avec le menu principal.
+async fn play_campaign(session: &mut Session) {
+    let mut campaign = match &session.campaign {
+        Some(start) => Campaign::new(start.clone()),
+        None => Campaign::shipped(),
+    };
+    loop {
+        let level = match campaign.load() {
+            Ok(level) => level,
+            Err(error) => {
+                eprintln!("Impossible de charger le niveau : {error}");
+                return;
+            }
+        };
+        match play(Difficulty::LEVEL, Mode::Level(&level), session).await {
+            Outcome::Restart => {}
+            Outcome::Won if campaign.advance(&level) => {}
+            Outcome::Won | Outcome::Lost | Outcome::Quit => return,
+        }
+    }
+}
 
-        draw_rectangle(button_x, hard_y, button_width, button_height, DARKGRAY);
+/// Joue une partie avec la difficulté choisie.
+///
+/// La partie est figée tant que le menu de pause est ouvert.
+///
+/// # Paramètres
+/// - `difficulty`: Le profil de difficulté de la partie.
+/// - `mode`: Le déroulement de la partie : vagues aléatoires, niveau, mode scripté ou défi.
+/// - `session`: Les ressources partagées avec le menu principal.
+///
+/// # Retour
+/// L'issue de la partie ; `Outcome::Quit` si le script du mode ne peut pas être chargé.
+async fn play(difficulty: Difficulty, mode: Mode<'_>, session: &mut Session) -> Outcome {
+    let mut run = Run::new(difficulty.preset_name());
+    let outcome = play_run(difficulty, mode, session, &mut run).await;
+
+    // Les astéroïdes détruits comptent pour les succès, quelle que soit l'issue
+    session.achievements.end_run(&run);
+    if let Err(error) = session.achievements.save() {
+        eprintln!("Impossible d'enregistrer {}: {error}", Achievements::FILE);
+    }
+
+    // Historique et résumé de la partie ; une partie modifiée par la console fausserait
+    // les comparaisons de l'historique
+    let entry = Entry::new(&run.stats, mode.name(&difficulty), outcome.name());
+    if !run.tainted {
+        if let Err(error) = History::append(&entry, Path::new(History::FILE)) {
+            eprintln!("Impossible d'enregistrer les statistiques : {error}");
+        }
+    }
+    if matches!(outcome, Outcome::Won | Outcome::Lost) {
+        summary_screen(&entry, &mut session.gamepads).await;
+    }
+    outcome
+}
+
+/// Affiche le résumé d'une partie terminée, jusqu'à ce que le joueur continue.
+///
+/// # Paramètres
+/// - `entry`: Les statistiques de la partie.
+/// - `gamepads`: Référence mutable aux manettes pour gérer les entrées.
+async fn summary_screen(entry: &Entry, gamepads: &mut Gamepads) {
+    let mut menu = Menu::new();
+
+    loop {
+        clear_background(BLACK);
+        view::set_screen_camera();
+        gamepads.poll();
+
+        let title = tr("stats.title");
+        let size = measure_text(title, None, 50, 1.0);
         draw_text(
-            "Hard",
-            button_x + button_width * 0.4,
-            hard_y + button_height * 0.6,
-            font_size,
+            title,
+            (view::WIDTH - size.width) / 2.0,
+            view::HEIGHT * 0.12,
+            50.0,
             WHITE,
         );
+        let mut y = view::HEIGHT * 0.22;
+        for (label, value) in entry.summary() {
+            draw_text(label, view::WIDTH * 0.3, y, 30.0, LIGHTGRAY);
+            let size = measure_text(&value, None, 30, 1.0);
+            draw_text(&value, view::WIDTH * 0.7 - size.width, y, 30.0, WHITE);
+            y += 44.0;
+        }
+
+        let menu_input = MenuInput::read(gamepads);
+        let mut ui = menu.begin(menu_input, Layout::centered(0.86));
+        let done = ui.button(tr("stats.continue")) || menu_input.back;
+        ui.end();
 
+        // Attente de la frame suivante, pour que la validation ne soit pas relue par le menu
         next_frame().await;
+        if done {
+            return;
+        }
     }
+}
 
-    // Initialisation du jeu avec la difficulté sélectionnée
-    let mut asteroids = Vec::new();
-    let mut spaceship = Spaceship::new(texture_spaceship);
-    let mut missiles = Vec::new();
-    let mut health = 3.0;
+/// Déroule une partie et les succès qu'elle débloque ; voir `play`.
+///
+/// # Paramètres
+/// - `difficulty`: Le profil de difficulté de la partie.
+/// - `mode`: Le déroulement de la partie.
+/// - `session`: Les ressources partagées avec le menu principal.
+/// - `run`: Le suivi de la partie, complété par ses événements.
+async fn play_run(
+    difficulty: Difficulty,
+    mode: Mode<'_>,
+    session: &mut Session,
+    run: &mut Run,
+) -> Outcome {
+    let Session {
+        ref mut gamepads,
+        ref mut bindings,
+        ref mut focus,
+        ref mut audio,
+        ref background_texture,
+        ref texture_spaceship,
+        world,
+        ref mut show_fps,
+        ref mut achievements,
+        ..
+    } = *session;
+    let world = match mode {
+        Mode::Level(level) => level.get_arena(),
+        Mode::Challenge(_) => Challenge::ARENA,
+        Mode::Waves | Mode::Script(_) => world,
+    };
 
-    // Création des astéroïdes en fonction de la difficulté
-    for _ in 0..difficulty {
-        asteroids.push(asteroid::Asteroid::new());
+    // Initialisation du jeu avec la difficulté sélectionnée
+    let mut state = GameState::new(Spaceship::new(texture_spaceship.clone()), world, difficulty);
+    let mut events = Vec::new();
+    let mut pause_menu: Option<PauseMenu> = None;
+    let mut input = InputState::new();
+    let mut particles = ParticleSystem::new();
+    let mut camera = GameCamera::new(world);
+    let mut starfield = Starfield::new(Some(background_texture.clone()));
+    let mut hud = Hud::new(*show_fps, difficulty.score_multiplier);
+    let mut console = dev_console();
+    let mut toasts = Toasts::new();
+    let mut renderer = Macroquad;
+    let mut steps = 0.0;
+    #[cfg(feature = "debug-overlay")]
+    let mut overlay = debug::DebugOverlay::new();
+
+    // Création des astéroïdes du niveau, du script ou en fonction de la difficulté
+    match mode {
+        Mode::Waves => spawn_wave(
+            &mut state.asteroids,
+            difficulty.asteroids,
+            difficulty.speed,
+            world,
+        ),
+        Mode::Level(level) => state.load_level(level),
+        Mode::Challenge(challenge) => {
+            state.load_level(&challenge.level());
+            state.rng = challenge.rng();
+        }
+        Mode::Script(script) => match script.load() {
+            Ok(script) => state.start_script(script, &mut hud, world),
+            Err(error) => {
+                eprintln!("Impossible de charger le mode : {error}");
+                return Outcome::Quit;
+            }
+        },
     }
 
+    // Enregistrement de la partie de défi et fantôme de la meilleure
+    let (mut replay, mut ghost) = match mode {
+        Mode::Challenge(challenge) => (
+            Some(Replay::new(challenge.get_seed())),
+            challenge.load_best().map(|best| {
+                Ghost::new(best, Spaceship::new(texture_spaceship.clone()), state.spawn)
+            }),
+        ),
+        _ => (None, None),
+    };
+
     // Boucle principale du jeu
     loop {
         // Dessin du jeu
-        draw(&asteroids, &spaceship, &missiles, &background_texture);
-        draw_health_bar(health);
+        clear_background(BLACK);
+        view::set_screen_camera();
+        starfield.draw(&mut renderer);
+        set_camera(&camera.get_camera());
+        draw_world(&mut renderer, &state, ghost.as_ref(), world);
+        particles.draw(&mut renderer);
+        #[cfg(feature = "debug-overlay")]
+        overlay.draw(&state.asteroids, &state.spaceship, &state.missiles);
+        view::set_screen_camera();
+        camera.draw_minimap(&mut renderer, &state.asteroids, &state.spaceship);
+        hud.draw(
+            &mut renderer,
+            &HudStatus {
+                lives: 1,
+                shield: state.health / state.difficulty.health,
+                wave: state.wave,
+                asteroids: state.asteroids.len(),
+                weapon: tr("hud.missile"),
+                reload: state.spaceship.get_reload(),
+                muted: audio.get_settings().muted,
+                objective: state
+                    .waves
+                    .as_ref()
+                    .map(|waves| state.win.describe(waves.get_elapsed())),
+            },
+        );
+        if let Some(ghost) = &ghost {
+            ghost.draw_split(&mut renderer);
+        }
+        toasts.draw(&mut renderer);
+        if bindings.scheme == ControlScheme::Mouse {
+            draw_crosshair(&mut renderer);
+        }
+        console.draw();
 
-        // Vérification des entrées du joueur
-        if handle_input(&mut spaceship, &mut missiles, &mut gamepads) {
-            break;
+        // Position du curseur dans le monde, pour la visée et la console
+        let (mx, my) = macroquad::input::mouse_position();
+        state.cursor = camera.screen_to_world(vec2(mx, my));
+
+        // Partie figée tant que la console est ouverte
+        let console_open = console.is_open();
+        console.update(&mut state);
+
+        // Une partie modifiée par la console ne peut pas être rejouée ni comparée, ni
+        // débloquer de succès
+        if state.tainted {
+            replay = None;
+            run.tainted = true;
+        }
+        if console_open || console.is_open() {
+            audio.stop_engine();
+            next_frame().await;
+            continue;
         }
 
-        // Mise à jour des objets dans le jeu
-        update_model(&mut asteroids, &mut spaceship, &mut missiles);
+        // M coupe ou rétablit le son, F3 affiche ou masque les images par seconde
+        if is_key_pressed(KeyCode::M) {
+            audio.toggle_mute();
+        }
+        if is_key_pressed(KeyCode::F3) {
+            hud.toggle_fps();
+            *show_fps = hud.get_show_fps();
+        }
+        #[cfg(feature = "debug-overlay")]
+        if is_key_pressed(KeyCode::F2) {
+            overlay.toggle();
+        }
 
-        // Gestion des collisions
-        if handle_collisions(&mut asteroids, &spaceship, &mut missiles) {
-            health -= 1.0;
+        // F12 enregistre une image SVG du monde, sans les particules ni l'interface
+        if is_key_pressed(KeyCode::F12) {
+            let mut svg = Svg::new(world);
+            draw_world(&mut svg, &state, ghost.as_ref(), world);
+            let path = Svg::snapshot_path();
+            match svg.save(&path) {
+                Ok(()) => hud.show_message(path.display().to_string()),
+                Err(error) => eprintln!("Impossible d'enregistrer l'image : {error}"),
+            }
+        }
+
+        // Partie figée pendant la pause
+        let focus_lost = focus.focus_lost();
+        if let Some(menu) = &mut pause_menu {
+            gamepads.poll();
+            input.update(bindings, gamepads);
+            let choice = menu.update(gamepads, &input);
+            audio.stop_engine();
+            match choice {
+                Some(PauseChoice::Resume) => pause_menu = None,
+                Some(PauseChoice::Restart) => return Outcome::Restart,
+                Some(PauseChoice::Settings) => controls_menu(bindings, gamepads).await,
+                Some(PauseChoice::QuitToMenu) => return Outcome::Quit,
+                None => {}
+            }
+            next_frame().await;
+            continue;
+        }
+
+        // Vérification des entrées du joueur ; la perte du focus met le jeu en pause avant
+        // que les commandes de la frame ne soient appliquées
+        events.clear();
+        let control = if focus_lost {
+            None
+        } else {
+            handle_input(
+                &mut state.spaceship,
+                &mut state.missiles,
+                gamepads,
+                bindings,
+                &mut input,
+                &mut events,
+                state.cursor,
+            )
+        };
+        let Some(control) = control else {
+            pause_menu = Some(PauseMenu::new());
+            next_frame().await;
+            continue;
+        };
+
+        if let Some(replay) = &mut replay {
+            replay.record(&control);
+        }
+
+        // Mise à jour des objets dans le jeu, plus ou moins souvent selon l'échelle de temps
+        steps += state.time_scale;
+        while steps >= 1.0 && state.health >= 0.0 {
+            steps -= 1.0;
+            state.update_objects(world);
+            if let Some(ghost) = &mut ghost {
+                ghost.advance(world);
+            }
+            particles.update();
+            starfield.update(state.spaceship.get_velocity());
+            run.stats.distance += state.spaceship.get_velocity().length();
+
+            // Gestion des collisions
+            #[cfg(feature = "debug-overlay")]
+            overlay.record(&state.asteroids, &state.spaceship, &state.missiles);
+            run.stats.damage += state.apply_collisions(&mut events, world);
+        }
+        camera.update(state.spaceship.get_position());
+        let delta = get_frame_time() * state.time_scale;
+        hud.update(delta);
+
+        // Temps de passage à chaque astéroïde touché, comparés à ceux du fantôme
+        if let Some(replay) = &mut replay {
+            for event in &events {
+                if matches!(event, GameEvent::AsteroidDestroyed { .. }) {
+                    let split = replay.split();
+                    if let Some(ghost) = &mut ghost {
+                        ghost.split(split, replay.inputs.len());
+                    }
+                }
+            }
+        }
+        if let Some(ghost) = &mut ghost {
+            ghost.update(delta);
         }
 
         // Gestion de fin du jeu
-        if health < 0.0 {
-            draw_game_over();
+        if state.health < 0.0 {
+            events.push(GameEvent::GameOver);
+        }
+        hud.handle_events(&events);
+        run.record(&events, delta);
+        run.stats.score = hud.get_score();
+        toasts.push(&achievements.update(run));
+        toasts.update(delta);
+        audio.update(&events);
+        particles.handle_events(&events);
+        camera.handle_events(&events);
+        if state.health < 0.0 {
+            audio.stop_engine();
+            draw_game_over(&mut renderer);
+            draw_final_score(&mut renderer, hud.get_score());
+            toasts.draw(&mut renderer);
             next_frame().await;
             thread::sleep(Duration::from_secs(3));
-            break;
+            return Outcome::Lost;
         }
 
-        // Gestion de la condition de victoire
-        if handle_win(&asteroids) {
+        // Vagues de renfort dans le mode à vagues ; dans un mode scripté, le script décide
+        let cleared = state.asteroids.is_empty();
+        if let Some(waves) = &mut state.waves {
+            let wave = waves.update(delta, cleared, world);
+            if !wave.is_empty() {
+                state.wave += 1;
+            }
+            for mut asteroid in wave {
+                asteroid.scale_speed(state.difficulty.speed);
+                state.asteroids.push(asteroid);
+            }
+        } else if state.script.is_some() {
+            state.update_script(delta, &events, &mut hud, world);
+        }
+        if handle_win(&mut renderer, &state, hud.get_score()) {
+            run.won = true;
+            toasts.push(&achievements.update(run));
+            draw_final_score(&mut renderer, hud.get_score());
+            // Seule une partie que la console n'a pas modifiée peut devenir la meilleure
+            if let (Mode::Challenge(challenge), Some(replay), false) = (mode, &replay, run.tainted)
+            {
+                match challenge.submit(replay) {
+                    Ok(true) => ghost::draw_new_best(&mut renderer, replay.get_time()),
+                    Ok(false) => {}
+                    Err(error) => eprintln!("Impossible d'enregistrer la partie : {error}"),
+                }
+            }
+            toasts.draw(&mut renderer);
+            audio.stop_engine();
             next_frame().await;
             thread::sleep(Duration::from_secs(3));
-            break;
+            return Outcome::Won;
         }
 
         next_frame().await;
@@ -553,7 +1985,7 @@ mod tests {
         handle_asteroid_collisions(&mut asteroids, &mut new_asteroids, &mut to_remove);
 
         assert_eq!(to_remove.len(), 2);
-        assert!(new_asteroids.len() > 0);
+        assert!(!new_asteroids.is_empty());
     }
 
     #[test]
@@ -569,16 +2001,116 @@ mod tests {
 
         let mut new_asteroids = Vec::new();
         let mut to_remove = Vec::new();
+        let mut events = Vec::new();
 
         handle_missile_asteroid_collisions(
             &mut missiles,
             &mut asteroids,
             &mut new_asteroids,
             &mut to_remove,
+            &mut events,
         );
 
         assert_eq!(to_remove.len(), 1);
-        assert!(new_asteroids.len() > 0 || asteroids.is_empty());
+        assert_eq!(
+            events,
+            vec![GameEvent::AsteroidDestroyed {
+                size: Asteroid::MEDIUM,
+                position: Vec2::new(100.0, 100.0),
+            }]
+        );
+        assert!(!new_asteroids.is_empty() || asteroids.is_empty());
+    }
+
+    #[test]
+    fn test_dev_console_commands() {
+        let world = vec2(800.0, 600.0);
+        let mut state = GameState::new(
+            Spaceship::without_texture(Vec2::ZERO),
+            world,
+            Difficulty::MEDIUM,
+        );
+        let mut console = dev_console();
+
+        console.execute("spawn medium 3", &mut state);
+        console.execute("spawn large 4000000000", &mut state);
+        console.execute("god", &mut state);
+        console.execute("health 1.5", &mut state);
+        console.execute("timescale 9", &mut state);
+
+        assert_eq!(state.asteroids.len(), 3);
+        assert_eq!(state.asteroids[0].get_position(), world / 2.0);
+        assert!(state.god_mode);
+        assert_eq!(state.health, 1.5);
+        assert_eq!(state.time_scale, 1.0);
+        assert!(state.dump().contains("asteroid 30 400 300"));
+    }
+
+    #[test]
+    fn test_dev_console_taints_the_run() {
+        let mut state = GameState::new(
+            Spaceship::without_texture(Vec2::ZERO),
+            vec2(800.0, 600.0),
+            Difficulty::MEDIUM,
+        );
+        let mut console = dev_console();
+
+        // Une commande refusée ou sans effet sur la partie ne la marque pas
+        console.execute("health -1", &mut state);
+        console.execute("help", &mut state);
+        assert!(!state.tainted);
+
+        console.execute("health 99", &mut state);
+        assert!(state.tainted);
+    }
+
+    #[test]
+    fn test_script_commands_apply_to_state() {
+        let world = vec2(800.0, 600.0);
+        let mut state = GameState::new(
+            Spaceship::without_texture(Vec2::ZERO),
+            world,
+            Difficulty::LEVEL,
+        );
+        let mut hud = Hud::new(false, 1.0);
+        let script = Script::compile(
+            r#"fn on_start() {
+                spawn_asteroids("medium", 2);
+                set_difficulty("health", 2);
+                next_wave();
+                win();
+            }"#,
+        )
+        .unwrap();
+
+        state.start_script(script, &mut hud, world);
+
+        assert_eq!(state.asteroids.len(), 2);
+        assert_eq!(state.asteroids[0].get_size(), Asteroid::MEDIUM);
+        assert_eq!(state.health, 2.0);
+        assert_eq!(state.wave, 2);
+        assert!(state.won);
+    }
+
+    #[test]
+    fn test_script_error_falls_back_to_clearing_the_field() {
+        let world = vec2(800.0, 600.0);
+        let mut state = GameState::new(
+            Spaceship::without_texture(Vec2::ZERO),
+            world,
+            Difficulty::LEVEL,
+        );
+        let mut hud = Hud::new(false, 1.0);
+        let script = Script::compile(r#"fn on_start() { spawn_asteroids("small", 1); }"#).unwrap();
+
+        state.start_script(script, &mut hud, world);
+        state.asteroids.clear();
+        assert!(!state.is_won(0));
+
+        // Un script en erreur est arrêté, et la partie se gagne comme le mode à vagues
+        state.apply_script(Err("boom".to_string()), &mut hud, world);
+        assert!(state.script.is_none());
+        assert!(state.is_won(0));
     }
 
     #[test]
@@ -4,6 +4,7 @@
 //! une vitesse, et un état (actif ou inactif). Ils se déplacent dans la direction où le vaisseau pointe
 //! et sont désactivés lorsqu'ils sortent des limites de l'écran.
 
+use crate::render::Renderer;
 use macroquad::prelude::*;
 
 /// Représente un missile dans le jeu.
@@ -42,18 +43,24 @@ impl Missile {
 
     /// Met à jour la position du missile en fonction de sa vitesse.
     ///
-    /// Si le missile dépasse les limites de l'écran, il est désactivé.
-    pub fn update(&mut self) {
+    /// Si le missile dépasse les limites de la zone de jeu, il est désactivé.
+    ///
+    /// # Paramètres
+    /// - `bounds`: Les dimensions de la zone de jeu.
+    pub fn update(&mut self, bounds: Vec2) {
         if self.active {
             self.position += self.velocity;
-            self.active = Self::bound_pos(self.position);
+            self.active = Self::bound_pos(self.position, bounds);
         }
     }
 
     /// Dessine le missile à sa position actuelle, si celui-ci est actif.
-    pub fn draw(&self) {
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
         if self.active {
-            draw_circle(self.position.x, self.position.y, Self::SIZE, RED);
+            renderer.circle(self.position, Self::SIZE, RED);
         }
     }
 
@@ -76,17 +83,24 @@ impl Missile {
         self.position
     }
 
-    /// Vérifie si une position donnée est dans les limites de l'écran.
+    /// Retourne la vitesse actuelle du missile.
+    #[cfg(feature = "debug-overlay")]
+    pub fn get_velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    /// Vérifie si une position donnée est dans les limites de la zone de jeu.
     ///
-    /// # Paramètre
+    /// # Paramètres
     /// - `pos`: La position à vérifier.
+    /// - `bounds`: Les dimensions de la zone de jeu.
     ///
     /// # Retour
-    /// - `true` si la position est dans les limites de l'écran.
+    /// - `true` si la position est dans les limites de la zone de jeu.
     /// - `false` si la position est hors des limites.
-    fn bound_pos(pos: Vec2) -> bool {
-        let x = !(pos.x < 0.0 || pos.x > screen_width());
-        let y = !(pos.y < 0.0 || pos.y > screen_height());
+    fn bound_pos(pos: Vec2, bounds: Vec2) -> bool {
+        let x = !(pos.x < 0.0 || pos.x > bounds.x);
+        let y = !(pos.y < 0.0 || pos.y > bounds.y);
         x & y
     }
 }
@@ -109,27 +123,26 @@ mod tests {
         assert!(missile.active);
     }
 
-    // Les tests macroquad::test dans missile et spaceship ne peuvent pas coexister en même temps. Si l'on commente l'un des deux,
-    // les tests se déroulent correctement, mais si les deux sont actifs, l'un d'entre eux échoue. Cela pourrait être dû à
-    // une erreur causée par l'asynchronisme de macroquad.
-
-    // #[macroquad::test]
-    // async fn test_missile_update() {
-    //     let spaceship1_position = Vec2::new(100.0,100.0);
-    //     let spaceship1_rotation = 0.0;
-    //     let mut missile = Missile::new(spaceship1_position, spaceship1_rotation);
-    //     missile.update();
-    //     let expected1_position = spaceship1_position + Vec2::from_angle(spaceship1_rotation) * Missile::SPEED;
-    //     assert_eq!(missile.position, expected1_position);
-
-    //     assert!(missile.is_active());
-
-    //     missile.position = Vec2::new(-10.0, -10.0);
-    //     missile.update();
-    //     assert!(!missile.is_active());
-
-    //     missile.position = Vec2::new(screen_width() - 1.0, screen_height() - 1.0);
-    //     missile.update();
-    //     assert!(!missile.is_active());
-    // }
+    #[test]
+    fn test_missile_update() {
+        let bounds = vec2(1920.0, 1080.0);
+        let spaceship1_position = Vec2::new(100.0, 100.0);
+        let spaceship1_rotation = 0.0;
+        let mut missile = Missile::new(spaceship1_position, spaceship1_rotation);
+        missile.update(bounds);
+        let expected1_position =
+            spaceship1_position + Vec2::from_angle(spaceship1_rotation) * Missile::SPEED;
+        assert_eq!(missile.position, expected1_position);
+
+        assert!(missile.is_active());
+
+        missile.position = Vec2::new(-10.0, -10.0);
+        missile.update(bounds);
+        assert!(!missile.is_active());
+
+        missile.position = Vec2::new(bounds.x - 1.0, bounds.y - 1.0);
+        missile.active = true;
+        missile.update(bounds);
+        assert!(!missile.is_active());
+    }
 }
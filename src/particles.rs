@@ -0,0 +1,226 @@
+//! Module des particules pour un jeu Asteroids.
+//!
+//! Ce module définit un système de particules alimenté par les événements de jeu :
+//! des débris à la destruction des astéroïdes, des gaz d'échappement pendant la
+//! propulsion et des étincelles lorsque le bouclier du vaisseau est touché.
+//!
+//! Les particules sont stockées dans un réservoir de taille fixe : lorsqu'il est plein,
+//! les nouvelles particules remplacent les plus anciennes, sans allocation pendant la partie.
+
+use crate::{asteroid::Asteroid, events::GameEvent, render::Renderer, spaceship::Spaceship};
+use ::rand::{rngs::SmallRng, Rng, SeedableRng};
+use macroquad::prelude::*;
+use std::f32::consts::PI;
+
+/// Particule élémentaire.
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    /// Position actuelle.
+    position: Vec2,
+    /// Vitesse, en pixels par frame.
+    velocity: Vec2,
+    /// Nombre de frames restantes avant que la particule disparaisse.
+    life: u32,
+    /// Durée de vie initiale, utilisée pour le fondu.
+    max_life: u32,
+    /// Rayon de la particule.
+    size: f32,
+    /// Couleur de la particule.
+    color: Color,
+}
+
+impl Particle {
+    /// Particule inactive, occupant un emplacement libre du réservoir.
+    const DEAD: Particle = Particle {
+        position: Vec2::ZERO,
+        velocity: Vec2::ZERO,
+        life: 0,
+        max_life: 1,
+        size: 0.0,
+        color: WHITE,
+    };
+}
+
+/// Système de particules à réservoir de taille fixe.
+pub struct ParticleSystem {
+    /// Réservoir des particules, actives ou non.
+    particles: Vec<Particle>,
+    /// Prochain emplacement du réservoir à utiliser.
+    next: usize,
+    /// Générateur aléatoire propre au système, pour ne pas perturber la simulation.
+    rng: SmallRng,
+}
+
+impl ParticleSystem {
+    /// Nombre maximal de particules simultanées.
+    pub const CAPACITY: usize = 512;
+    /// Freinage appliqué à la vitesse des particules à chaque frame.
+    pub const DRAG: f32 = 0.97;
+
+    /// Crée un système de particules vide.
+    pub fn new() -> Self {
+        Self {
+            particles: vec![Particle::DEAD; Self::CAPACITY],
+            next: 0,
+            rng: SmallRng::seed_from_u64(0),
+        }
+    }
+
+    /// Émet les particules correspondant aux événements d'une frame.
+    ///
+    /// # Paramètres
+    /// - `events`: Les événements survenus pendant la frame.
+    pub fn handle_events(&mut self, events: &[GameEvent]) {
+        for event in events {
+            match *event {
+                GameEvent::AsteroidDestroyed { size, position } => self.debris(size, position),
+                GameEvent::SpaceshipHit { position } => self.sparks(position),
+                GameEvent::Thrust {
+                    position,
+                    direction,
+                    intensity,
+                } => self.exhaust(position, direction, intensity),
+                GameEvent::MissileFired { .. } | GameEvent::GameOver => {}
+            }
+        }
+    }
+
+    /// Fait avancer toutes les particules actives d'une frame.
+    pub fn update(&mut self) {
+        for particle in self.particles.iter_mut().filter(|p| p.life > 0) {
+            particle.position += particle.velocity;
+            particle.velocity *= Self::DRAG;
+            particle.life -= 1;
+        }
+    }
+
+    /// Dessine toutes les particules actives, en les estompant en fin de vie.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        for particle in self.particles.iter().filter(|p| p.life > 0) {
+            let fade = particle.life as f32 / particle.max_life as f32;
+            let mut color = particle.color;
+            color.a *= fade;
+            renderer.circle(particle.position, particle.size * (0.5 + fade * 0.5), color);
+        }
+    }
+
+    /// Retourne le nombre de particules actives.
+    #[cfg(test)]
+    fn get_active_count(&self) -> usize {
+        self.particles.iter().filter(|p| p.life > 0).count()
+    }
+
+    /// Émet des débris dont le nombre et la vitesse dépendent de la taille de l'astéroïde.
+    fn debris(&mut self, size: f32, position: Vec2) {
+        let count = (size / Asteroid::SMALL) as usize * 4;
+        let speed = 1.0 + size / Asteroid::LARGE * 2.0;
+        for _ in 0..count {
+            let angle = self.rng.gen_range(0.0..2.0 * PI);
+            let velocity = Vec2::from_angle(angle) * self.rng.gen_range(0.3..1.0) * speed;
+            let life = self.rng.gen_range(30..60);
+            let shade = self.rng.gen_range(0.5..0.8);
+            let radius = self.rng.gen_range(1.0..3.0);
+            self.emit(
+                position,
+                velocity,
+                life,
+                radius,
+                Color::new(shade, shade * 0.9, shade * 0.8, 1.0),
+            );
+        }
+    }
+
+    /// Émet des étincelles au point d'impact sur le bouclier du vaisseau.
+    fn sparks(&mut self, position: Vec2) {
+        for _ in 0..20 {
+            let angle = self.rng.gen_range(0.0..2.0 * PI);
+            let velocity = Vec2::from_angle(angle) * self.rng.gen_range(2.0..5.0);
+            let life = self.rng.gen_range(10..25);
+            self.emit(
+                position,
+                velocity,
+                life,
+                1.5,
+                Color::new(0.6, 0.9, 1.0, 1.0),
+            );
+        }
+    }
+
+    /// Émet des gaz d'échappement à l'arrière du vaisseau.
+    fn exhaust(&mut self, position: Vec2, direction: f32, intensity: f32) {
+        let backward = -Vec2::from_angle(direction);
+        let nozzle = position + backward * Spaceship::SIZE * 0.4;
+        let count = (intensity * 3.0).ceil() as usize;
+        for _ in 0..count {
+            let spread = self.rng.gen_range(-0.3..0.3);
+            let velocity = Vec2::from_angle(direction + PI + spread) * self.rng.gen_range(1.5..3.0);
+            let life = self.rng.gen_range(8..18);
+            self.emit(nozzle, velocity, life, 2.0, Color::new(1.0, 0.6, 0.2, 0.9));
+        }
+    }
+
+    /// Place une particule dans le prochain emplacement du réservoir.
+    fn emit(&mut self, position: Vec2, velocity: Vec2, life: u32, size: f32, color: Color) {
+        self.particles[self.next] = Particle {
+            position,
+            velocity,
+            life,
+            max_life: life,
+            size,
+            color,
+        };
+        self.next = (self.next + 1) % Self::CAPACITY;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debris_scales_with_tier() {
+        let mut large = ParticleSystem::new();
+        large.handle_events(&[GameEvent::AsteroidDestroyed {
+            size: Asteroid::LARGE,
+            position: vec2(0.0, 0.0),
+        }]);
+        let mut small = ParticleSystem::new();
+        small.handle_events(&[GameEvent::AsteroidDestroyed {
+            size: Asteroid::SMALL,
+            position: vec2(0.0, 0.0),
+        }]);
+
+        assert!(large.get_active_count() > small.get_active_count());
+        assert!(small.get_active_count() > 0);
+    }
+
+    #[test]
+    fn test_particles_expire() {
+        let mut system = ParticleSystem::new();
+        system.handle_events(&[GameEvent::SpaceshipHit {
+            position: vec2(10.0, 10.0),
+        }]);
+
+        for _ in 0..60 {
+            system.update();
+        }
+
+        assert_eq!(system.get_active_count(), 0);
+    }
+
+    #[test]
+    fn test_pool_is_bounded() {
+        let mut system = ParticleSystem::new();
+        for _ in 0..100 {
+            system.handle_events(&[GameEvent::AsteroidDestroyed {
+                size: Asteroid::LARGE,
+                position: vec2(0.0, 0.0),
+            }]);
+        }
+
+        assert_eq!(system.get_active_count(), ParticleSystem::CAPACITY);
+    }
+}
@@ -0,0 +1,191 @@
+//! Module du menu de pause pour un jeu Asteroids.
+//!
+//! Ce module définit le menu affiché par-dessus la partie figée, ainsi que la détection
+//! de la perte de focus de la fenêtre, qui met automatiquement le jeu en pause.
+
+use crate::input::{Action, InputState};
+use crate::locale::tr;
+use crate::ui::{self, Item, Layout, Menu, MenuInput};
+use crate::view;
+use gamepads::Gamepads;
+use macroquad::input::utils::{register_input_subscriber, repeat_all_miniquad_input};
+use macroquad::miniquad::EventHandler;
+use macroquad::prelude::*;
+
+/// Choix proposé par le menu de pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseChoice {
+    /// Reprendre la partie.
+    Resume,
+    /// Recommencer la partie avec la même difficulté.
+    Restart,
+    /// Ouvrir l'écran des contrôles.
+    Settings,
+    /// Abandonner la partie et revenir au menu principal.
+    QuitToMenu,
+}
+
+impl PauseChoice {
+    /// Liste de tous les choix, dans l'ordre d'affichage.
+    pub const ALL: [PauseChoice; 4] = [
+        PauseChoice::Resume,
+        PauseChoice::Restart,
+        PauseChoice::Settings,
+        PauseChoice::QuitToMenu,
+    ];
+
+    /// Retourne le texte affiché pour ce choix.
+    pub fn label(self) -> &'static str {
+        match self {
+            PauseChoice::Resume => tr("pause.resume"),
+            PauseChoice::Restart => tr("pause.restart"),
+            PauseChoice::Settings => tr("pause.settings"),
+            PauseChoice::QuitToMenu => tr("pause.quit"),
+        }
+    }
+}
+
+/// Menu de pause affiché pendant que la partie est figée.
+pub struct PauseMenu {
+    /// Menu dont le focus désigne le choix sélectionné dans `PauseChoice::ALL`.
+    menu: Menu,
+}
+
+impl PauseMenu {
+    /// Crée un menu de pause avec « Resume » sélectionné.
+    pub fn new() -> Self {
+        Self { menu: Menu::new() }
+    }
+
+    /// Traite les entrées du joueur et dessine le menu par-dessus la partie, assombrie.
+    ///
+    /// Le clavier (flèches, Entrée), la croix directionnelle et le bouton A des manettes
+    /// ainsi que la souris permettent de choisir. L'action « pause » reprend la partie.
+    ///
+    /// # Paramètres
+    /// - `gamepads`: Manettes, déjà interrogées pour cette frame.
+    /// - `input`: Les entrées de la frame, déjà lues.
+    ///
+    /// # Retour
+    /// Le choix validé par le joueur, ou `None` s'il n'a encore rien choisi.
+    pub fn update(&mut self, gamepads: &Gamepads, input: &InputState) -> Option<PauseChoice> {
+        draw_rectangle(
+            0.0,
+            0.0,
+            view::WIDTH,
+            view::HEIGHT,
+            Color::from_rgba(0, 0, 0, 170),
+        );
+
+        let (choice, items) =
+            self.choose(MenuInput::read(gamepads), input.is_pressed(Action::Pause));
+        ui::draw_items(&items);
+        choice
+    }
+
+    /// Traite les entrées d'une frame, sans dessiner le menu.
+    ///
+    /// # Paramètres
+    /// - `menu_input`: Les entrées de navigation de la frame.
+    /// - `pause`: Indique si l'action « pause » vient d'être déclenchée.
+    ///
+    /// # Retour
+    /// Le choix validé par le joueur, ou `None`, puis les éléments du menu à dessiner.
+    fn choose(&mut self, menu_input: MenuInput, pause: bool) -> (Option<PauseChoice>, Vec<Item>) {
+        let mut choice = None;
+        let mut ui = self.menu.begin(menu_input, Layout::centered(0.22));
+        ui.title(tr("pause.title"));
+        for option in PauseChoice::ALL {
+            if ui.button(option.label()) {
+                choice = Some(option);
+            }
+        }
+        let items = ui.finish();
+
+        if pause {
+            return (Some(PauseChoice::Resume), items);
+        }
+        (choice, items)
+    }
+}
+
+/// Détecte la perte de focus de la fenêtre.
+///
+/// Macroquad transmet la perte de focus comme une minimisation de la fenêtre : l'événement
+/// est relu à chaque frame depuis la file d'événements de miniquad.
+pub struct FocusWatcher {
+    /// Identifiant d'abonné aux événements de miniquad.
+    subscriber: usize,
+    /// Indique si le focus a été perdu depuis la dernière vérification.
+    lost: bool,
+}
+
+impl FocusWatcher {
+    /// Crée un observateur et l'abonne aux événements de la fenêtre.
+    pub fn new() -> Self {
+        Self {
+            subscriber: register_input_subscriber(),
+            lost: false,
+        }
+    }
+
+    /// Indique si la fenêtre a perdu le focus depuis le dernier appel.
+    ///
+    /// Doit être appelée à chaque frame pour vider la file d'événements.
+    pub fn focus_lost(&mut self) -> bool {
+        self.lost = false;
+        let subscriber = self.subscriber;
+        repeat_all_miniquad_input(self, subscriber);
+        self.lost
+    }
+}
+
+impl EventHandler for FocusWatcher {
+    fn update(&mut self) {}
+
+    fn draw(&mut self) {}
+
+    fn window_minimized_event(&mut self) {
+        self.lost = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_menu_choices() {
+        let mut menu = PauseMenu::new();
+        let up = MenuInput {
+            up: true,
+            ..Default::default()
+        };
+        let down = MenuInput {
+            down: true,
+            ..Default::default()
+        };
+        let activate = MenuInput {
+            activate: true,
+            ..Default::default()
+        };
+
+        let (choice, items) = menu.choose(MenuInput::default(), false);
+        assert_eq!(choice, None);
+        assert_eq!(items.len(), PauseChoice::ALL.len() + 1);
+
+        // La sélection revient au dernier choix depuis le premier
+        menu.choose(up, false);
+        assert_eq!(
+            menu.choose(activate, false).0,
+            Some(PauseChoice::QuitToMenu)
+        );
+
+        menu.choose(down, false);
+        menu.choose(down, false);
+        assert_eq!(menu.choose(activate, false).0, Some(PauseChoice::Restart));
+
+        // L'action « pause » reprend la partie, quel que soit le choix sélectionné
+        assert_eq!(menu.choose(down, true).0, Some(PauseChoice::Resume));
+    }
+}
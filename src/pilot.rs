@@ -0,0 +1,207 @@
+//! Module du pilote automatique pour un jeu Asteroids.
+//!
+//! Ce module définit un pilote contrôlé par l'ordinateur. À partir de l'état du vaisseau
+//! et des astéroïdes, il produit les mêmes commandes que le joueur (`ShipControl`) :
+//! il vise l'astéroïde le plus proche en anticipant son déplacement et évite les
+//! astéroïdes qui menacent d'entrer en collision avec le vaisseau.
+
+use crate::{
+    asteroid::Asteroid,
+    control::{angle_diff, steer, ShipControl},
+    missile::Missile,
+    spaceship::Spaceship,
+};
+use macroquad::prelude::*;
+use std::f32::consts::PI;
+
+/// Pilote automatique du vaisseau spatial.
+pub struct Pilot {
+    /// Nombre de frames restantes avant de pouvoir tirer à nouveau.
+    cooldown: u32,
+}
+
+impl Pilot {
+    /// Nombre de frames entre deux tirs.
+    pub const FIRE_COOLDOWN: u32 = 12;
+    /// Écart angulaire maximal (en radians) entre le vaisseau et sa cible pour tirer.
+    pub const AIM_TOLERANCE: f32 = 0.08;
+    /// Distance maximale à laquelle le pilote tire sur une cible.
+    pub const FIRE_RANGE: f32 = 450.0;
+    /// Nombre de frames pendant lesquelles les trajectoires sont anticipées pour l'évitement.
+    pub const DODGE_HORIZON: f32 = 60.0;
+    /// Marge de sécurité ajoutée aux rayons lors de l'évitement.
+    pub const DODGE_MARGIN: f32 = 20.0;
+
+    /// Crée un nouveau pilote prêt à tirer.
+    pub fn new() -> Self {
+        Self { cooldown: 0 }
+    }
+
+    /// Calcule les commandes à appliquer au vaisseau pour la frame courante.
+    ///
+    /// # Paramètres
+    /// - `spaceship`: Le vaisseau piloté.
+    /// - `asteroids`: Liste des astéroïdes présents.
+    ///
+    /// # Retour
+    /// Les commandes à appliquer au vaisseau.
+    pub fn think(&mut self, spaceship: &Spaceship, asteroids: &[Asteroid]) -> ShipControl {
+        self.decide(
+            spaceship.get_position(),
+            spaceship.get_direction(),
+            spaceship.get_velocity(),
+            asteroids,
+        )
+    }
+
+    /// Calcule les commandes à partir de l'état brut du vaisseau.
+    ///
+    /// L'évitement est prioritaire sur la visée : en cas de menace, le vaisseau s'oriente
+    /// à l'opposé du point d'impact et accélère. Il tire malgré tout si sa cible est alignée.
+    fn decide(
+        &mut self,
+        position: Vec2,
+        rotation: f32,
+        velocity: Vec2,
+        asteroids: &[Asteroid],
+    ) -> ShipControl {
+        self.cooldown = self.cooldown.saturating_sub(1);
+        let mut control = ShipControl::default();
+
+        let threat = Self::find_threat(position, velocity, asteroids);
+        if let Some(away) = threat {
+            let diff = angle_diff(rotation, away.y.atan2(away.x));
+            control.rotate = steer(diff);
+            if diff.abs() < PI / 2.0 {
+                control.thrust = 1.0;
+            }
+        }
+
+        if let Some((aim, distance)) = Self::find_target(position, asteroids) {
+            let diff = angle_diff(rotation, aim.y.atan2(aim.x));
+            if threat.is_none() {
+                control.rotate = steer(diff);
+            }
+            if diff.abs() < Self::AIM_TOLERANCE && distance < Self::FIRE_RANGE && self.cooldown == 0
+            {
+                control.fire = true;
+                self.cooldown = Self::FIRE_COOLDOWN;
+            }
+        }
+
+        control
+    }
+
+    /// Recherche l'astéroïde le plus proche et calcule le point de visée anticipé.
+    ///
+    /// # Retour
+    /// Le point de visée relatif au vaisseau et la distance actuelle de la cible,
+    /// ou `None` si aucune cible ne peut être interceptée.
+    fn find_target(position: Vec2, asteroids: &[Asteroid]) -> Option<(Vec2, f32)> {
+        let target = asteroids.iter().min_by(|a, b| {
+            let da = a.get_position().distance_squared(position);
+            let db = b.get_position().distance_squared(position);
+            da.total_cmp(&db)
+        })?;
+
+        let relative = target.get_position() - position;
+        let time = intercept_time(relative, target.get_speed(), Missile::SPEED)?;
+        Some((relative + target.get_speed() * time, relative.length()))
+    }
+
+    /// Recherche l'astéroïde qui menace le plus tôt d'entrer en collision avec le vaisseau.
+    ///
+    /// # Retour
+    /// La direction de fuite (du point d'impact vers le vaisseau), ou `None` si aucun
+    /// astéroïde ne menace le vaisseau dans l'horizon d'anticipation.
+    fn find_threat(position: Vec2, velocity: Vec2, asteroids: &[Asteroid]) -> Option<Vec2> {
+        let mut threat: Option<(f32, Vec2)> = None;
+
+        for asteroid in asteroids {
+            let relative = asteroid.get_position() - position;
+            let relative_velocity = asteroid.get_speed() - velocity;
+            let speed_squared = relative_velocity.length_squared();
+            let time = if speed_squared > f32::EPSILON {
+                (-relative.dot(relative_velocity) / speed_squared).clamp(0.0, Self::DODGE_HORIZON)
+            } else {
+                0.0
+            };
+
+            let closest = relative + relative_velocity * time;
+            let radius = asteroid.get_size() / 2.0 + Spaceship::SIZE / 2.0 + Self::DODGE_MARGIN;
+            if closest.length() < radius && threat.is_none_or(|(earliest, _)| time < earliest) {
+                let away = if closest.length() > f32::EPSILON {
+                    -closest
+                } else {
+                    relative_velocity.perp()
+                };
+                threat = Some((time, away));
+            }
+        }
+
+        threat.map(|(_, away)| away)
+    }
+}
+
+/// Calcule le temps nécessaire à un projectile pour intercepter une cible mobile.
+///
+/// # Paramètres
+/// - `relative`: Position de la cible relative au point de tir.
+/// - `velocity`: Vitesse de la cible.
+/// - `speed`: Vitesse du projectile.
+///
+/// # Retour
+/// Le plus petit temps d'interception positif, ou `None` si la cible ne peut pas être atteinte.
+fn intercept_time(relative: Vec2, velocity: Vec2, speed: f32) -> Option<f32> {
+    let a = velocity.dot(velocity) - speed * speed;
+    let b = 2.0 * relative.dot(velocity);
+    let c = relative.dot(relative);
+
+    if a.abs() < f32::EPSILON {
+        let time = -c / b;
+        return (b.abs() > f32::EPSILON && time > 0.0).then_some(time);
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    let t1 = (-b - root) / (2.0 * a);
+    let t2 = (-b + root) / (2.0 * a);
+    [t1.min(t2), t1.max(t2)].into_iter().find(|&t| t > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intercept_time() {
+        let time = intercept_time(vec2(100.0, 0.0), vec2(0.0, 0.0), 5.0).unwrap();
+        assert!((time - 20.0).abs() < 1e-4);
+
+        assert!(intercept_time(vec2(100.0, 0.0), vec2(10.0, 0.0), 5.0).is_none());
+    }
+
+    #[test]
+    fn test_pilot_turns_towards_target() {
+        let mut pilot = Pilot::new();
+        let asteroids = vec![Asteroid::with_size(Asteroid::SMALL, vec2(0.0, 300.0))];
+
+        let control = pilot.decide(vec2(0.0, 0.0), 0.0, vec2(0.0, 0.0), &asteroids);
+
+        assert!(control.rotate > 0.0);
+        assert!(!control.fire);
+    }
+
+    #[test]
+    fn test_find_threat() {
+        let close = vec![Asteroid::with_size(Asteroid::LARGE, vec2(20.0, 0.0))];
+        assert!(Pilot::find_threat(vec2(0.0, 0.0), vec2(0.0, 0.0), &close).is_some());
+
+        let far = vec![Asteroid::with_size(Asteroid::LARGE, vec2(1000.0, 0.0))];
+        assert!(Pilot::find_threat(vec2(0.0, 0.0), vec2(0.0, 0.0), &far).is_none());
+    }
+}
@@ -0,0 +1,618 @@
+//! Module des moteurs de rendu pour un jeu Asteroids.
+//!
+//! Les frames d'une partie se dessinent à travers le trait `Renderer`, qui offre quelques
+//! primitives (cercles, rectangles, lignes, triangles, textures et texte) dans les
+//! coordonnées de la caméra en cours : le monde pour les objets, le fond étoilé et les
+//! particules, la vue pour l'interface, la minicarte et les notifications. Seuls les
+//! menus, la console et l'affichage de débogage dessinent directement avec Macroquad.
+//! Trois moteurs implémentent le trait :
+//! - `Macroquad` dessine dans la fenêtre du jeu ;
+//! - `Ascii` dessine des caractères colorés dans une grille, affichée dans un terminal
+//!   par le mode `--tui` ;
+//! - `Svg` écrit une image vectorielle de la frame, utilisable dans un rapport de bogue
+//!   ou comme référence d'un test, sans carte graphique.
+//!
+//! Les moteurs sans carte graphique ne dessinent pas les textures : les objets texturés
+//! se dessinent alors avec des formes simples (voir `Renderer::has_textures`).
+
+use macroquad::prelude::*;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Primitives de dessin utilisées par les objets du jeu.
+pub trait Renderer {
+    /// Dessine un disque plein.
+    fn circle(&mut self, center: Vec2, radius: f32, color: Color);
+
+    /// Dessine le contour d'un cercle.
+    fn circle_lines(&mut self, center: Vec2, radius: f32, thickness: f32, color: Color);
+
+    /// Dessine un segment.
+    fn line(&mut self, from: Vec2, to: Vec2, thickness: f32, color: Color);
+
+    /// Dessine un triangle plein ; le premier sommet est la pointe des vaisseaux.
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color);
+
+    /// Dessine un rectangle plein à partir de son coin supérieur gauche.
+    fn rectangle(&mut self, position: Vec2, size: Vec2, color: Color);
+
+    /// Dessine du texte, `position` étant le début de la ligne de base.
+    fn text(&mut self, text: &str, position: Vec2, size: f32, color: Color);
+
+    /// Mesure un texte, pour le placer avant de le dessiner.
+    ///
+    /// Sans police chargée, la mesure est estimée à partir du nombre de caractères.
+    fn measure_text(&self, text: &str, size: f32) -> TextDimensions {
+        TextDimensions {
+            width: text.chars().count() as f32 * size * 0.5,
+            height: size,
+            offset_y: size * 0.8,
+        }
+    }
+
+    /// Dessine le contour d'un rectangle.
+    fn rectangle_lines(&mut self, position: Vec2, size: Vec2, thickness: f32, color: Color) {
+        let corners = [
+            position,
+            position + vec2(size.x, 0.0),
+            position + size,
+            position + vec2(0.0, size.y),
+        ];
+        for (index, &corner) in corners.iter().enumerate() {
+            self.line(corner, corners[(index + 1) % 4], thickness, color);
+        }
+    }
+
+    /// Indique si le moteur dessine les textures.
+    fn has_textures(&self) -> bool {
+        false
+    }
+
+    /// Dessine une texture centrée et tournée, teintée d'une couleur.
+    ///
+    /// Ignoré par les moteurs qui ne dessinent pas les textures.
+    fn texture(
+        &mut self,
+        _texture: &Texture2D,
+        _center: Vec2,
+        _size: Vec2,
+        _rotation: f32,
+        _color: Color,
+    ) {
+    }
+}
+
+/// Moteur de rendu qui dessine dans la fenêtre avec Macroquad.
+pub struct Macroquad;
+
+impl Renderer for Macroquad {
+    fn circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        draw_circle(center.x, center.y, radius, color);
+    }
+
+    fn circle_lines(&mut self, center: Vec2, radius: f32, thickness: f32, color: Color) {
+        draw_circle_lines(center.x, center.y, radius, thickness, color);
+    }
+
+    fn line(&mut self, from: Vec2, to: Vec2, thickness: f32, color: Color) {
+        draw_line(from.x, from.y, to.x, to.y, thickness, color);
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        draw_triangle(a, b, c, color);
+    }
+
+    fn rectangle(&mut self, position: Vec2, size: Vec2, color: Color) {
+        draw_rectangle(position.x, position.y, size.x, size.y, color);
+    }
+
+    fn text(&mut self, text: &str, position: Vec2, size: f32, color: Color) {
+        draw_text(text, position.x, position.y, size, color);
+    }
+
+    fn measure_text(&self, text: &str, size: f32) -> TextDimensions {
+        measure_text(text, None, size as u16, 1.0)
+    }
+
+    fn rectangle_lines(&mut self, position: Vec2, size: Vec2, thickness: f32, color: Color) {
+        draw_rectangle_lines(position.x, position.y, size.x, size.y, thickness, color);
+    }
+
+    fn has_textures(&self) -> bool {
+        true
+    }
+
+    fn texture(
+        &mut self,
+        texture: &Texture2D,
+        center: Vec2,
+        size: Vec2,
+        rotation: f32,
+        color: Color,
+    ) {
+        draw_texture_ex(
+            texture,
+            center.x - size.x / 2.0,
+            center.y - size.y / 2.0,
+            color,
+            DrawTextureParams {
+                dest_size: Some(size),
+                rotation,
+                pivot: Some(center),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Moteur de rendu qui dessine des caractères colorés dans une grille.
+///
+/// Le monde est étiré sur toute la grille ; les objets plus petits qu'une case sont
+/// dessinés dans la case de leur centre.
+pub struct Ascii {
+    /// Nombre de colonnes de la grille.
+    columns: usize,
+    /// Nombre de lignes de la grille.
+    rows: usize,
+    /// Dimensions du monde dessiné.
+    world: Vec2,
+    /// Caractère et couleur de chaque case, ligne par ligne.
+    cells: Vec<Option<(char, Color)>>,
+}
+
+impl Ascii {
+    /// Crée une grille vide.
+    ///
+    /// # Paramètres
+    /// - `columns`: Le nombre de colonnes.
+    /// - `rows`: Le nombre de lignes.
+    /// - `world`: Les dimensions du monde dessiné dans la grille.
+    pub fn new(columns: usize, rows: usize, world: Vec2) -> Self {
+        Self {
+            columns,
+            rows,
+            world,
+            cells: vec![None; columns * rows],
+        }
+    }
+
+    /// Retourne les dimensions d'une case, en unités du monde.
+    fn cell_size(&self) -> Vec2 {
+        self.world / vec2(self.columns as f32, self.rows as f32)
+    }
+
+    /// Retourne la case qui contient un point du monde.
+    fn cell_at(&self, point: Vec2) -> (i32, i32) {
+        let cell = (point / self.cell_size()).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+
+    /// Retourne le centre d'une case, en coordonnées du monde.
+    fn cell_center(&self, column: i32, row: i32) -> Vec2 {
+        (vec2(column as f32, row as f32) + 0.5) * self.cell_size()
+    }
+
+    /// Écrit un caractère dans une case, s'il est dans la grille.
+    fn plot(&mut self, column: i32, row: i32, character: char, color: Color) {
+        if (0..self.columns as i32).contains(&column) && (0..self.rows as i32).contains(&row) {
+            self.cells[row as usize * self.columns + column as usize] = Some((character, color));
+        }
+    }
+
+    /// Remplit les cases dont le centre vérifie une condition, dans un rectangle du monde.
+    ///
+    /// # Retour
+    /// `true` si au moins une case a été remplie.
+    fn fill(
+        &mut self,
+        min: Vec2,
+        max: Vec2,
+        character: char,
+        color: Color,
+        inside: impl Fn(Vec2) -> bool,
+    ) -> bool {
+        let (left, top) = self.cell_at(min);
+        let (right, bottom) = self.cell_at(max);
+        let mut filled = false;
+        for row in top..=bottom {
+            for column in left..=right {
+                if inside(self.cell_center(column, row)) {
+                    self.plot(column, row, character, color);
+                    filled = true;
+                }
+            }
+        }
+        filled
+    }
+
+    /// Retourne la grille sous forme de texte, sans couleurs.
+    #[cfg(test)]
+    pub fn to_text(&self) -> String {
+        self.cells
+            .chunks(self.columns)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.map_or(' ', |(c, _)| c))
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Retourne la grille avec les séquences de couleur ANSI d'un terminal, une ligne
+    /// par rangée de la grille.
+    pub fn to_ansi(&self) -> String {
+        let mut output = String::new();
+        for (index, row) in self.cells.chunks(self.columns).enumerate() {
+            if index > 0 {
+                output.push_str("\r\n");
+            }
+            let mut current = None;
+            for cell in row {
+                let (character, color) = cell.unwrap_or((' ', BLACK));
+                let [r, g, b, _]: [u8; 4] = color.into();
+                if current != Some([r, g, b]) {
+                    let _ = write!(output, "\x1b[38;2;{r};{g};{b}m");
+                    current = Some([r, g, b]);
+                }
+                output.push(character);
+            }
+            output.push_str("\x1b[0m");
+        }
+        output
+    }
+}
+
+/// Retourne le caractère d'une surface pleine : plus léger si elle est translucide.
+fn fill_character(color: Color) -> char {
+    if color.a < 0.5 {
+        '.'
+    } else {
+        '#'
+    }
+}
+
+/// Retourne le caractère d'un segment selon sa direction à l'écran.
+fn line_character(direction: Vec2) -> char {
+    let (x, y) = (direction.x.abs(), direction.y.abs());
+    if x > 2.0 * y {
+        '-'
+    } else if y > 2.0 * x {
+        '|'
+    } else if direction.x * direction.y > 0.0 {
+        '\\'
+    } else {
+        '/'
+    }
+}
+
+/// Retourne la flèche qui indique une direction à l'écran.
+fn arrow_character(direction: Vec2) -> char {
+    if direction.x.abs() > direction.y.abs() {
+        if direction.x > 0.0 {
+            '>'
+        } else {
+            '<'
+        }
+    } else if direction.y > 0.0 {
+        'v'
+    } else {
+        '^'
+    }
+}
+
+impl Renderer for Ascii {
+    fn circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        let filled = self.fill(
+            center - radius,
+            center + radius,
+            fill_character(color),
+            color,
+            |point| point.distance(center) <= radius,
+        );
+        if !filled {
+            let (column, row) = self.cell_at(center);
+            self.plot(column, row, 'o', color);
+        }
+    }
+
+    fn circle_lines(&mut self, center: Vec2, radius: f32, thickness: f32, color: Color) {
+        let margin = (thickness / 2.0).max(self.cell_size().max_element() / 2.0);
+        self.fill(
+            center - radius - margin,
+            center + radius + margin,
+            '+',
+            color,
+            |point| (point.distance(center) - radius).abs() <= margin,
+        );
+    }
+
+    fn line(&mut self, from: Vec2, to: Vec2, _thickness: f32, color: Color) {
+        let cell = self.cell_size();
+        let character = line_character((to - from) / cell);
+        let steps = ((to - from) / cell).abs().max_element().ceil() as usize * 2;
+        for step in 0..=steps {
+            let point = from.lerp(to, step as f32 / steps.max(1) as f32);
+            let (column, row) = self.cell_at(point);
+            self.plot(column, row, character, color);
+        }
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        let edge = |p: Vec2, q: Vec2, point: Vec2| (q - p).perp_dot(point - p);
+        let filled = self.fill(
+            a.min(b).min(c),
+            a.max(b).max(c),
+            fill_character(color),
+            color,
+            |point| {
+                let sides = [edge(a, b, point), edge(b, c, point), edge(c, a, point)];
+                sides.iter().all(|&side| side >= 0.0) || sides.iter().all(|&side| side <= 0.0)
+            },
+        );
+        let center = (a + b + c) / 3.0;
+        if !filled || color.a >= 0.5 {
+            // La pointe indique l'orientation du vaisseau
+            let (column, row) = self.cell_at(center);
+            let direction = (a - center) / self.cell_size();
+            self.plot(column, row, arrow_character(direction), color);
+        }
+    }
+
+    fn rectangle(&mut self, position: Vec2, size: Vec2, color: Color) {
+        self.fill(
+            position,
+            position + size,
+            fill_character(color),
+            color,
+            |point| point.cmpge(position).all() && point.cmple(position + size).all(),
+        );
+    }
+
+    fn rectangle_lines(&mut self, position: Vec2, size: Vec2, thickness: f32, color: Color) {
+        // Les bords droit et bas sont ramenés dans la dernière case, au lieu de la suivante
+        let inset = self.cell_size() / 2.0;
+        let corners = [
+            position,
+            position + vec2(size.x - inset.x, 0.0),
+            position + size - inset,
+            position + vec2(0.0, size.y - inset.y),
+        ];
+        for (index, &corner) in corners.iter().enumerate() {
+            self.line(corner, corners[(index + 1) % 4], thickness, color);
+        }
+    }
+
+    fn text(&mut self, text: &str, position: Vec2, _size: f32, color: Color) {
+        let (column, row) = self.cell_at(position);
+        for (offset, character) in text.chars().enumerate() {
+            self.plot(column + offset as i32, row, character, color);
+        }
+    }
+}
+
+/// Moteur de rendu qui écrit une image SVG de la frame.
+pub struct Svg {
+    /// Dimensions de l'image, en unités du monde.
+    size: Vec2,
+    /// Éléments SVG dessinés, dans l'ordre.
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /// Répertoire des images enregistrées pendant les parties.
+    pub const DIRECTORY: &'static str = "snapshots";
+
+    /// Crée une image vide sur fond noir.
+    ///
+    /// # Paramètres
+    /// - `size`: Les dimensions de l'image, en unités du monde.
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            elements: Vec::new(),
+        }
+    }
+
+    /// Retourne le document SVG.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (self.size.x, self.size.y);
+        let mut output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\n\
+             <rect width=\"{width}\" height=\"{height}\" fill=\"#000000\"/>\n"
+        );
+        for element in &self.elements {
+            output.push_str(element);
+            output.push('\n');
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+
+    /// Enregistre le document SVG, en créant son répertoire si besoin.
+    ///
+    /// # Retour
+    /// Un message décrivant l'erreur d'écriture, le cas échéant.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let error = |error: std::io::Error| format!("{}: {error}", path.display());
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(error)?;
+        }
+        fs::write(path, self.to_svg()).map_err(error)
+    }
+
+    /// Retourne un nouveau chemin d'image dans `snapshots/`, nommé d'après l'heure.
+    pub fn snapshot_path() -> PathBuf {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis());
+        Path::new(Self::DIRECTORY).join(format!("frame-{millis}.svg"))
+    }
+}
+
+/// Retourne les attributs SVG d'une couleur de remplissage ou de contour.
+///
+/// # Paramètres
+/// - `attribute`: `fill` ou `stroke`.
+/// - `color`: La couleur, dont l'opacité n'est écrite que si elle est partielle.
+fn paint(attribute: &str, color: Color) -> String {
+    let [r, g, b, _]: [u8; 4] = color.into();
+    let mut paint = format!("{attribute}=\"#{r:02x}{g:02x}{b:02x}\"");
+    if color.a < 1.0 {
+        let _ = write!(paint, " {attribute}-opacity=\"{:.2}\"", color.a);
+    }
+    paint
+}
+
+/// Échappe les caractères réservés du XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Renderer for Svg {
+    fn circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        self.elements.push(format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{radius:.1}\" {}/>",
+            center.x,
+            center.y,
+            paint("fill", color)
+        ));
+    }
+
+    fn circle_lines(&mut self, center: Vec2, radius: f32, thickness: f32, color: Color) {
+        self.elements.push(format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{radius:.1}\" fill=\"none\" {} stroke-width=\"{thickness:.1}\"/>",
+            center.x,
+            center.y,
+            paint("stroke", color)
+        ));
+    }
+
+    fn line(&mut self, from: Vec2, to: Vec2, thickness: f32, color: Color) {
+        self.elements.push(format!(
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {} stroke-width=\"{thickness:.1}\"/>",
+            from.x,
+            from.y,
+            to.x,
+            to.y,
+            paint("stroke", color)
+        ));
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.elements.push(format!(
+            "<polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" {}/>",
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            c.x,
+            c.y,
+            paint("fill", color)
+        ));
+    }
+
+    fn rectangle(&mut self, position: Vec2, size: Vec2, color: Color) {
+        self.elements.push(format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" {}/>",
+            position.x,
+            position.y,
+            size.x,
+            size.y,
+            paint("fill", color)
+        ));
+    }
+
+    fn text(&mut self, text: &str, position: Vec2, size: f32, color: Color) {
+        self.elements.push(format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{size:.1}\" font-family=\"monospace\" {}>{}</text>",
+            position.x,
+            position.y,
+            paint("fill", color),
+            escape(text)
+        ));
+    }
+
+    fn rectangle_lines(&mut self, position: Vec2, size: Vec2, thickness: f32, color: Color) {
+        self.elements.push(format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" {} stroke-width=\"{thickness:.1}\"/>",
+            position.x,
+            position.y,
+            size.x,
+            size.y,
+            paint("stroke", color)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::missile::Missile;
+    use crate::spaceship::Spaceship;
+
+    #[test]
+    fn test_svg_frame() {
+        let mut svg = Svg::new(vec2(100.0, 50.0));
+        svg.circle(vec2(20.0, 25.0), 10.0, YELLOW);
+        svg.rectangle_lines(Vec2::ZERO, vec2(100.0, 50.0), 2.0, DARKGRAY);
+        svg.text(
+            "A < B",
+            vec2(5.0, 45.0),
+            12.0,
+            Color::new(1.0, 1.0, 1.0, 0.5),
+        );
+
+        assert_eq!(
+            svg.to_svg(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"50\" viewBox=\"0 0 100 50\">\n\
+             <rect width=\"100\" height=\"50\" fill=\"#000000\"/>\n\
+             <circle cx=\"20.0\" cy=\"25.0\" r=\"10.0\" fill=\"#fcf900\"/>\n\
+             <rect x=\"0.0\" y=\"0.0\" width=\"100.0\" height=\"50.0\" fill=\"none\" stroke=\"#4f4f4f\" stroke-width=\"2.0\"/>\n\
+             <text x=\"5.0\" y=\"45.0\" font-size=\"12.0\" font-family=\"monospace\" fill=\"#ffffff\" fill-opacity=\"0.50\">A &lt; B</text>\n\
+             </svg>\n"
+        );
+    }
+
+    #[test]
+    fn test_ascii_shapes() {
+        let mut ascii = Ascii::new(10, 5, vec2(100.0, 50.0));
+        ascii.circle(vec2(25.0, 25.0), 12.0, YELLOW);
+        ascii.circle(vec2(82.0, 12.0), 1.0, RED);
+        ascii.line(vec2(60.0, 45.0), vec2(100.0, 45.0), 1.0, WHITE);
+        ascii.rectangle(vec2(90.0, 30.0), vec2(10.0, 10.0), WHITE);
+
+        assert_eq!(
+            ascii.to_text(),
+            "          \n  #     o \n ###      \n  #      #\n      ----"
+        );
+        assert!(ascii
+            .to_ansi()
+            .starts_with("\x1b[38;2;0;0;0m          \x1b[0m\r\n"));
+    }
+
+    #[test]
+    fn test_objects_draw_without_gpu() {
+        let mut spaceship = Spaceship::without_texture(vec2(400.0, 300.0));
+        spaceship.rotate(1.0);
+        let missile = Missile::new(vec2(100.0, 100.0), 0.0);
+        let mut ascii = Ascii::new(40, 20, vec2(800.0, 600.0));
+        let mut svg = Svg::new(vec2(800.0, 600.0));
+        for renderer in [&mut ascii as &mut dyn Renderer, &mut svg] {
+            spaceship.draw(renderer);
+            missile.draw(renderer);
+        }
+
+        let text = ascii.to_text();
+        assert_eq!(text.matches('o').count(), 1);
+        assert_eq!(text.matches(['>', '<', '^', 'v']).count(), 1);
+        assert!(svg.to_svg().contains("<polygon"));
+        assert!(svg.to_svg().contains("<circle cx=\"100.0\" cy=\"100.0\""));
+    }
+}
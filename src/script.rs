@@ -0,0 +1,523 @@
+//! Module des modes de jeu scriptés pour un jeu Asteroids.
+//!
+//! Un mode est un script [Rhai](https://rhai.rs). Les modes du répertoire `modes/` sont
+//! intégrés au programme ; ceux d'un répertoire choisi par le joueur s'y ajoutent, et
+//! remplacent un mode intégré du même nom. Le script réagit aux événements de la partie en définissant des fonctions, toutes
+//! facultatives :
+//!
+//! ```text
+//! fn on_start() { this.kills = 0; spawn_asteroids("large", 4); }
+//! fn on_asteroid_destroyed(tier, x, y) { this.kills += 1; }
+//! fn on_wave_cleared(wave) { next_wave(); spawn_asteroids("large", 4 + wave); }
+//! fn on_timer(name) { message("Time is up"); win(); }
+//! ```
+//!
+//! Il agit sur la partie avec `spawn_asteroids(tier, count)`,
+//! `spawn_asteroid(tier, x, y)`, `set_difficulty(name, value)`, `message(text)` (ou
+//! `print`), `timer(name, seconds)`, `next_wave()` et `win()`, et la consulte avec
+//! `score()`, `wave()`, `asteroids()` et `elapsed()`. Les tailles sont `"small"`,
+//! `"medium"` ou `"large"`. Les valeurs conservées d'un événement à l'autre sont rangées
+//! dans `this`.
+//!
+//! Le script s'exécute dans un bac à sable : il n'a accès ni aux fichiers ni aux autres
+//! modules, et chaque appel est limité en nombre d'instructions, en profondeur et en
+//! taille des valeurs. Un script qui dépasse ces limites est interrompu avec une erreur.
+
+use crate::events::GameEvent;
+use crate::level::Tier;
+use macroquad::prelude::*;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope, AST};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Action demandée par un script, appliquée ensuite à la partie.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Fait apparaître des astéroïdes.
+    Spawn {
+        /// Taille des astéroïdes.
+        tier: Tier,
+        /// Nombre d'astéroïdes.
+        count: usize,
+        /// Position d'apparition, ou `None` pour un point au hasard près d'un bord.
+        at: Option<Vec2>,
+    },
+    /// Modifie une valeur du profil de difficulté, désignée par son nom.
+    SetDifficulty(String, f32),
+    /// Affiche un message.
+    Message(String),
+    /// Passe à la vague suivante.
+    NextWave,
+    /// Termine la partie sur une victoire.
+    Win,
+}
+
+/// État de la partie que le script peut consulter.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Snapshot {
+    /// Score de la partie.
+    pub score: u32,
+    /// Numéro de la vague en cours.
+    pub wave: u32,
+    /// Nombre d'astéroïdes présents.
+    pub asteroids: usize,
+    /// Temps écoulé depuis le début de la partie, en secondes.
+    pub elapsed: f32,
+}
+
+/// Mode de jeu proposé dans le menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeScript {
+    /// Nom du mode, tiré du nom de son fichier.
+    pub name: String,
+    /// Fichier du script, ou `None` pour un mode intégré au programme.
+    pub path: Option<PathBuf>,
+}
+
+impl ModeScript {
+    /// Charge et compile le script du mode.
+    ///
+    /// # Retour
+    /// Le script, ou un message d'erreur si le fichier est illisible ou invalide.
+    pub fn load(&self) -> Result<Script, String> {
+        if let Some(path) = &self.path {
+            return Script::load(path);
+        }
+        let (_, source) = Script::SHIPPED
+            .iter()
+            .find(|(name, _)| *name == self.name)
+            .ok_or_else(|| format!("{}: no such shipped mode", self.name))?;
+        Script::compile(source).map_err(|error| format!("{}: {error}", self.name))
+    }
+}
+
+/// Script d'un mode de jeu, compilé et prêt à réagir aux événements.
+pub struct Script {
+    /// Moteur Rhai limité au bac à sable.
+    engine: Engine,
+    /// Script compilé.
+    ast: AST,
+    /// Noms des fonctions définies par le script.
+    handlers: HashSet<String>,
+    /// Actions demandées par le script depuis la dernière mise à jour.
+    commands: Rc<RefCell<Vec<Command>>>,
+    /// État de la partie consulté par le script.
+    snapshot: Rc<RefCell<Snapshot>>,
+    /// Minuteries en cours : nom et temps restant, en secondes.
+    timers: Rc<RefCell<Vec<(String, f32)>>>,
+    /// Valeurs du script conservées d'un appel à l'autre, accessibles par `this`.
+    state: Dynamic,
+    /// Indique si le champ était déjà dégagé à la mise à jour précédente.
+    cleared: bool,
+}
+
+impl Script {
+    /// Modes de jeu intégrés au programme : (nom, code source).
+    pub const SHIPPED: &'static [(&'static str, &'static str)] = &[
+        ("endless", include_str!("../modes/endless.rhai")),
+        ("survival", include_str!("../modes/survival.rhai")),
+    ];
+    /// Extension des fichiers de script.
+    pub const EXTENSION: &'static str = "rhai";
+    /// Nombre maximal d'instructions exécutées par appel de fonction du script.
+    pub const MAX_OPERATIONS: u64 = 100_000;
+    /// Nombre maximal d'actions demandées par mise à jour.
+    pub const MAX_COMMANDS: usize = 256;
+    /// Nombre maximal d'astéroïdes créés par un appel à `spawn_asteroids`.
+    pub const MAX_SPAWN: i64 = 50;
+
+    /// Charge et compile le script d'un fichier.
+    ///
+    /// # Paramètres
+    /// - `path`: Le chemin du fichier.
+    ///
+    /// # Retour
+    /// Le script, ou un message d'erreur si le fichier est illisible ou invalide.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source =
+            fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+        Self::compile(&source).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    /// Compile le code source d'un script.
+    ///
+    /// # Paramètres
+    /// - `source`: Le code source.
+    ///
+    /// # Retour
+    /// Le script, ou un message d'erreur si le code est invalide.
+    pub fn compile(source: &str) -> Result<Self, String> {
+        let commands = Rc::new(RefCell::new(Vec::new()));
+        let snapshot = Rc::new(RefCell::new(Snapshot::default()));
+        let timers = Rc::new(RefCell::new(Vec::new()));
+        let engine = sandbox(&commands, &snapshot, &timers);
+        let ast = engine.compile(source).map_err(|error| error.to_string())?;
+        let handlers = ast
+            .iter_functions()
+            .map(|function| function.name.to_string())
+            .collect();
+        Ok(Self {
+            engine,
+            ast,
+            handlers,
+            commands,
+            snapshot,
+            timers,
+            state: Map::new().into(),
+            cleared: false,
+        })
+    }
+
+    /// Liste les modes de jeu intégrés et ceux d'un répertoire, triés par nom.
+    ///
+    /// # Paramètres
+    /// - `directory`: Le répertoire optionnel des scripts du joueur, dont les modes
+    ///   remplacent les modes intégrés du même nom.
+    pub fn list(directory: Option<&Path>) -> Vec<ModeScript> {
+        let mut modes: Vec<ModeScript> = Self::SHIPPED
+            .iter()
+            .map(|(name, _)| ModeScript {
+                name: name.to_string(),
+                path: None,
+            })
+            .collect();
+        let files = directory
+            .map(fs::read_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == Self::EXTENSION));
+        for path in files {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            modes.retain(|mode| mode.name != name);
+            modes.push(ModeScript {
+                name: name.to_string(),
+                path: Some(path),
+            });
+        }
+        modes.sort_by(|a, b| a.name.cmp(&b.name));
+        modes
+    }
+
+    /// Appelle `on_start` au lancement de la partie.
+    ///
+    /// # Retour
+    /// Les actions demandées par le script, ou l'erreur qui l'a interrompu.
+    pub fn start(&mut self) -> Result<Vec<Command>, String> {
+        self.call("on_start", ())?;
+        Ok(self.commands.take())
+    }
+
+    /// Transmet au script les événements d'une frame.
+    ///
+    /// Appelle `on_asteroid_destroyed` pour chaque astéroïde détruit, `on_timer` pour
+    /// chaque minuterie écoulée et `on_wave_cleared` lorsque le champ vient d'être dégagé.
+    ///
+    /// # Paramètres
+    /// - `delta`: La durée écoulée, en secondes.
+    /// - `snapshot`: L'état de la partie ; son temps écoulé est tenu par le script.
+    /// - `events`: Les événements de la frame.
+    ///
+    /// # Retour
+    /// Les actions demandées par le script, ou l'erreur qui l'a interrompu.
+    pub fn update(
+        &mut self,
+        delta: f32,
+        snapshot: Snapshot,
+        events: &[GameEvent],
+    ) -> Result<Vec<Command>, String> {
+        let elapsed = self.snapshot.borrow().elapsed + delta;
+        *self.snapshot.borrow_mut() = Snapshot {
+            elapsed,
+            ..snapshot
+        };
+
+        for event in events {
+            if let GameEvent::AsteroidDestroyed { size, position } = *event {
+                let args = (
+                    Tier::from_size(size).name(),
+                    position.x as f64,
+                    position.y as f64,
+                );
+                self.call("on_asteroid_destroyed", args)?;
+            }
+        }
+
+        let mut expired = Vec::new();
+        self.timers.borrow_mut().retain_mut(|(name, remaining)| {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                expired.push(name.clone());
+            }
+            *remaining > 0.0
+        });
+        for name in expired {
+            self.call("on_timer", (name,))?;
+        }
+
+        let cleared = snapshot.asteroids == 0;
+        if cleared && !self.cleared {
+            self.call("on_wave_cleared", (snapshot.wave as i64,))?;
+        }
+        self.cleared = cleared;
+        Ok(self.commands.take())
+    }
+
+    /// Appelle une fonction du script si elle est définie, avec `this` lié à son état.
+    fn call(&mut self, name: &str, args: impl FuncArgs) -> Result<(), String> {
+        if !self.handlers.contains(name) {
+            return Ok(());
+        }
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        self.engine
+            .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), &self.ast, name, args)
+            .map(|_| ())
+            .map_err(|error| format!("{name}: {error}"))
+    }
+}
+
+/// Crée un moteur Rhai limité au bac à sable, avec les fonctions offertes aux scripts.
+///
+/// # Paramètres
+/// - `commands`: La liste complétée par les actions du script.
+/// - `snapshot`: L'état de la partie consulté par le script.
+/// - `timers`: Les minuteries du script.
+fn sandbox(
+    commands: &Rc<RefCell<Vec<Command>>>,
+    snapshot: &Rc<RefCell<Snapshot>>,
+    timers: &Rc<RefCell<Vec<(String, f32)>>>,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_modules(0);
+    engine.set_max_operations(Script::MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(1024);
+    engine.set_max_array_size(1024);
+    engine.set_max_map_size(256);
+    engine.disable_symbol("eval");
+
+    let push = {
+        let commands = commands.clone();
+        move |command: Command| -> Result<(), Box<EvalAltResult>> {
+            let mut commands = commands.borrow_mut();
+            if commands.len() >= Script::MAX_COMMANDS {
+                return Err("too many commands in one update".into());
+            }
+            commands.push(command);
+            Ok(())
+        }
+    };
+    let on_print = push.clone();
+    engine.on_print(move |text| {
+        let _ = on_print(Command::Message(text.to_string()));
+    });
+
+    let spawn_asteroids = push.clone();
+    engine.register_fn("spawn_asteroids", move |tier: &str, count: i64| {
+        if !(0..=Script::MAX_SPAWN).contains(&count) {
+            let max = Script::MAX_SPAWN;
+            return Err(format!("spawn count must be between 0 and {max}").into());
+        }
+        spawn_asteroids(Command::Spawn {
+            tier: parse_tier(tier)?,
+            count: count as usize,
+            at: None,
+        })
+    });
+    let spawn_asteroid = push.clone();
+    engine.register_fn(
+        "spawn_asteroid",
+        move |tier: &str, x: Dynamic, y: Dynamic| {
+            spawn_asteroid(Command::Spawn {
+                tier: parse_tier(tier)?,
+                count: 1,
+                at: Some(vec2(number(x)?, number(y)?)),
+            })
+        },
+    );
+    let set_difficulty = push.clone();
+    engine.register_fn("set_difficulty", move |name: &str, value: Dynamic| {
+        set_difficulty(Command::SetDifficulty(name.to_string(), number(value)?))
+    });
+    let message = push.clone();
+    engine.register_fn("message", move |text: &str| {
+        message(Command::Message(text.to_string()))
+    });
+    let next_wave = push.clone();
+    engine.register_fn("next_wave", move || next_wave(Command::NextWave));
+    engine.register_fn("win", move || push(Command::Win));
+
+    let timers = timers.clone();
+    engine.register_fn(
+        "timer",
+        move |name: &str, seconds: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            let seconds = number(seconds)?;
+            let mut timers = timers.borrow_mut();
+            timers.retain(|(timer, _)| timer != name);
+            if timers.len() >= Script::MAX_COMMANDS {
+                return Err("too many timers".into());
+            }
+            timers.push((name.to_string(), seconds));
+            Ok(())
+        },
+    );
+
+    let state = snapshot.clone();
+    engine.register_fn("score", move || state.borrow().score as i64);
+    let state = snapshot.clone();
+    engine.register_fn("wave", move || state.borrow().wave as i64);
+    let state = snapshot.clone();
+    engine.register_fn("asteroids", move || state.borrow().asteroids as i64);
+    let state = snapshot.clone();
+    engine.register_fn("elapsed", move || state.borrow().elapsed as f64);
+    engine
+}
+
+/// Convertit un nombre entier ou décimal d'un script.
+fn number(value: Dynamic) -> Result<f32, Box<EvalAltResult>> {
+    match value.as_float() {
+        Ok(value) => Ok(value as f32),
+        Err(_) => value
+            .as_int()
+            .map(|value| value as f32)
+            .map_err(|kind| format!("expected a number, got {kind}").into()),
+    }
+}
+
+/// Convertit le nom d'une taille d'astéroïde (`small`, `medium` ou `large`).
+fn parse_tier(name: &str) -> Result<Tier, Box<EvalAltResult>> {
+    Tier::ALL
+        .into_iter()
+        .find(|tier| tier.name() == name)
+        .ok_or_else(|| format!("unknown asteroid size: {name}").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asteroid::Asteroid;
+
+    fn snapshot(asteroids: usize) -> Snapshot {
+        Snapshot {
+            score: 100,
+            wave: 1,
+            asteroids,
+            elapsed: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_handlers_emit_commands() {
+        let mut script = Script::compile(
+            r#"
+            fn on_start() { this.kills = 0; spawn_asteroids("large", 3); }
+            fn on_asteroid_destroyed(tier, x, y) {
+                this.kills += 1;
+                if tier == "small" { spawn_asteroid("medium", x, 10); }
+            }
+            fn on_wave_cleared(wave) {
+                next_wave();
+                set_difficulty("speed", 1.5);
+                message(`Wave ${wave + 1}, ${this.kills} kills, score ${score()}`);
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            script.start(),
+            Ok(vec![Command::Spawn {
+                tier: Tier::Large,
+                count: 3,
+                at: None
+            }])
+        );
+        let events = [GameEvent::AsteroidDestroyed {
+            size: Asteroid::SMALL,
+            position: vec2(40.0, 50.0),
+        }];
+        assert_eq!(
+            script.update(0.1, snapshot(0), &events),
+            Ok(vec![
+                Command::Spawn {
+                    tier: Tier::Medium,
+                    count: 1,
+                    at: Some(vec2(40.0, 10.0))
+                },
+                Command::NextWave,
+                Command::SetDifficulty("speed".to_string(), 1.5),
+                Command::Message("Wave 2, 1 kills, score 100".to_string()),
+            ])
+        );
+
+        // Le champ reste dégagé : la vague n'est signalée qu'une fois
+        assert_eq!(script.update(0.1, snapshot(0), &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_timers_fire_once() {
+        let mut script = Script::compile(
+            r#"
+            fn on_start() { timer("end", 1); }
+            fn on_timer(name) { print(`${name} at ${elapsed()}`); win(); }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(script.start(), Ok(vec![]));
+        assert_eq!(script.update(0.5, snapshot(1), &[]), Ok(vec![]));
+        assert_eq!(
+            script.update(0.5, snapshot(1), &[]),
+            Ok(vec![
+                Command::Message("end at 1.0".to_string()),
+                Command::Win
+            ])
+        );
+        assert_eq!(script.update(0.5, snapshot(1), &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_sandbox_limits() {
+        let mut endless = Script::compile("fn on_start() { loop {} }").unwrap();
+        assert!(endless.start().unwrap_err().contains("operations"));
+
+        let mut flood =
+            Script::compile(r#"fn on_start() { spawn_asteroids("large", 1000); }"#).unwrap();
+        assert!(flood.start().is_err());
+
+        let mut import = Script::compile(r#"fn on_start() { import "std" as std; }"#).unwrap();
+        assert!(import.start().is_err());
+
+        assert!(Script::compile(r#"fn on_start() { eval("win()"); }"#).is_err());
+        assert!(Script::compile("fn on_start( {").is_err());
+    }
+
+    #[test]
+    fn test_shipped_modes_compile() {
+        let shipped = Script::list(None);
+        assert_eq!(shipped.len(), Script::SHIPPED.len());
+
+        // Les fichiers du répertoire remplacent les modes intégrés du même nom
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("modes");
+        let files = Script::list(Some(&directory));
+        assert_eq!(files.len(), shipped.len());
+
+        for (shipped, file) in shipped.iter().zip(&files) {
+            assert_eq!(shipped.name, file.name);
+            assert_eq!(shipped.path, None);
+            assert!(file.path.is_some());
+            assert!(shipped.load().is_ok(), "{}", shipped.name);
+            assert!(file.load().is_ok(), "{}", file.name);
+        }
+    }
+}
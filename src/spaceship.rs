@@ -4,6 +4,8 @@
 //! incluant sa position, sa vitesse, sa rotation, et sa gestion des bordures d'écran.
 //! Le vaisseau peut être contrôlé pour avancer, tourner à gauche ou à droite.
 
+use crate::render::Renderer;
+use crate::view;
 use macroquad::prelude::*;
 
 /// Représente un vaisseau spatial dans le jeu.
@@ -14,10 +16,14 @@ pub struct Spaceship {
     velocity: Vec2,
     /// Rotation actuelle du vaisseau spatial (en radians).
     rotation: f32,
-    /// Indique si le vaisseau spatial est en poussée (propulsion).
-    push: bool,
-    /// Texture utilisée pour dessiner le vaisseau spatial.
-    texture: Texture2D,
+    /// Intensité de la poussée (propulsion), de `0.0` (aucune) à `1.0` (pleine poussée).
+    thrust: f32,
+    /// Nombre de frames restantes avant de pouvoir tirer à nouveau.
+    reload: u32,
+    /// Nombre de frames entre deux tirs de missile ; `0` laisse tirer à chaque appui.
+    cooldown: u32,
+    /// Texture utilisée pour dessiner le vaisseau spatial, absente pour une simulation sans fenêtre.
+    texture: Option<Texture2D>,
 }
 
 impl Spaceship {
@@ -28,7 +34,7 @@ impl Spaceship {
     /// Intensité de la poussée appliquée au vaisseau spatial.
     pub const SPEED: f32 = 0.05;
 
-    /// Crée un nouveau vaisseau spatial centré sur l'écran avec une texture donnée.
+    /// Crée un nouveau vaisseau spatial centré sur la vue avec une texture donnée.
     ///
     /// # Paramètres
     /// - `texture`: La texture à utiliser pour dessiner le vaisseau spatial.
@@ -37,33 +43,90 @@ impl Spaceship {
     /// Une nouvelle instance de `Spaceship`.
     pub fn new(texture: Texture2D) -> Self {
         Self {
-            position: vec2(screen_width() / 2.0, screen_height() / 2.0),
+            position: vec2(view::WIDTH / 2.0, view::HEIGHT / 2.0),
             velocity: vec2(0.0, 0.0),
             rotation: 0.0,
-            push: false,
-            texture,
+            thrust: 0.0,
+            reload: 0,
+            cooldown: 0,
+            texture: Some(texture),
+        }
+    }
+
+    /// Crée un vaisseau spatial sans texture à une position donnée.
+    ///
+    /// Utilisé par les simulations qui tournent sans fenêtre ; le vaisseau est alors
+    /// dessiné sous la forme d'un triangle.
+    ///
+    /// # Paramètres
+    /// - `position`: La position initiale du vaisseau spatial.
+    pub fn without_texture(position: Vec2) -> Self {
+        Self {
+            position,
+            velocity: vec2(0.0, 0.0),
+            rotation: 0.0,
+            thrust: 0.0,
+            reload: 0,
+            cooldown: 0,
+            texture: None,
         }
     }
 
     /// Met à jour la position et la vitesse du vaisseau spatial.
     ///
-    /// - Si la propulsion est activée (`push` est `true`), une nouvelle vitesse
-    ///   est ajoutée dans la direction de la rotation actuelle.
-    /// - La position est ajustée pour rester dans les limites de l'écran.
+    /// - Si la propulsion est activée (`thrust` est positif), une nouvelle vitesse
+    ///   proportionnelle à la poussée est ajoutée dans la direction de la rotation actuelle.
+    /// - La position est ajustée pour rester dans les limites de la zone de jeu.
     /// - Si aucune propulsion n'est appliquée, la vitesse diminue progressivement
     ///   pour simuler l'inertie.
-    pub fn update(&mut self) {
-        if self.push {
-            let velocity_vector = Vec2::from_angle(self.rotation) * Self::SPEED;
+    ///
+    /// # Paramètres
+    /// - `bounds`: Les dimensions de la zone de jeu.
+    pub fn update(&mut self, bounds: Vec2) {
+        if self.thrust > 0.0 {
+            let velocity_vector = Vec2::from_angle(self.rotation) * Self::SPEED * self.thrust;
             self.velocity += velocity_vector;
         }
 
         self.position += self.velocity;
-        self.position = Self::bound_pos(self.position);
+        self.position = Self::bound_pos(self.position, bounds);
 
         // Si aucun nouveau vecteur de vitesse n'est ajouté,
         // la vitesse est multipliée par 0,99 pour simuler un ralentissement progressif.
         self.velocity *= 0.99;
+        self.reload = self.reload.saturating_sub(1);
+    }
+
+    /// Tente de tirer un missile en respectant le temps de recharge.
+    ///
+    /// # Retour
+    /// `true` si le tir est autorisé, auquel cas la recharge recommence, sinon `false`.
+    pub fn try_fire(&mut self) -> bool {
+        if self.reload > 0 {
+            return false;
+        }
+        self.reload = self.cooldown;
+        true
+    }
+
+    /// Retourne la fraction du temps de recharge restante, de `0.0` (prêt) à `1.0`.
+    ///
+    /// # Retour
+    /// `None` si le vaisseau n'a pas de temps de recharge.
+    pub fn get_reload(&self) -> Option<f32> {
+        (self.cooldown > 0).then(|| self.reload as f32 / self.cooldown as f32)
+    }
+
+    /// Modifie le nombre de frames entre deux tirs de missile.
+    ///
+    /// Un vaisseau qui vient d'être créé n'a pas de temps de recharge : c'est le profil de
+    /// difficulté de la partie qui le fixe.
+    ///
+    /// # Paramètres
+    /// - `cooldown`: Le temps de recharge, `0` pour tirer à chaque appui.
+    pub fn set_fire_cooldown(&mut self, cooldown: u32) {
+        self.cooldown = cooldown;
+        self.reload = self.reload.min(cooldown);
     }
 
     /// Retourne la position actuelle du vaisseau spatial.
@@ -76,50 +139,86 @@ impl Spaceship {
         self.rotation
     }
 
-    /// Active ou désactive la propulsion du vaisseau spatial.
+    /// Place le vaisseau spatial à une position donnée.
     ///
     /// # Paramètres
-    /// - `push`: `true` pour activer la propulsion, `false` pour la désactiver.
-    pub fn set_push(&mut self, push: bool) {
-        self.push = push;
+    /// - `position`: La nouvelle position du vaisseau spatial.
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
     }
 
-    /// Tourne le vaisseau spatial vers la gauche.
-    pub fn rotate_left(&mut self) {
-        self.rotation -= Self::ROTATION_SPEED;
+    /// Retourne le vecteur de vitesse actuel du vaisseau spatial.
+    pub fn get_velocity(&self) -> Vec2 {
+        self.velocity
     }
 
-    /// Tourne le vaisseau spatial vers la droite.
-    pub fn rotate_right(&mut self) {
-        self.rotation += Self::ROTATION_SPEED;
+    /// Règle l'intensité de la propulsion du vaisseau spatial.
+    ///
+    /// # Paramètres
+    /// - `thrust`: De `0.0` pour désactiver la propulsion à `1.0` pour une poussée maximale.
+    pub fn set_thrust(&mut self, thrust: f32) {
+        self.thrust = thrust.clamp(0.0, 1.0);
+    }
+
+    /// Tourne le vaisseau spatial d'une fraction de sa vitesse de rotation.
+    ///
+    /// # Paramètres
+    /// - `amount`: Fraction de `ROTATION_SPEED`, de `-1.0` (gauche) à `1.0` (droite).
+    pub fn rotate(&mut self, amount: f32) {
+        self.rotation += Self::ROTATION_SPEED * amount.clamp(-1.0, 1.0);
     }
 
     /// Dessine le vaisseau spatial à sa position actuelle avec la rotation et la texture définies.
-    pub fn draw(&self) {
-        draw_texture_ex(
-            &self.texture,
-            self.position.x - Self::SIZE / 2.0,
-            self.position.y - Self::SIZE / 2.0,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(vec2(Self::SIZE, Self::SIZE)),
-                rotation: self.rotation,
-                pivot: Some(self.position),
-                ..Default::default()
-            },
-        );
-    }
-
-    /// Gère le retour de la position dans les limites de l'écran.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        self.draw_tinted(renderer, WHITE);
+    }
+
+    /// Dessine le vaisseau spatial teinté d'une couleur, par exemple translucide.
+    ///
+    /// Sans texture, ou avec un moteur qui ne dessine pas les textures, le vaisseau est un
+    /// triangle pointant dans sa direction.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    /// - `color`: La couleur multipliée à la texture, ou la couleur du triangle.
+    pub fn draw_tinted(&self, renderer: &mut dyn Renderer, color: Color) {
+        match &self.texture {
+            Some(texture) if renderer.has_textures() => {
+                renderer.texture(
+                    texture,
+                    self.position,
+                    Vec2::splat(Self::SIZE),
+                    self.rotation,
+                    color,
+                );
+            }
+            _ => {
+                let nose = Vec2::from_angle(self.rotation) * Self::SIZE / 2.0;
+                let wing = nose.perp() * 0.6;
+                renderer.triangle(
+                    self.position + nose,
+                    self.position - nose + wing,
+                    self.position - nose - wing,
+                    color,
+                );
+            }
+        }
+    }
+
+    /// Gère le retour de la position dans les limites de la zone de jeu.
     ///
     /// # Paramètres
     /// - `pos`: La position à ajuster.
+    /// - `bounds`: Les dimensions de la zone de jeu.
     ///
     /// # Retour
-    /// La position ajustée pour qu'elle reste dans les limites de l'écran.
-    fn bound_pos(mut pos: Vec2) -> Vec2 {
-        pos.x = Self::bound_to(pos.x, screen_width());
-        pos.y = Self::bound_to(pos.y, screen_height());
+    /// La position ajustée pour qu'elle reste dans les limites de la zone de jeu.
+    fn bound_pos(mut pos: Vec2, bounds: Vec2) -> Vec2 {
+        pos.x = Self::bound_to(pos.x, bounds.x);
+        pos.y = Self::bound_to(pos.y, bounds.y);
         pos
     }
 
@@ -153,21 +252,60 @@ mod tests {
 
         let mut spaceship = Spaceship::new(texture);
 
-        let expected_position = vec2(screen_width() / 2.0, screen_height() / 2.0);
+        let expected_position = vec2(view::WIDTH / 2.0, view::HEIGHT / 2.0);
 
         assert_eq!(spaceship.position, expected_position);
         assert_eq!(spaceship.velocity, vec2(0.0, 0.0));
         assert_eq!(spaceship.rotation, 0.0);
-        assert_eq!(spaceship.push, false);
+        assert_eq!(spaceship.thrust, 0.0);
 
-        spaceship.set_push(true);
-        spaceship.update();
+        let bounds = vec2(view::WIDTH, view::HEIGHT);
+        spaceship.set_thrust(1.0);
+        spaceship.update(bounds);
 
-        assert!(spaceship.position != vec2(screen_width() / 2.0, screen_height() / 2.0));
+        assert!(spaceship.position != vec2(view::WIDTH / 2.0, view::HEIGHT / 2.0));
 
         let previous_velocity = spaceship.velocity;
-        spaceship.set_push(false);
-        spaceship.update();
+        spaceship.set_thrust(0.0);
+        spaceship.update(bounds);
         assert!(spaceship.velocity.length() < previous_velocity.length());
     }
+
+    #[test]
+    fn test_spaceship_partial_thrust() {
+        let bounds = vec2(800.0, 600.0);
+        let mut full = Spaceship::without_texture(bounds / 2.0);
+        let mut half = Spaceship::without_texture(bounds / 2.0);
+
+        full.set_thrust(1.0);
+        half.set_thrust(0.5);
+        full.update(bounds);
+        half.update(bounds);
+
+        assert!((half.velocity.length() * 2.0 - full.velocity.length()).abs() < 1e-5);
+
+        half.set_thrust(3.0);
+        assert_eq!(half.thrust, 1.0);
+    }
+
+    #[test]
+    fn test_fire_cooldown() {
+        let bounds = vec2(800.0, 600.0);
+        let mut spaceship = Spaceship::without_texture(bounds / 2.0);
+
+        // Sans temps de recharge, chaque appui tire
+        assert!(spaceship.try_fire());
+        assert!(spaceship.try_fire());
+        assert_eq!(spaceship.get_reload(), None);
+
+        spaceship.set_fire_cooldown(4);
+        assert!(spaceship.try_fire());
+        assert!(!spaceship.try_fire());
+        assert_eq!(spaceship.get_reload(), Some(1.0));
+        for _ in 0..4 {
+            spaceship.update(bounds);
+        }
+        assert_eq!(spaceship.get_reload(), Some(0.0));
+        assert!(spaceship.try_fire());
+    }
 }
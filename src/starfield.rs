@@ -0,0 +1,152 @@
+//! Module du fond étoilé pour un jeu Asteroids.
+//!
+//! Ce module génère un champ d'étoiles à plusieurs couches de parallaxe. Chaque couche
+//! est une tuile d'étoiles répétée pour couvrir la fenêtre, quelle que soit sa taille,
+//! et se déplace à l'opposé du vaisseau, d'autant plus vite qu'elle est proche.
+//! La génération utilise une graine fixe : le ciel est identique d'une partie à l'autre.
+
+use crate::render::Renderer;
+use crate::view;
+use ::rand::{rngs::SmallRng, Rng, SeedableRng};
+use macroquad::prelude::*;
+
+/// Étoile d'une couche, positionnée dans la tuile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Star {
+    /// Position dans la tuile.
+    position: Vec2,
+    /// Rayon de l'étoile.
+    size: f32,
+    /// Luminosité, de `0.0` à `1.0`.
+    brightness: f32,
+}
+
+/// Couche de parallaxe.
+#[derive(Debug, Clone, PartialEq)]
+struct Layer {
+    /// Étoiles de la tuile.
+    stars: Vec<Star>,
+    /// Fraction de la vitesse du vaisseau appliquée au défilement de la couche.
+    parallax: f32,
+    /// Décalage actuel de la couche, ramené dans la tuile.
+    offset: Vec2,
+}
+
+/// Fond étoilé à plusieurs couches de parallaxe.
+pub struct Starfield {
+    /// Couches, de la plus lointaine à la plus proche.
+    layers: Vec<Layer>,
+    /// Image de fond optionnelle, dessinée derrière les étoiles.
+    backdrop: Option<Texture2D>,
+}
+
+impl Starfield {
+    /// Graine de génération des étoiles.
+    pub const SEED: u64 = 0x5EED;
+    /// Taille de la tuile d'étoiles répétée sur la fenêtre, en pixels.
+    pub const TILE: f32 = 512.0;
+    /// Parallaxe et nombre d'étoiles de chaque couche, de la plus lointaine à la plus proche.
+    pub const LAYERS: [(f32, usize); 3] = [(0.1, 120), (0.3, 60), (0.6, 25)];
+
+    /// Génère le fond étoilé.
+    ///
+    /// # Paramètres
+    /// - `backdrop`: Image dessinée derrière les étoiles, étirée sur toute la fenêtre.
+    pub fn new(backdrop: Option<Texture2D>) -> Self {
+        let mut rng = SmallRng::seed_from_u64(Self::SEED);
+        let layers = Self::LAYERS
+            .iter()
+            .enumerate()
+            .map(|(depth, &(parallax, count))| Layer {
+                stars: (0..count)
+                    .map(|_| Star {
+                        position: vec2(
+                            rng.gen_range(0.0..Self::TILE),
+                            rng.gen_range(0.0..Self::TILE),
+                        ),
+                        size: rng.gen_range(0.5..1.0) * (depth + 1) as f32,
+                        brightness: rng.gen_range(0.3..0.7) + depth as f32 * 0.15,
+                    })
+                    .collect(),
+                parallax,
+                offset: Vec2::ZERO,
+            })
+            .collect();
+
+        Self { layers, backdrop }
+    }
+
+    /// Fait défiler les couches à l'opposé du déplacement du vaisseau.
+    ///
+    /// # Paramètres
+    /// - `velocity`: La vitesse du vaisseau pendant la frame.
+    pub fn update(&mut self, velocity: Vec2) {
+        for layer in &mut self.layers {
+            let offset = layer.offset - velocity * layer.parallax;
+            layer.offset = vec2(
+                offset.x.rem_euclid(Self::TILE),
+                offset.y.rem_euclid(Self::TILE),
+            );
+        }
+    }
+
+    /// Dessine l'image de fond puis les couches d'étoiles sur toute la fenêtre.
+    ///
+    /// À appeler avec la caméra de l'interface.
+    ///
+    /// # Paramètres
+    /// - `renderer`: Le moteur de rendu.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        let area = vec2(view::WIDTH, view::HEIGHT);
+        if let Some(backdrop) = &self.backdrop {
+            renderer.texture(backdrop, area / 2.0, area, 0.0, WHITE);
+        }
+
+        for layer in &self.layers {
+            let mut tile_y = layer.offset.y - Self::TILE;
+            while tile_y < view::HEIGHT {
+                let mut tile_x = layer.offset.x - Self::TILE;
+                while tile_x < view::WIDTH {
+                    for star in &layer.stars {
+                        renderer.circle(
+                            vec2(tile_x, tile_y) + star.position,
+                            star.size,
+                            Color::new(1.0, 1.0, 1.0, star.brightness.min(1.0)),
+                        );
+                    }
+                    tile_x += Self::TILE;
+                }
+                tile_y += Self::TILE;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starfield_is_seeded() {
+        let first = Starfield::new(None);
+        let second = Starfield::new(None);
+
+        assert_eq!(first.layers, second.layers);
+        assert_eq!(first.layers.len(), Starfield::LAYERS.len());
+    }
+
+    #[test]
+    fn test_near_layers_scroll_faster() {
+        let mut starfield = Starfield::new(None);
+        starfield.update(vec2(-10.0, 0.0));
+
+        assert!(starfield.layers[0].offset.x < starfield.layers[2].offset.x);
+        assert!((starfield.layers[2].offset.x - 6.0).abs() < 1e-5);
+
+        starfield.update(vec2(10.0 * Starfield::TILE, 0.0));
+        assert!(starfield
+            .layers
+            .iter()
+            .all(|layer| layer.offset.x >= 0.0 && layer.offset.x < Starfield::TILE));
+    }
+}
@@ -0,0 +1,372 @@
+//! Module des statistiques de partie pour un jeu Asteroids.
+//!
+//! Pendant une partie, `Stats` accumule les tirs, les astéroïdes touchés par taille, les
+//! collisions et les dégâts subis, la distance parcourue, le temps passé à accélérer et
+//! la durée de jeu. À la fin de la partie, ces valeurs sont résumées à l'écran et
+//! ajoutées à l'historique `stats.csv`, une ligne par partie :
+//!
+//! ```text
+//! timestamp,mode,outcome,score,duration,shots,hits,accuracy,small,medium,large,collisions,damage,distance,thrust_time
+//! 1760000000,hard,won,2450,183.2,140,61,0.4357,30,20,11,2,3,9120.5,64.3
+//! ```
+//!
+//! L'historique peut être exporté en CSV ou en JSON avec `--export-stats <fichier>`.
+
+use crate::events::GameEvent;
+use crate::level::Tier;
+use crate::locale::tr;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Statistiques accumulées pendant une partie.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    /// Score de la partie.
+    pub score: u32,
+    /// Nombre de missiles tirés.
+    pub shots: u32,
+    /// Nombre d'astéroïdes touchés par un missile, par taille.
+    pub destroyed: [u32; 3],
+    /// Nombre de collisions subies par le vaisseau.
+    pub collisions: u32,
+    /// Points de bouclier perdus.
+    pub damage: f32,
+    /// Distance parcourue par le vaisseau.
+    pub distance: f32,
+    /// Temps passé à accélérer, en secondes.
+    pub thrust_time: f32,
+    /// Durée de jeu, en secondes.
+    pub duration: f32,
+}
+
+impl Stats {
+    /// Accumule les événements d'une frame.
+    ///
+    /// # Paramètres
+    /// - `events`: Les événements de la frame.
+    /// - `delta`: La durée de la frame, en secondes.
+    pub fn record(&mut self, events: &[GameEvent], delta: f32) {
+        self.duration += delta;
+        let mut thrusting = false;
+        for event in events {
+            match *event {
+                GameEvent::AsteroidDestroyed { size, .. } => {
+                    self.destroyed[Tier::from_size(size) as usize] += 1;
+                }
+                GameEvent::SpaceshipHit { .. } => self.collisions += 1,
+                GameEvent::MissileFired { .. } => self.shots += 1,
+                GameEvent::Thrust { .. } => thrusting = true,
+                GameEvent::GameOver => {}
+            }
+        }
+        if thrusting {
+            self.thrust_time += delta;
+        }
+    }
+
+    /// Retourne le nombre d'astéroïdes touchés par un missile.
+    pub fn get_hits(&self) -> u32 {
+        self.destroyed.iter().sum()
+    }
+
+    /// Retourne la part des missiles qui ont touché un astéroïde, de `0.0` à `1.0`.
+    pub fn get_accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            self.get_hits() as f32 / self.shots as f32
+        }
+    }
+}
+
+/// Ligne de l'historique des parties.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Date de la fin de la partie, en secondes depuis le 1er janvier 1970.
+    pub timestamp: u64,
+    /// Mode de jeu : profil de difficulté, `custom`, `level` ou `mode:<script>`.
+    pub mode: String,
+    /// Issue de la partie : `won`, `lost`, `restart` ou `quit`.
+    pub outcome: String,
+    /// Score de la partie.
+    pub score: u32,
+    /// Durée de jeu, en secondes.
+    pub duration: f32,
+    /// Nombre de missiles tirés.
+    pub shots: u32,
+    /// Nombre d'astéroïdes touchés.
+    pub hits: u32,
+    /// Part des missiles qui ont touché, de `0.0` à `1.0`.
+    pub accuracy: f32,
+    /// Petits astéroïdes touchés.
+    pub small: u32,
+    /// Astéroïdes moyens touchés.
+    pub medium: u32,
+    /// Grands astéroïdes touchés.
+    pub large: u32,
+    /// Collisions subies par le vaisseau.
+    pub collisions: u32,
+    /// Points de bouclier perdus.
+    pub damage: f32,
+    /// Distance parcourue par le vaisseau.
+    pub distance: f32,
+    /// Temps passé à accélérer, en secondes.
+    pub thrust_time: f32,
+}
+
+impl Entry {
+    /// Crée la ligne d'une partie qui vient de se terminer.
+    ///
+    /// # Paramètres
+    /// - `stats`: Les statistiques de la partie.
+    /// - `mode`: Le mode de jeu.
+    /// - `outcome`: L'issue de la partie.
+    pub fn new(stats: &Stats, mode: String, outcome: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let [small, medium, large] = stats.destroyed;
+        Self {
+            timestamp,
+            mode,
+            outcome: outcome.to_string(),
+            score: stats.score,
+            duration: stats.duration,
+            shots: stats.shots,
+            hits: stats.get_hits(),
+            accuracy: stats.get_accuracy(),
+            small,
+            medium,
+            large,
+            collisions: stats.collisions,
+            damage: stats.damage,
+            distance: stats.distance,
+            thrust_time: stats.thrust_time,
+        }
+    }
+
+    /// Retourne les lignes du résumé de fin de partie : libellé traduit et valeur.
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (tr("game.score"), self.score.to_string()),
+            (tr("stats.duration"), format!("{:.0} s", self.duration)),
+            (tr("stats.shots"), self.shots.to_string()),
+            (tr("stats.hits"), self.hits.to_string()),
+            (
+                tr("stats.accuracy"),
+                format!("{:.0} %", self.accuracy * 100.0),
+            ),
+            (
+                tr("stats.destroyed"),
+                format!("{} / {} / {}", self.small, self.medium, self.large),
+            ),
+            (tr("stats.collisions"), self.collisions.to_string()),
+            (tr("stats.damage"), format!("{:.1}", self.damage)),
+            (tr("stats.distance"), format!("{:.0}", self.distance)),
+            (
+                tr("stats.thrust_time"),
+                format!("{:.0} s", self.thrust_time),
+            ),
+        ]
+    }
+}
+
+/// Historique des parties, enregistré au format CSV.
+pub struct History {
+    /// Parties, de la plus ancienne à la plus récente.
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Chemin du fichier de l'historique.
+    pub const FILE: &'static str = "stats.csv";
+
+    /// Charge l'historique d'un fichier CSV.
+    ///
+    /// # Paramètres
+    /// - `path`: Le chemin du fichier.
+    ///
+    /// # Retour
+    /// L'historique, vide si le fichier n'existe pas, ou une erreur si une ligne est
+    /// invalide.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|error| format!("{}: {error}", path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self {
+                entries: Vec::new(),
+            }),
+            Err(error) => Err(format!("{}: {error}", path.display())),
+        }
+    }
+
+    /// Lit l'historique depuis le contenu d'un fichier CSV, avec sa ligne d'en-tête.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let entries = csv::Reader::from_reader(text.as_bytes())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map_err(|error| error.to_string())?;
+        Ok(Self { entries })
+    }
+
+    /// Ajoute une partie à la fin d'un fichier CSV, créé avec son en-tête s'il n'existe
+    /// pas.
+    ///
+    /// # Paramètres
+    /// - `entry`: La partie à ajouter.
+    /// - `path`: Le chemin du fichier.
+    pub fn append(entry: &Entry, path: &Path) -> Result<(), String> {
+        let error = |error: &dyn std::fmt::Display| format!("{}: {error}", path.display());
+        let is_new = fs::metadata(path).map_or(true, |metadata| metadata.len() == 0);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| error(&e))?;
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(is_new)
+            .from_writer(file);
+        writer.serialize(entry).map_err(|e| error(&e))?;
+        writer.flush().map_err(|e| error(&e))
+    }
+
+    /// Retourne les parties de l'historique.
+    pub fn get_entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Écrit l'historique au format CSV, avec sa ligne d'en-tête.
+    pub fn to_csv(&self) -> Result<String, String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for entry in &self.entries {
+            writer.serialize(entry).map_err(|error| error.to_string())?;
+        }
+        let bytes = writer.into_inner().map_err(|error| error.to_string())?;
+        String::from_utf8(bytes).map_err(|error| error.to_string())
+    }
+
+    /// Écrit l'historique au format JSON, sous la forme d'un tableau d'objets.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.entries).map_err(|error| error.to_string())
+    }
+
+    /// Exporte l'historique dans un fichier, au format JSON si son extension est
+    /// `.json`, sinon au format CSV.
+    ///
+    /// # Paramètres
+    /// - `path`: Le chemin du fichier exporté.
+    pub fn export(&self, path: &Path) -> Result<(), String> {
+        let text = if path.extension().is_some_and(|ext| ext == "json") {
+            self.to_json()?
+        } else {
+            self.to_csv()?
+        };
+        fs::write(path, text).map_err(|error| format!("{}: {error}", path.display()))
+    }
+}
+
+/// Exporte l'historique des parties, depuis la ligne de commande.
+///
+/// # Paramètres
+/// - `target`: Le fichier exporté, en CSV ou en JSON selon son extension.
+pub fn export_stdio(target: Option<&str>) {
+    let Some(target) = target else {
+        eprintln!("Usage : --export-stats <fichier.csv|fichier.json>");
+        return;
+    };
+    let result = History::load(Path::new(History::FILE))
+        .and_then(|history| history.export(Path::new(target)).map(|()| history));
+    match result {
+        Ok(history) => println!(
+            "{} partie(s) exportée(s) vers {target}",
+            history.get_entries().len()
+        ),
+        Err(error) => eprintln!("Impossible d'exporter les statistiques : {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asteroid::Asteroid;
+    use macroquad::prelude::*;
+
+    fn sample() -> Entry {
+        let mut stats = Stats::default();
+        let frame = [
+            GameEvent::MissileFired {
+                position: Vec2::ZERO,
+            },
+            GameEvent::Thrust {
+                position: Vec2::ZERO,
+                direction: 0.0,
+                intensity: 1.0,
+            },
+        ];
+        for _ in 0..4 {
+            stats.record(&frame, 0.5);
+        }
+        stats.record(
+            &[
+                GameEvent::AsteroidDestroyed {
+                    size: Asteroid::LARGE,
+                    position: Vec2::ZERO,
+                },
+                GameEvent::SpaceshipHit {
+                    position: Vec2::ZERO,
+                },
+            ],
+            0.5,
+        );
+        stats.damage = 1.5;
+        stats.distance = 250.0;
+        stats.score = 120;
+        Entry::new(&stats, "hard".to_string(), "won")
+    }
+
+    #[test]
+    fn test_stats_from_events() {
+        let entry = sample();
+
+        assert_eq!(entry.shots, 4);
+        assert_eq!((entry.hits, entry.large), (1, 1));
+        assert_eq!(entry.accuracy, 0.25);
+        assert_eq!(entry.collisions, 1);
+        assert_eq!(entry.thrust_time, 2.0);
+        assert_eq!(entry.duration, 2.5);
+        assert_eq!(Stats::default().get_accuracy(), 0.0);
+    }
+
+    #[test]
+    fn test_csv_and_json_round_trip() {
+        let history = History {
+            entries: vec![sample(), sample()],
+        };
+
+        let csv = history.to_csv().unwrap();
+        assert!(csv.starts_with("timestamp,mode,outcome,score,duration,shots,hits,accuracy,"));
+        assert_eq!(
+            History::parse(&csv).unwrap().get_entries(),
+            history.get_entries()
+        );
+
+        let json: Vec<Entry> = serde_json::from_str(&history.to_json().unwrap()).unwrap();
+        assert_eq!(json, history.entries);
+        assert!(History::parse("timestamp,mode\nsoon,hard\n").is_err());
+    }
+
+    #[test]
+    fn test_append_writes_header_once() {
+        let path = std::env::temp_dir().join("asteroids-stats-test.csv");
+        let _ = fs::remove_file(&path);
+
+        History::append(&sample(), &path).unwrap();
+        History::append(&sample(), &path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(text.matches("timestamp").count(), 1);
+        assert_eq!(History::parse(&text).unwrap().get_entries().len(), 2);
+    }
+}
@@ -0,0 +1,368 @@
+//! Module du mode terminal pour un jeu Asteroids.
+//!
+//! Lancé avec `--tui` (et éventuellement `--seed <nombre>` et `--world
+//! <largeur>x<hauteur>`), le jeu s'exécute sans fenêtre : la même partie que dans la
+//! fenêtre, en difficulté moyenne, est simulée par `GameState` et dessinée par
+//! `draw_world` en caractères colorés, avec le moteur de rendu `Ascii` à la taille du
+//! terminal, et pilotée au clavier. La graine fixe le champ d'astéroïdes.
+//!
+//! Les terminaux ne signalent généralement que l'appui des touches, répété tant qu'elles
+//! restent enfoncées : une touche est donc considérée comme enfoncée pendant un court
+//! délai après son dernier appui. Les terminaux qui signalent le relâchement des touches
+//! l'utilisent directement.
+
+use crate::control::ShipControl;
+use crate::difficulty::Difficulty;
+use crate::events::GameEvent;
+use crate::hud::Hud;
+use crate::locale::{self, tr, Language};
+use crate::render::Ascii;
+use crate::spaceship::Spaceship;
+use crate::{apply_control, asteroid, draw_world, spawn_wave, GameState};
+use ::rand::rngs::SmallRng;
+use ::rand::SeedableRng;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use macroquad::math::Vec2;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Durée d'une frame, le jeu tournant à 60 images par seconde.
+const FRAME: Duration = Duration::from_micros(16_667);
+
+/// Lance une partie dans le terminal, jusqu'à ce que le joueur quitte.
+///
+/// # Paramètres
+/// - `seed`: La graine utilisée pour générer les astéroïdes.
+/// - `world`: Les dimensions du monde.
+pub fn run(seed: u64, world: Vec2) {
+    locale::set_language(Language::load());
+    let result = Terminal::open().and_then(|terminal| play(&terminal, seed, world));
+    if let Err(error) = result {
+        eprintln!("Erreur du terminal : {error}");
+    }
+}
+
+/// Terminal en mode brut sur l'écran alternatif, restauré à la fin de la partie.
+struct Terminal {
+    /// Indique si le terminal signale le relâchement des touches.
+    releases: bool,
+}
+
+impl Terminal {
+    /// Passe le terminal en mode brut, sur l'écran alternatif et sans curseur.
+    fn open() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        let releases = terminal::supports_keyboard_enhancement().unwrap_or(false);
+        if releases {
+            execute!(
+                io::stdout(),
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            )?;
+        }
+        Ok(Self { releases })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        if self.releases {
+            let _ = execute!(io::stdout(), PopKeyboardEnhancementFlags);
+        }
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Touches enfoncées, avec la frame de leur dernier appui.
+struct Keys {
+    /// Frame du dernier appui de chaque touche enfoncée.
+    held: HashMap<KeyCode, u32>,
+    /// Numéro de la frame en cours.
+    frame: u32,
+    /// Indique si le terminal signale le relâchement des touches.
+    releases: bool,
+}
+
+impl Keys {
+    /// Nombre de frames pendant lesquelles une touche reste enfoncée après son dernier
+    /// appui, plus long que le délai de répétition des terminaux.
+    const HOLD: u32 = 30;
+
+    /// Crée l'ensemble des touches, toutes relâchées.
+    ///
+    /// # Paramètres
+    /// - `releases`: Indique si le terminal signale le relâchement des touches.
+    fn new(releases: bool) -> Self {
+        Self {
+            held: HashMap::new(),
+            frame: 0,
+            releases,
+        }
+    }
+
+    /// Prend en compte l'appui, la répétition ou le relâchement d'une touche.
+    fn handle(&mut self, key: KeyEvent) {
+        match key.kind {
+            KeyEventKind::Press | KeyEventKind::Repeat => {
+                self.held.insert(key.code, self.frame);
+            }
+            KeyEventKind::Release => {
+                self.held.remove(&key.code);
+            }
+        }
+    }
+
+    /// Passe à la frame suivante.
+    fn tick(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Indique si l'une des touches est enfoncée.
+    fn is_held(&self, codes: &[KeyCode]) -> bool {
+        codes.iter().any(|code| {
+            self.held
+                .get(code)
+                .is_some_and(|&frame| self.releases || self.frame - frame < Self::HOLD)
+        })
+    }
+
+    /// Retourne les commandes du vaisseau : flèches ou WASD, et espace pour tirer.
+    fn control(&self) -> ShipControl {
+        let left = self.is_held(&[KeyCode::Left, KeyCode::Char('a')]);
+        let right = self.is_held(&[KeyCode::Right, KeyCode::Char('d')]);
+        ShipControl {
+            rotate: f32::from(u8::from(right)) - f32::from(u8::from(left)),
+            thrust: f32::from(u8::from(self.is_held(&[KeyCode::Up, KeyCode::Char('w')]))),
+            fire: self.is_held(&[KeyCode::Char(' ')]),
+            aim: None,
+        }
+    }
+}
+
+/// Indique si une touche demande de quitter : Q, Échap ou Ctrl+C.
+fn is_quit(key: &KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && (matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)))
+}
+
+/// Crée une partie en difficulté moyenne, avec un champ d'astéroïdes tiré de la graine.
+///
+/// # Paramètres
+/// - `seed`: La graine utilisée pour générer les astéroïdes.
+/// - `world`: Les dimensions du monde.
+fn new_game(seed: u64, world: Vec2) -> GameState {
+    let difficulty = Difficulty::MEDIUM;
+    let mut state = GameState::new(Spaceship::without_texture(world / 2.0), world, difficulty);
+    state.rng = SmallRng::seed_from_u64(seed);
+    asteroid::with_rng(&mut state.rng, || {
+        spawn_wave(
+            &mut state.asteroids,
+            difficulty.asteroids,
+            difficulty.speed,
+            world,
+        );
+    });
+    state
+}
+
+/// Fait avancer la partie d'une frame, comme la boucle de la fenêtre.
+///
+/// # Paramètres
+/// - `state`: L'état de la partie.
+/// - `hud`: L'interface, qui compte le score.
+/// - `control`: Les commandes du joueur.
+/// - `world`: Les dimensions du monde.
+fn step(state: &mut GameState, hud: &mut Hud, control: &ShipControl, world: Vec2) {
+    let mut events = Vec::new();
+    apply_control(
+        &mut state.spaceship,
+        &mut state.missiles,
+        control,
+        &mut events,
+    );
+    state.update_objects(world);
+    state.apply_collisions(&mut events, world);
+    if state.health < 0.0 {
+        events.push(GameEvent::GameOver);
+    }
+    hud.handle_events(&events);
+    hud.update(FRAME.as_secs_f32());
+}
+
+/// Déroule les parties, relancées avec R à la fin de chacune.
+fn play(terminal: &Terminal, seed: u64, world: Vec2) -> io::Result<()> {
+    let mut state = new_game(seed, world);
+    let mut hud = Hud::new(false, state.difficulty.score_multiplier);
+    let mut keys = Keys::new(terminal.releases);
+    let mut stdout = io::stdout();
+
+    loop {
+        let start = Instant::now();
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                if is_quit(&key) {
+                    return Ok(());
+                }
+                keys.handle(key);
+            }
+        }
+
+        step(&mut state, &mut hud, &keys.control(), world);
+        keys.tick();
+
+        let score = hud.get_score();
+        let outcome = if state.health < 0.0 {
+            Some(tr("game.over"))
+        } else if state.is_won(score) {
+            Some(tr("game.win"))
+        } else {
+            None
+        };
+        let status = match outcome {
+            Some(outcome) => format!(
+                "{outcome}  {} {score}  {}",
+                tr("game.score"),
+                tr("tui.again")
+            ),
+            None => format!(
+                "{} {score}  {} {:.0}  {} {}  {} {}  {}",
+                tr("game.score"),
+                tr("tui.shield"),
+                state.health.max(0.0),
+                tr("hud.wave"),
+                state.wave,
+                tr("hud.asteroids"),
+                state.asteroids.len(),
+                tr("tui.help")
+            ),
+        };
+        draw(&state, world, &status, &mut stdout)?;
+
+        if outcome.is_some() {
+            if !wait_for_restart()? {
+                return Ok(());
+            }
+            state = new_game(seed, world);
+            hud = Hud::new(false, state.difficulty.score_multiplier);
+            keys = Keys::new(terminal.releases);
+        }
+
+        thread::sleep(FRAME.saturating_sub(start.elapsed()));
+    }
+}
+
+/// Dessine le monde sur tout le terminal, avec une ligne d'état en bas.
+fn draw(state: &GameState, world: Vec2, status: &str, stdout: &mut impl Write) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let mut ascii = Ascii::new(
+        usize::from(columns),
+        usize::from(rows.saturating_sub(1)),
+        world,
+    );
+    draw_world(&mut ascii, state, None, world);
+    queue!(
+        stdout,
+        MoveTo(0, 0),
+        Print(ascii.to_ansi()),
+        MoveTo(0, rows.saturating_sub(1)),
+        Clear(ClearType::CurrentLine),
+        Print(status)
+    )?;
+    stdout.flush()
+}
+
+/// Attend que le joueur choisisse de rejouer (R) ou de quitter.
+///
+/// # Retour
+/// `true` si le joueur rejoue.
+fn wait_for_restart() -> io::Result<bool> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if is_quit(&key) {
+                return Ok(false);
+            }
+            if key.kind == KeyEventKind::Press && key.code == KeyCode::Char('r') {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Svg;
+    use macroquad::math::vec2;
+
+    fn key(code: KeyCode, kind: KeyEventKind) -> KeyEvent {
+        KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind)
+    }
+
+    #[test]
+    fn test_keys_stay_held_until_repeat_stops() {
+        let mut keys = Keys::new(false);
+        keys.handle(key(KeyCode::Up, KeyEventKind::Press));
+        keys.handle(key(KeyCode::Left, KeyEventKind::Press));
+        assert_eq!(keys.control().thrust, 1.0);
+        assert_eq!(keys.control().rotate, -1.0);
+
+        for _ in 0..Keys::HOLD {
+            keys.tick();
+        }
+        assert_eq!(keys.control(), ShipControl::default());
+    }
+
+    #[test]
+    fn test_seeded_game_renders_without_gpu() {
+        let world = vec2(1200.0, 900.0);
+        let render = |state: &GameState| {
+            let mut svg = Svg::new(world);
+            draw_world(&mut svg, state, None, world);
+            svg.to_svg()
+        };
+        let first = new_game(3, world);
+        assert_eq!(render(&first), render(&new_game(3, world)));
+        assert_eq!(
+            render(&first).matches("<circle").count(),
+            Difficulty::MEDIUM.asteroids
+        );
+
+        // Une frame de tir ajoute un missile, comme dans la fenêtre
+        let mut state = new_game(3, world);
+        let mut hud = Hud::new(false, 1.0);
+        let fire = ShipControl {
+            fire: true,
+            ..Default::default()
+        };
+        step(&mut state, &mut hud, &fire, world);
+        assert_eq!(state.missiles.len(), 1);
+    }
+
+    #[test]
+    fn test_keys_released_when_reported() {
+        let mut keys = Keys::new(true);
+        keys.handle(key(KeyCode::Char(' '), KeyEventKind::Press));
+        for _ in 0..Keys::HOLD {
+            keys.tick();
+        }
+        assert!(keys.control().fire);
+
+        keys.handle(key(KeyCode::Char(' '), KeyEventKind::Release));
+        assert!(!keys.control().fire);
+        assert!(is_quit(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL
+        )));
+    }
+}
@@ -0,0 +1,579 @@
+//! Module des menus pour un jeu Asteroids.
+//!
+//! Ce module fournit des éléments de menu (boutons, curseurs, cases à cocher, listes)
+//! décrits à chaque frame, dans l'ordre d'affichage :
+//!
+//! ```ignore
+//! let mut ui = menu.begin(MenuInput::read(&gamepads), Layout::centered(0.3));
+//! ui.title("Options");
+//! if ui.button("Play") { /* ... */ }
+//! ui.slider("Volume", &mut volume, 0.0..=1.0, 0.1);
+//! ui.end();
+//! ```
+//!
+//! Le `Menu` conserve l'élément qui a le focus d'une frame à l'autre. Le focus se
+//! déplace avec les flèches ou la croix directionnelle et suit la souris ; Entrée, le
+//! bouton A ou un clic valident, gauche et droite modifient la valeur des curseurs et des
+//! listes. Échap et le bouton B demandent le retour à l'écran précédent. Les éléments
+//! sont d'abord collectés puis dessinés, ce qui permet de tester les menus sans fenêtre.
+
+use crate::view;
+use gamepads::{Button, Gamepads};
+use macroquad::prelude::*;
+use std::ops::RangeInclusive;
+
+/// Entrées de navigation d'une frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MenuInput {
+    /// Le focus monte d'un élément.
+    pub up: bool,
+    /// Le focus descend d'un élément.
+    pub down: bool,
+    /// La valeur de l'élément qui a le focus diminue.
+    pub left: bool,
+    /// La valeur de l'élément qui a le focus augmente.
+    pub right: bool,
+    /// L'élément qui a le focus est validé.
+    pub activate: bool,
+    /// Le joueur demande le retour à l'écran précédent.
+    pub back: bool,
+    /// Position de la souris, en coordonnées logiques.
+    pub mouse: Vec2,
+    /// Le bouton gauche de la souris vient d'être enfoncé.
+    pub clicked: bool,
+    /// Le bouton gauche de la souris est maintenu.
+    pub held: bool,
+}
+
+impl MenuInput {
+    /// Lit le clavier, la croix directionnelle et les boutons A et B des manettes, et la
+    /// souris.
+    ///
+    /// # Paramètres
+    /// - `gamepads`: Manettes, déjà interrogées pour cette frame.
+    pub fn read(gamepads: &Gamepads) -> Self {
+        let pad = |button: Button| {
+            gamepads
+                .all()
+                .any(|gamepad| gamepad.is_just_pressed(button))
+        };
+        let (mx, my) = view::mouse_position();
+        Self {
+            up: is_key_pressed(KeyCode::Up) || pad(Button::DPadUp),
+            down: is_key_pressed(KeyCode::Down) || pad(Button::DPadDown),
+            left: is_key_pressed(KeyCode::Left) || pad(Button::DPadLeft),
+            right: is_key_pressed(KeyCode::Right) || pad(Button::DPadRight),
+            activate: is_key_pressed(KeyCode::Enter) || pad(Button::ActionDown),
+            back: is_key_pressed(KeyCode::Escape) || pad(Button::ActionRight),
+            mouse: vec2(mx, my),
+            clicked: is_mouse_button_pressed(MouseButton::Left),
+            held: is_mouse_button_down(MouseButton::Left),
+        }
+    }
+}
+
+/// Disposition des lignes d'un menu, empilées verticalement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    /// Abscisse du bord gauche des lignes.
+    pub x: f32,
+    /// Ordonnée de la première ligne.
+    pub y: f32,
+    /// Largeur des lignes.
+    pub width: f32,
+    /// Hauteur d'une ligne.
+    pub row_height: f32,
+    /// Espace entre deux lignes.
+    pub spacing: f32,
+}
+
+impl Layout {
+    /// Crée une disposition centrée horizontalement dans la vue.
+    ///
+    /// # Paramètres
+    /// - `top`: Ordonnée de la première ligne, en fraction de la hauteur de la vue.
+    pub fn centered(top: f32) -> Self {
+        let width = view::WIDTH * 0.4;
+        Self {
+            x: (view::WIDTH - width) / 2.0,
+            y: view::HEIGHT * top,
+            width,
+            row_height: view::HEIGHT * 0.08,
+            spacing: view::HEIGHT * 0.025,
+        }
+    }
+}
+
+/// Élément collecté pendant la frame, prêt à être dessiné.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    /// Zone occupée par l'élément.
+    rect: Rect,
+    /// Texte principal.
+    label: String,
+    /// Valeur affichée à droite, pour les curseurs, cases et listes.
+    value: Option<String>,
+    /// Remplissage de la jauge d'un curseur, de `0.0` à `1.0`.
+    fill: Option<f32>,
+    /// Indique si l'élément est un titre, sans interaction.
+    title: bool,
+    /// Indique si l'élément a le focus.
+    focused: bool,
+    /// Indique si la souris survole l'élément.
+    hovered: bool,
+}
+
+/// Menu conservant le focus d'une frame à l'autre.
+pub struct Menu {
+    /// Indice de l'élément qui a le focus.
+    focus: usize,
+    /// Nombre d'éléments interactifs lors de la dernière frame.
+    count: usize,
+    /// Position de la souris lors de la dernière frame.
+    mouse: Vec2,
+}
+
+impl Menu {
+    /// Crée un menu dont le premier élément a le focus.
+    pub fn new() -> Self {
+        Self {
+            focus: 0,
+            count: 0,
+            mouse: Vec2::ZERO,
+        }
+    }
+
+    /// Retourne l'indice de l'élément interactif qui a le focus.
+    pub fn get_focus(&self) -> usize {
+        self.focus
+    }
+
+    /// Déplace le focus, en revenant au début après le dernier élément.
+    ///
+    /// # Paramètres
+    /// - `down`: `true` pour descendre d'un élément, `false` pour monter.
+    /// - `count`: Nombre d'éléments interactifs du menu.
+    pub fn navigate(&mut self, down: bool, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.focus = if down {
+            (self.focus + 1) % count
+        } else {
+            (self.focus + count - 1) % count
+        };
+    }
+
+    /// Commence la description du menu pour la frame courante.
+    ///
+    /// # Paramètres
+    /// - `input`: Les entrées de la frame.
+    /// - `layout`: La disposition des lignes.
+    pub fn begin(&mut self, input: MenuInput, layout: Layout) -> Ui<'_> {
+        if input.up {
+            self.navigate(false, self.count);
+        }
+        if input.down {
+            self.navigate(true, self.count);
+        }
+        let mouse_moved = input.mouse != self.mouse;
+        self.mouse = input.mouse;
+
+        Ui {
+            menu: self,
+            input,
+            mouse_moved,
+            layout,
+            y: layout.y,
+            index: 0,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Description d'un menu pendant une frame.
+pub struct Ui<'a> {
+    /// Menu décrit.
+    menu: &'a mut Menu,
+    /// Entrées de la frame.
+    input: MenuInput,
+    /// Indique si la souris a bougé depuis la frame précédente.
+    mouse_moved: bool,
+    /// Disposition des lignes.
+    layout: Layout,
+    /// Ordonnée de la prochaine ligne.
+    y: f32,
+    /// Indice du prochain élément interactif.
+    index: usize,
+    /// Éléments collectés.
+    items: Vec<Item>,
+}
+
+impl Ui<'_> {
+    /// Ajoute un titre centré, sans interaction.
+    pub fn title(&mut self, text: &str) {
+        let rect = self.row();
+        self.items.push(Item {
+            rect,
+            label: text.to_string(),
+            value: None,
+            fill: None,
+            title: true,
+            focused: false,
+            hovered: false,
+        });
+    }
+
+    /// Ajoute un bouton.
+    ///
+    /// # Retour
+    /// `true` si le bouton a été validé pendant la frame.
+    pub fn button(&mut self, label: &str) -> bool {
+        let (rect, focused, hovered) = self.widget();
+        self.push(rect, label, None, None, focused, hovered);
+        self.activated(focused, hovered)
+    }
+
+    /// Ajoute un bouton qui affiche une valeur à droite de son texte.
+    ///
+    /// # Retour
+    /// `true` si le bouton a été validé pendant la frame.
+    pub fn entry(&mut self, label: &str, value: &str) -> bool {
+        let (rect, focused, hovered) = self.widget();
+        self.push(rect, label, Some(value.to_string()), None, focused, hovered);
+        self.activated(focused, hovered)
+    }
+
+    /// Ajoute une case à cocher, inversée par validation ou par gauche et droite.
+    ///
+    /// # Retour
+    /// `true` si la valeur a changé pendant la frame.
+    pub fn toggle(&mut self, label: &str, value: &mut bool) -> bool {
+        let (rect, focused, hovered) = self.widget();
+        let changed =
+            self.activated(focused, hovered) || (focused && (self.input.left || self.input.right));
+        if changed {
+            *value = !*value;
+        }
+        let text = if *value { "[x]" } else { "[ ]" };
+        self.push(rect, label, Some(text.to_string()), None, focused, hovered);
+        changed
+    }
+
+    /// Ajoute un curseur réglé par gauche et droite ou en faisant glisser la souris.
+    ///
+    /// # Paramètres
+    /// - `value`: La valeur réglée, ramenée dans `range`.
+    /// - `range`: Les valeurs minimale et maximale.
+    /// - `step`: Le pas d'un appui sur gauche ou droite.
+    ///
+    /// # Retour
+    /// `true` si la valeur a changé pendant la frame.
+    pub fn slider(
+        &mut self,
+        label: &str,
+        value: &mut f32,
+        range: RangeInclusive<f32>,
+        step: f32,
+    ) -> bool {
+        let (rect, focused, hovered) = self.widget();
+        let (min, max) = (*range.start(), *range.end());
+        let previous = *value;
+
+        if focused && self.input.left {
+            *value -= step;
+        }
+        if focused && self.input.right {
+            *value += step;
+        }
+        let bar = bar_rect(rect);
+        if hovered && self.input.held && self.input.mouse.x >= bar.x {
+            let fraction = ((self.input.mouse.x - bar.x) / bar.w).clamp(0.0, 1.0);
+            *value = min + ((max - min) * fraction / step).round() * step;
+        }
+        *value = value.clamp(min, max);
+
+        let decimals = if step.fract() == 0.0 { 0 } else { 2 };
+        let fill = if max > min {
+            (*value - min) / (max - min)
+        } else {
+            1.0
+        };
+        self.push(
+            rect,
+            label,
+            Some(format!("{:.*}", decimals, *value)),
+            Some(fill),
+            focused,
+            hovered,
+        );
+        *value != previous
+    }
+
+    /// Ajoute une liste de choix, parcourue par gauche et droite ou par validation.
+    ///
+    /// # Paramètres
+    /// - `selected`: L'indice du choix sélectionné dans `options`.
+    /// - `options`: Les choix possibles.
+    ///
+    /// # Retour
+    /// `true` si le choix a changé pendant la frame.
+    pub fn list(&mut self, label: &str, selected: &mut usize, options: &[&str]) -> bool {
+        let (rect, focused, hovered) = self.widget();
+        let count = options.len().max(1);
+        let previous = *selected;
+
+        if self.activated(focused, hovered) || (focused && self.input.right) {
+            *selected = (*selected + 1) % count;
+        } else if focused && self.input.left {
+            *selected = (*selected + count - 1) % count;
+        }
+
+        let text = options.get(*selected).copied().unwrap_or_default();
+        self.push(
+            rect,
+            label,
+            Some(format!("< {text} >")),
+            None,
+            focused,
+            hovered,
+        );
+        *selected != previous
+    }
+
+    /// Termine la description du menu et retourne les éléments à dessiner.
+    pub fn finish(self) -> Vec<Item> {
+        self.menu.count = self.index;
+        if self.menu.focus >= self.index {
+            self.menu.focus = self.index.saturating_sub(1);
+        }
+        self.items
+    }
+
+    /// Termine la description du menu et le dessine.
+    pub fn end(self) {
+        draw_items(&self.finish());
+    }
+
+    /// Réserve la prochaine ligne de la disposition.
+    fn row(&mut self) -> Rect {
+        let layout = self.layout;
+        let rect = Rect::new(layout.x, self.y, layout.width, layout.row_height);
+        self.y += layout.row_height + layout.spacing;
+        rect
+    }
+
+    /// Réserve la ligne d'un élément interactif et met à jour le focus selon la souris.
+    ///
+    /// # Retour
+    /// La zone de l'élément, puis s'il a le focus et s'il est survolé.
+    fn widget(&mut self) -> (Rect, bool, bool) {
+        let rect = self.row();
+        let hovered = rect.contains(self.input.mouse);
+        if hovered && (self.mouse_moved || self.input.clicked) {
+            self.menu.focus = self.index;
+        }
+        let focused = self.menu.focus == self.index;
+        self.index += 1;
+        (rect, focused, hovered)
+    }
+
+    /// Indique si un élément est validé au clavier, à la manette ou par un clic.
+    fn activated(&self, focused: bool, hovered: bool) -> bool {
+        (focused && self.input.activate) || (hovered && self.input.clicked)
+    }
+
+    /// Ajoute un élément interactif à la liste des éléments à dessiner.
+    fn push(
+        &mut self,
+        rect: Rect,
+        label: &str,
+        value: Option<String>,
+        fill: Option<f32>,
+        focused: bool,
+        hovered: bool,
+    ) {
+        self.items.push(Item {
+            rect,
+            label: label.to_string(),
+            value,
+            fill,
+            title: false,
+            focused,
+            hovered,
+        });
+    }
+}
+
+/// Calcule la zone de la jauge d'un curseur, dans la moitié droite de sa ligne.
+fn bar_rect(rect: Rect) -> Rect {
+    Rect::new(
+        rect.x + rect.w * 0.55,
+        rect.y + rect.h * 0.35,
+        rect.w * 0.4,
+        rect.h * 0.3,
+    )
+}
+
+/// Dessine les éléments d'un menu.
+///
+/// L'élément qui a le focus est encadré et plus clair ; l'élément survolé est éclairci.
+pub fn draw_items(items: &[Item]) {
+    for item in items {
+        let rect = item.rect;
+        let font_size = rect.h * 0.55;
+
+        if item.title {
+            let size = measure_text(&item.label, None, (rect.h * 0.9) as u16, 1.0);
+            draw_text(
+                &item.label,
+                rect.x + (rect.w - size.width) / 2.0,
+                rect.y + rect.h * 0.8,
+                rect.h * 0.9,
+                WHITE,
+            );
+            continue;
+        }
+
+        let background = if item.focused {
+            Color::from_rgba(60, 90, 150, 230)
+        } else if item.hovered {
+            Color::from_rgba(90, 90, 90, 220)
+        } else {
+            Color::from_rgba(60, 60, 60, 200)
+        };
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, background);
+        if item.focused {
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, WHITE);
+        }
+        draw_text(
+            &item.label,
+            rect.x + rect.w * 0.05,
+            rect.y + rect.h * 0.65,
+            font_size,
+            WHITE,
+        );
+
+        if let Some(fill) = item.fill {
+            let bar = bar_rect(rect);
+            draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+            draw_rectangle(bar.x, bar.y, bar.w * fill, bar.h, SKYBLUE);
+            if let Some(value) = &item.value {
+                draw_text(
+                    value,
+                    bar.x - rect.w * 0.12,
+                    rect.y + rect.h * 0.65,
+                    font_size,
+                    LIGHTGRAY,
+                );
+            }
+        } else if let Some(value) = &item.value {
+            let size = measure_text(value, None, font_size as u16, 1.0);
+            draw_text(
+                value,
+                rect.x + rect.w * 0.95 - size.width,
+                rect.y + rect.h * 0.65,
+                font_size,
+                LIGHTGRAY,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: Layout = Layout {
+        x: 100.0,
+        y: 100.0,
+        width: 200.0,
+        row_height: 40.0,
+        spacing: 10.0,
+    };
+
+    #[test]
+    fn test_keyboard_navigation_wraps() {
+        let mut menu = Menu::new();
+        let describe = |menu: &mut Menu, input: MenuInput| {
+            let mut ui = menu.begin(input, LAYOUT);
+            ui.title("Title");
+            let first = ui.button("First");
+            let second = ui.button("Second");
+            ui.finish();
+            (first, second)
+        };
+
+        describe(&mut menu, MenuInput::default());
+        let up = MenuInput {
+            up: true,
+            ..Default::default()
+        };
+        describe(&mut menu, up);
+        assert_eq!(menu.get_focus(), 1);
+
+        let activate = MenuInput {
+            activate: true,
+            ..Default::default()
+        };
+        assert_eq!(describe(&mut menu, activate), (false, true));
+    }
+
+    #[test]
+    fn test_mouse_hover_and_click() {
+        let mut menu = Menu::new();
+        // Deuxième ligne : y de 150 à 190
+        let input = MenuInput {
+            mouse: vec2(150.0, 160.0),
+            clicked: true,
+            ..Default::default()
+        };
+        let mut ui = menu.begin(input, LAYOUT);
+        let first = ui.button("First");
+        let second = ui.button("Second");
+        let items = ui.finish();
+
+        assert!(!first && second);
+        assert!(items[1].focused && items[1].hovered);
+        assert_eq!(menu.get_focus(), 1);
+    }
+
+    #[test]
+    fn test_slider_list_and_toggle_values() {
+        let mut menu = Menu::new();
+        let right = MenuInput {
+            right: true,
+            ..Default::default()
+        };
+        let mut volume = 0.95;
+        let mut ui = menu.begin(right, LAYOUT);
+        assert!(ui.slider("Volume", &mut volume, 0.0..=1.0, 0.1));
+        ui.finish();
+        assert_eq!(volume, 1.0);
+
+        let mut selected = 2;
+        let mut enabled = false;
+        menu.navigate(true, 3);
+        let mut ui = menu.begin(right, LAYOUT);
+        ui.slider("Volume", &mut volume, 0.0..=1.0, 0.1);
+        assert!(ui.list("Language", &mut selected, &["en", "fr", "de"]));
+        assert!(!ui.toggle("Option", &mut enabled));
+        let items = ui.finish();
+        assert_eq!(selected, 0);
+        assert_eq!(items[1].value.as_deref(), Some("< en >"));
+        assert_eq!(items[0].fill, Some(1.0));
+    }
+
+    #[test]
+    fn test_entry_shows_its_value() {
+        let mut menu = Menu::new();
+        let activate = MenuInput {
+            activate: true,
+            ..Default::default()
+        };
+        let mut ui = menu.begin(activate, LAYOUT);
+        assert!(ui.entry("Fire", "key:Space"));
+        let items = ui.finish();
+        assert_eq!(items[0].value.as_deref(), Some("key:Space"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Module de la résolution virtuelle pour un jeu Asteroids.
+//!
+//! Le jeu est dessiné dans une résolution logique fixe (`WIDTH` x `HEIGHT`), mise à
+//! l'échelle pour remplir la fenêtre en conservant ses proportions ; des bandes noires
+//! comblent l'espace restant. Les menus, la taille de la vue et les tailles des objets
+//! sont exprimés en unités logiques : redimensionner la fenêtre ne change que l'échelle.
+
+use macroquad::prelude::*;
+
+/// Largeur logique de la vue.
+pub const WIDTH: f32 = 1280.0;
+/// Hauteur logique de la vue.
+pub const HEIGHT: f32 = 720.0;
+
+/// Calcule la zone de la fenêtre occupée par la vue logique.
+///
+/// # Paramètres
+/// - `window`: Les dimensions de la fenêtre, en pixels.
+///
+/// # Retour
+/// Le rectangle centré, aux proportions de la vue logique, le plus grand possible.
+pub fn letterbox(window: Vec2) -> Rect {
+    let scale = (window.x / WIDTH).min(window.y / HEIGHT);
+    let size = vec2(WIDTH, HEIGHT) * scale;
+    Rect::new(
+        (window.x - size.x) / 2.0,
+        (window.y - size.y) / 2.0,
+        size.x,
+        size.y,
+    )
+}
+
+/// Crée une caméra qui affiche une zone logique dans la partie utile de la fenêtre.
+///
+/// # Paramètres
+/// - `area`: La zone affichée, de la taille de la vue logique.
+pub fn camera(area: Rect) -> Camera2D {
+    let window = vec2(screen_width(), screen_height());
+    let target = letterbox(window);
+    let mut camera = Camera2D::from_display_rect(area);
+    // La zone d'affichage d'OpenGL part du coin inférieur gauche de la fenêtre
+    camera.viewport = Some((
+        target.x as i32,
+        (window.y - target.y - target.h) as i32,
+        target.w as i32,
+        target.h as i32,
+    ));
+    camera
+}
+
+/// Caméra des menus et de l'interface, en coordonnées logiques.
+fn screen_camera() -> Camera2D {
+    camera(Rect::new(0.0, 0.0, WIDTH, HEIGHT))
+}
+
+/// Active la caméra des menus et de l'interface.
+///
+/// À appeler au début de chaque frame : Macroquad rétablit la caméra par défaut
+/// à chaque nouvelle frame.
+pub fn set_screen_camera() {
+    set_camera(&screen_camera());
+}
+
+/// Retourne la position de la souris en coordonnées logiques.
+pub fn mouse_position() -> (f32, f32) {
+    let (x, y) = macroquad::input::mouse_position();
+    let position = screen_camera().screen_to_world(vec2(x, y));
+    (position.x, position.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letterbox() {
+        assert_eq!(
+            letterbox(vec2(WIDTH, HEIGHT)),
+            Rect::new(0.0, 0.0, WIDTH, HEIGHT)
+        );
+
+        // Fenêtre trop large : bandes noires à gauche et à droite
+        let wide = letterbox(vec2(2000.0, 720.0));
+        assert_eq!((wide.x, wide.w, wide.h), (360.0, WIDTH, HEIGHT));
+
+        // Fenêtre trop haute : bandes noires en haut et en bas
+        let tall = letterbox(vec2(640.0, 1000.0));
+        assert_eq!((tall.x, tall.y, tall.w, tall.h), (0.0, 320.0, 640.0, 360.0));
+    }
+}